@@ -1,4 +1,4 @@
-import {helloworld, Controller, Observer} from "./index.js"
+import {helloworld, Controller, Observer, Captor, HotkeyManager, TextExpander, Remapper} from "./index.js"
 import type {ExtraKey, KeyEv, KeyCombination} from "./index.js"
 
 // region 可用性测试
@@ -52,6 +52,15 @@ ct.mouseScroll(1, true)  // 向右一个单位
 
 // 12. 获取当前鼠标位置 (以主屏幕左上角为原点)
 const position = ct.mouseLocation()
+
+// 13. 多屏幕: 坐标相对于指定屏幕的左上角 (屏幕信息与 `Captor.listScreens()` 相同)
+const [, second] = ct.listScreens()
+ct.mouseMove({x: 100, y: 100}, {screen: second.id})
+const inSecond = ct.mouseLocation({screen: second.id})
+
+// 14. 绝对定位默认限制在可见桌面内 (超出时移至最近的屏幕边缘), 可通过 `clamp: false` 关闭; 相对定位默认不限制, 可通过 `clamp: true` 开启
+ct.mouseMove({x: -10_000, y: 100}, {clamp: false})
+ct.mouseMove({x: 10_000, y: 0}, {relative: true, clamp: true})
 // endregion
 
 // region 监听
@@ -85,7 +94,13 @@ ob.onKey({key: 'KeyA', extra: {ctrl: true, shift: true, alt: true, meta: true},
     console.log('按键 ctrl + shift + alt + meta + A 被按下')
 })
 
-// 4. 取消监听按键
+// 3.1 同一组合键可注册多个回调 (按注册顺序触发), 返回句柄用于单独取消; `onceKey` 仅触发一次
+const saveA = ob.onKey({key: 'KeyS', extra: {ctrl: true}, down: true}, () => console.log('模块 A: 保存'))
+const saveB = ob.onKey({key: 'KeyS', extra: {ctrl: true}, down: true}, () => console.log('模块 B: 保存'))
+ob.onceKey({key: 'Escape', down: true}, () => console.log('仅第一次按下 Escape 时触发'))
+ob.off(saveA)  // => true -- 模块 B 的回调不受影响
+
+// 4. 取消监听按键 (移除该组合键上的全部回调)
 ob.offKey({key: 'KeyA'})
 ob.offKey({key: 'KeyA', down: true})
 
@@ -95,12 +110,39 @@ ob.offKey({key: 'KeyA', extra: {ctrl: true, shift: true}})
 ob.offKey({key: 'KeyA', extra: {ctrl: true, shift: true, alt: true}})
 ob.offKey({key: 'KeyA', extra: {ctrl: true, shift: true, alt: true, meta: true}})
 
-// 6. 监听所有按键事件
+// 6. 监听所有按键事件 (`extra` 为事件发生后的修饰键状态; `state` 区分左右修饰键, 并包含锁定键状态)
 ob.onKeyAll((err, key_ev) => {
     console.log(`按键 ${key_ev.key} 被 ${key_ev.down ? '按下' : '释放'}`)
+    if (key_ev.extra?.ctrl) console.log(key_ev.state?.ctrlRight ? '右 ctrl 按下中' : '左 ctrl 按下中')
+    if (key_ev.state?.capsLock) console.log('大写锁定已开启')
+    // 无法识别的按键 (媒体键 / F13 及以上等) 以 `Unknown` 交付, `code` 为原始键码
+    if (key_ev.key === 'Unknown') console.log(`未知按键: ${key_ev.code}`)
+    // 长按产生的自动重复按下同样交付, `isRepeat` 为 `true` (其他监听均忽略重复按下)
+    if (key_ev.isRepeat) return
 })
 
-// 7. 取消监听所有按键事件
+// 只监听下一个按键事件 (如 "按下任意键以设置快捷键")
+ob.onceKeyAll((err, key_ev) => console.log(`已录入 ${key_ev.key}`))
+
+// 6.1 监听失败 (如 macOS 未授予辅助功能权限), 失败后不会再有按键事件
+ob.onError((err, reason) => console.error(reason))
+
+// 6.2 监听按键序列 (相邻两步的间隔不超过 `timeoutMs`, 默认 1000ms; 修饰键的按下不打断序列)
+const gg = ob.onSequence([{key: 'KeyG'}, {key: 'KeyG'}], {timeoutMs: 500}, () => console.log('g g'))
+ob.onSequence([{key: 'KeyK', extra: {ctrl: true}}, {key: 'KeyS', extra: {ctrl: true}}], null, () => console.log('ctrl + K, ctrl + S'))
+ob.pendingSequences  // => [{id, matched: [{key: 'KeyK', ...}], next: {key: 'KeyS', ...}, remainingMs: 800}] -- 可用于提示 "等待下一个按键"
+ob.off(gg)
+
+// 6.3 双击 / 长按 (`Shift` / `Control` / `Alt` 不区分左右), 返回句柄用于 `ob.off(id)`
+ob.onDoubleTap('Control', 300, () => console.log('双击 ctrl: 打开快速搜索'))
+ob.onLongPress('Space', 800, () => console.log('长按空格'))
+
+// 6.4 拦截组合键: 不再送达前台应用 (避免全局快捷键同时输入到当前窗口), 本进程内的监听不受影响
+ob.grab({key: 'KeyS', extra: {ctrl: true, alt: true}})
+ob.onKey({key: 'KeyS', extra: {ctrl: true, alt: true}, down: true}, () => console.log('仅由本应用处理'))
+ob.ungrab({key: 'KeyS', extra: {ctrl: true, alt: true}})  // `ob.dispose()` 时自动解除
+
+// 7. 取消监听所有按键事件 (`onKeyAll` 同样返回句柄, 可通过 `ob.off(id)` 单独取消)
 ob.offKeyAll()
 
 // 8. 主动触发一次监听回调
@@ -114,4 +156,133 @@ ob.registeredKeys  // KeyEv[]
 
 // 10. 结束监听 (必须手动调用以释放引用, 否则当前线程会始终存活且阻止所有处于注册状态的事件回调函数触发gc)
 ob.dispose()
+
+// 11. 具名快捷键 (按下时触发); 与已注册的快捷键或系统快捷键相同时抛出异常, 指定 `force` 强制注册
+const hk = new HotkeyManager()
+hk.register('editor.save', 'editor', {key: 'KeyS', extra: {ctrl: true}}, () => console.log('保存'))
+hk.conflicts({key: 'KeyS', extra: {ctrl: true, shift: true}})  // => [{kind: 'subsumed', name: 'editor.save', owner: 'editor', ...}]
+hk.register('plugin.saveAll', 'plugin', {key: 'KeyS', extra: {ctrl: true, shift: true}}, () => {})  // 返回 `subsumed` 提示, 不阻止注册
+hk.rebind('editor.save', {key: 'KeyS', extra: {ctrl: true, alt: true}})
+hk.bindings  // => [{name, owner, combination}]
+
+// 11.1 导出 / 导入绑定 (JSON); 导入时已注册的快捷键立即改绑, 未注册的在注册时生效
+const json = hk.exportJson()
+hk.importJson(json)  // => 冲突列表 (仅提示)
+hk.unregister('plugin.saveAll')
+hk.dispose()  // 同 `ob.dispose()`, 必须手动调用
+
+// 12. 缩写展开 (输入缩写后自动删除并输出展开结果; `;Sig` / `;SIG` 分别展开为首字母大写 / 全部大写)
+const te = new TextExpander({method: 'type', excludedApps: ['KeePassXC']})
+te.add({abbreviation: ';sig', expansion: 'Best regards,\nAlice'})
+te.add({abbreviation: ';d', expansion: '{date:%Y/%m/%d}'})
+te.add({abbreviation: ';quote', expansion: '> {clipboard}\n\n{cursor}', apps: ['Code', 'thunderbird']})  // 仅在指定应用中生效
+TextExpander.activeApp()  // => 当前前台应用名, 用于填写 `apps` / `excludedApps`
+te.enabled = false  // 暂停
+te.dispose()  // 同 `ob.dispose()`, 必须手动调用
+
+// 13. 按键重映射 (源按键不再送达前台应用, 改为输出其他按键; 输出的按键不能再被重映射)
+const rm = new Remapper({tappingTermMs: 200})
+rm.map({from: {key: 'CapsLock'}, action: {keys: {key: 'Escape'}}})  // 被拦截的 CapsLock 不切换大小写
+// 13.1 双重功能键: 点击 -- Esc; 按住 (超过 200ms 或期间按下其他键, 期间按下的键在按下 Ctrl 后重放) -- Ctrl
+rm.map({from: {key: 'CapsLock'}, action: {keys: {key: 'Escape'}}, hold: {keys: {key: 'Control'}}})
+// 13.2 层: 点击 Tab 仍为 Tab, 按住 Tab 时 HJKL 为方向键; F12 开启 / 关闭数字层
+rm.map({from: {key: 'Tab'}, action: {keys: {key: 'Tab'}}, hold: {layer: 'nav'}})
+// rm.map({from: {key: 'Space'}, action: {keys: {key: 'Space'}}})  // => 抛出异常: 输出的 Space 会被再次重映射
+rm.map({from: {key: 'KeyH'}, layer: 'nav', action: {keys: {key: 'ArrowLeft'}}})
+rm.map({from: {key: 'KeyL'}, layer: 'nav', action: {keys: {key: 'ArrowRight'}}})
+rm.map({from: {key: 'F12'}, action: {toggleLayer: 'num'}})
+rm.map({from: {key: 'KeyJ'}, layer: 'num', action: {keys: {key: 'Digit4'}}})
+rm.toggleLayer('num')  // => true
+rm.activeLayers  // => ['num']
+rm.unmap({key: 'KeyJ'}, 'num')
+rm.rules  // => 已注册的规则
+rm.enabled = false  // 暂停 (解除所有拦截)
+rm.dispose()  // 同 `ob.dispose()`, 必须手动调用
+// endregion
+
+// region 截图
+// 1. 获取所有屏幕信息 (id / 位置 / 尺寸 / 缩放比例 / 旋转角度 / 刷新率 / 是否主屏幕)
+const screens = Captor.listScreens()
+
+// 2. 截取指定屏幕 (png)
+const shot = Captor.captureScreen(screens[0].id)
+
+// 3. 截取全部屏幕 (png[], 任一屏幕失败即抛出异常, `err.code` 为失败原因)
+const shots = Captor.captureAllScreen()
+
+// 3.1 逐个截取全部屏幕 (单个屏幕失败不影响其他屏幕)
+Captor.captureEachScreen().forEach(({id, data, code}) => {
+    console.log(data ? `屏幕 ${id} 截图成功` : `屏幕 ${id} 截图失败: ${code}`)
+})
+
+// 4. 截取虚拟桌面中的矩形区域 (png, 可跨越多个屏幕)
+const area = Captor.captureArea({x: 100, y: 100, w: 400, h: 300})
+
+// 5. 指定输出格式 (rgba / bgra 为原始像素, 附带 stride; png 可指定压缩等级; jpeg / webp 可指定质量; qoi)
+const raw = Captor.captureScreenImage(screens[0].id, {format: 'bgra'})  // => {w, h, stride, bytes, format}
+const jpeg = Captor.captureAreaImage({x: 0, y: 0, w: 400, h: 300}, {format: 'jpeg', quality: 75})
+
+// 5.0 在截图中绘制光标 / 单独获取光标图像及热点
+const withCursor = Captor.captureScreenImage(screens[0].id, {format: 'png'}, true)
+const cursor = Captor.cursorImage()  // => {x, y, hotX, hotY, image} | null (光标被隐藏; macOS 上抛出 `unsupported` 异常)
+
+// 5.1 读取指定点的颜色 (虚拟桌面坐标) -- 可配合 `ct.mouseLocation()` 实现取色
+const color = Captor.pixelAt(ct.mouseLocation())  // => {r, g, b, a}
+const colors = Captor.pixelsAt([{x: 10, y: 10}, {x: 20, y: 20}])
+
+// 5.2 以图找图 (模板可为 RawImage 或 png / jpeg 等图像文件的字节), 结果按匹配度从高到低排列
+const [button] = Captor.findImage(require('fs').readFileSync('button.png'), {threshold: 0.9, grayscale: true, scales: [1, 1.5]})
+if (button) {
+    ct.mouseMove({x: button.x + button.w / 2, y: button.y + button.h / 2})
+    ct.mouseClick('Left')
+}
+
+// 5.3 等待屏幕状态 (原生轮询, 超时后 reject, `err.code` 为 `timeout`)
+Captor.waitForImage(require('fs').readFileSync('button.png'), 10_000).then(({x, y}) => ct.mouseMove({x, y}))
+Captor.waitForPixel({x: 10, y: 10}, {r: 0, g: 255, b: 0, a: 255}, 8, 5_000).then(color => console.log('变绿了', color))
+Captor.waitForRegionChange({x: 0, y: 0, w: 400, h: 300}, 5_000, 50).then(rects => console.log('变化区域', rects))
+
+// 6. 连续截图 (子线程中按帧率截图; js 处理不及时时丢帧, `frame.dropped` 为丢弃的帧数)
+const stream = Captor.startStream({screenId: screens[0].id, fps: 30, format: {format: 'rgba'}, cursor: true}, (err, frame) => {
+    if (!err) console.log(`第 ${frame.seq} 帧: ${frame.image.w}x${frame.image.h}`)
+})
+setTimeout(() => stream.stop(), 5_000)  // 必须手动结束
+// 截图失败时退避重试; 遇到无法恢复的错误 (如 `invalid-arg` / `permission-denied`) 时交付该错误 (`err.code` 为失败原因) 后自行结束, `stream.running` 变为 `false`
+
+// 7. 连续截图 (仅交付与上一帧相比发生变化的区域)
+const diffStream = Captor.startDiffStream({fps: 30}, 64, (err, frame) => {
+    if (!err && frame.changed) frame.rects.forEach(({x, y, image}) => console.log(`变化区域: (${x}, ${y}) ${image.w}x${image.h}`))
+})
+setTimeout(() => diffStream.stop(), 5_000)
+
+// 8. 坐标换算 (所有接口均使用逻辑坐标; 截图为物理像素, 缩放比例见 `screens[i].scaleFactor`)
+const physical = Captor.toPhysical(ct.mouseLocation())  // => {screenId, x, y} -- 鼠标在所在屏幕截图中的像素位置
+ct.mouseMove(Captor.toLogical({screenId: screens[0].id, x: 300, y: 200}))  // 点击截图中 (300, 200) 处的像素
+
+// 9. 标注截图 (依次绘制, 坐标为图像像素坐标), 返回 png
+const report = Captor.annotate(Captor.captureScreen(screens[0].id), [
+    {kind: 'pixelate', rect: {x: 0, y: 0, w: 400, h: 40}, size: 12},  // 打码
+    {kind: 'blur', rect: {x: 0, y: 40, w: 400, h: 200}},
+    {kind: 'rect', rect: {x: 500, y: 300, w: 200, h: 80}, color: {r: 255, g: 0, b: 0, a: 255}, thickness: 3},
+    {kind: 'arrow', from: {x: 900, y: 600}, to: {x: 705, y: 385}},
+    {kind: 'text', at: {x: 500, y: 270}, text: 'Bug here!', fill: {r: 255, g: 255, b: 255, a: 200}},
+    {kind: 'crop', rect: {x: 0, y: 0, w: 1000, h: 700}},
+])
+
+// 10. 文字识别 (需以 `--features ocr` 编译, 模型文件需提前下载到本地), 结果为虚拟桌面坐标; 未指定模型时以 `invalid-arg` reject, 模型加载失败时以 `model-load-failed` reject, 识别失败时以 `recognize-failed` reject
+Captor.recognizeText({x: 0, y: 0, w: 800, h: 600}, {
+    lang: 'eng',
+    detectionModel: './models/text-detection.rten',
+    recognitionModel: './models/text-recognition.rten',
+}).then(words => {
+    const save = words.find(word => word.text === 'Save' && word.confidence > 0.5)
+    if (save) {
+        ct.mouseMove({x: save.x + save.w / 2, y: save.y + save.h / 2})
+        ct.mouseClick('Left')
+    }
+})
+
+// 11. 录屏 (编码按扩展名推断: `.gif` 为动图, 其余为 motion-JPEG 的 AVI)
+const recorder = Captor.record({path: './ticket.avi', screenId: screens[0].id, fps: 15, cursor: true})
+setTimeout(() => recorder.stop().then(({path, frames, duration, truncated}) => console.log(`已录制 ${path}: ${frames} 帧, ${duration}ms${truncated ? ' (已达到 1 GB 上限)' : ''}`)), 10_000)
 // endregion
\ No newline at end of file
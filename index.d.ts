@@ -15,18 +15,96 @@ export interface ExtraKey {
 /** 组合键情况 (目标键 + 辅助键) */
 export interface KeyCombination {
   /** 目标键 */
-  key: 'F1' | 'F2' | 'F3' | 'F4' | 'F5' | 'F6' | 'F7' | 'F8' | 'F9' | 'F10' | 'F11' | 'F12' | 'Digit0' | 'Digit1' | 'Digit2' | 'Digit3' | 'Digit4' | 'Digit5' | 'Digit6' | 'Digit7' | 'Digit8' | 'Digit9' | 'KeyA' | 'KeyB' | 'KeyC' | 'KeyD' | 'KeyE' | 'KeyF' | 'KeyG' | 'KeyH' | 'KeyI' | 'KeyJ' | 'KeyK' | 'KeyL' | 'KeyM' | 'KeyN' | 'KeyO' | 'KeyP' | 'KeyQ' | 'KeyR' | 'KeyS' | 'KeyT' | 'KeyU' | 'KeyV' | 'KeyW' | 'KeyX' | 'KeyY' | 'KeyZ' | 'Meta' | 'Escape' | 'Tab' | 'CapsLock' | 'Shift' | 'Control' | 'Alt' | 'Space' | 'ArrowUp' | 'ArrowRight' | 'ArrowDown' | 'ArrowLeft' | 'Enter' | 'Backspace' | 'Delete' | 'Home' | 'PageUp' | 'PageDown' | 'End' | 'Backquote' | 'Minus' | 'Equal' | 'BracketLeft' | 'BracketRight' | 'Comma' | 'Period' | 'Semicolon' | 'Quote' | 'Slash' | 'BackSlash' | 'ShiftLeft' | 'ShiftRight' | 'ControlLeft' | 'ControlRight' | 'AltLeft' | 'AltRight' | 'Numpad0' | 'Numpad1' | 'Numpad2' | 'Numpad3' | 'Numpad4' | 'Numpad5' | 'Numpad6' | 'Numpad7' | 'Numpad8' | 'Numpad9' | 'NumpadAdd' | 'NumpadSubtract' | 'NumpadMultiply' | 'NumpadDivide'
+  key: 'F1' | 'F2' | 'F3' | 'F4' | 'F5' | 'F6' | 'F7' | 'F8' | 'F9' | 'F10' | 'F11' | 'F12' | 'Digit0' | 'Digit1' | 'Digit2' | 'Digit3' | 'Digit4' | 'Digit5' | 'Digit6' | 'Digit7' | 'Digit8' | 'Digit9' | 'KeyA' | 'KeyB' | 'KeyC' | 'KeyD' | 'KeyE' | 'KeyF' | 'KeyG' | 'KeyH' | 'KeyI' | 'KeyJ' | 'KeyK' | 'KeyL' | 'KeyM' | 'KeyN' | 'KeyO' | 'KeyP' | 'KeyQ' | 'KeyR' | 'KeyS' | 'KeyT' | 'KeyU' | 'KeyV' | 'KeyW' | 'KeyX' | 'KeyY' | 'KeyZ' | 'Meta' | 'Escape' | 'Tab' | 'CapsLock' | 'Shift' | 'Control' | 'Alt' | 'Space' | 'ArrowUp' | 'ArrowRight' | 'ArrowDown' | 'ArrowLeft' | 'Enter' | 'Backspace' | 'Delete' | 'Home' | 'PageUp' | 'PageDown' | 'End' | 'Backquote' | 'Minus' | 'Equal' | 'BracketLeft' | 'BracketRight' | 'Comma' | 'Period' | 'Semicolon' | 'Quote' | 'Slash' | 'BackSlash' | 'IntlBackslash' | 'Insert' | 'PrintScreen' | 'Pause' | 'NumLock' | 'ScrollLock' | 'Fn' | 'ShiftLeft' | 'ShiftRight' | 'ControlLeft' | 'ControlRight' | 'AltLeft' | 'AltRight' | 'Numpad0' | 'Numpad1' | 'Numpad2' | 'Numpad3' | 'Numpad4' | 'Numpad5' | 'Numpad6' | 'Numpad7' | 'Numpad8' | 'Numpad9' | 'NumpadAdd' | 'NumpadSubtract' | 'NumpadMultiply' | 'NumpadDivide' | 'NumpadEnter' | 'NumpadDecimal'
   /** 辅助键 见[ExtraKey] */
   extra?: ExtraKey
 }
+/** 具名快捷键绑定 (见 `HotkeyManager`) */
+export interface HotkeyBinding {
+  /** 快捷键名称 (唯一) */
+  name: string
+  /** 注册方 (如插件名) */
+  owner: string
+  /** 组合键 (在目标键按下时触发) */
+  combination: KeyCombination
+}
+/** 快捷键冲突 */
+export interface HotkeyConflict {
+  /** 冲突类型: 与已注册的快捷键相同 / 与已注册的快捷键互相包含 (如 `ctrl + K` 与 `ctrl + shift + K`) / 与系统快捷键相同 */
+  kind: 'duplicate' | 'subsumed' | 'system'
+  /** 冲突的快捷键名称 (`kind` 为 `'system'` 时为 null) */
+  name?: string
+  /** 冲突的快捷键注册方 (`kind` 为 `'system'` 时为 null) */
+  owner?: string
+  /** 冲突的组合键 */
+  combination: KeyCombination
+  /** 说明 */
+  description: string
+}
+/** 按键序列选项 */
+export interface SequenceOptions {
+  /** 相邻两步之间的最大间隔 (毫秒), 默认 `1000` */
+  timeoutMs?: number
+}
+/** 进行中的按键序列 */
+export interface PendingSequence {
+  /** `onSequence` 返回的句柄 */
+  id: number
+  /** 已按下的步骤 */
+  matched: Array<KeyCombination>
+  /** 等待按下的下一步 */
+  next: KeyCombination
+  /** 剩余等待时间 (毫秒) */
+  remainingMs: number
+}
+/** 缩写展开规则 (见 `TextExpander`) */
+export interface Expansion {
+  /** 缩写 (如 `;sig`), 输入完成时立即展开 */
+  abbreviation: string
+  /** 展开后的文本, 支持占位符: `{date}` / `{time}` / `{date:%Y/%m/%d}` (strftime 格式) / `{clipboard}` / `{cursor}` (展开后光标位置); `{{` / `}}` 为花括号本身 */
+  expansion: string
+  /** 仅在这些应用中生效 (进程名 / 应用名, 不区分大小写, 忽略 `.exe`), 默认在所有应用中生效 */
+  apps?: Array<string>
+}
+/** 缩写展开选项 */
+export interface ExpanderOptions {
+  /** 输出方式: 模拟键盘输入 / 经剪贴板粘贴 (粘贴后恢复剪贴板中原有的文本), 默认 `'type'` */
+  method?: 'type' | 'paste'
+  /** 是否按缩写的大小写调整展开结果 (`;Sig` -- 首字母大写, `;SIG` -- 全部大写), 默认 `true` */
+  propagateCase?: boolean
+  /** 不生效的应用 (如密码管理器 / 终端) */
+  excludedApps?: Array<string>
+}
+/** 事件发生后的修饰键 (区分左右) 与锁定键状态 */
+export interface KeyState {
+  ctrlLeft: boolean
+  ctrlRight: boolean
+  altLeft: boolean
+  altRight: boolean
+  shiftLeft: boolean
+  shiftRight: boolean
+  metaLeft: boolean
+  metaRight: boolean
+  capsLock: boolean
+  /** macos 下始终为 `false` */
+  numLock: boolean
+  /** macos 下始终为 `false` */
+  scrollLock: boolean
+}
 /** 按键事件 (目标键 + 辅助键 + 按键状态) */
 export interface KeyEv {
   /** 目标键 */
-  key: 'F1' | 'F2' | 'F3' | 'F4' | 'F5' | 'F6' | 'F7' | 'F8' | 'F9' | 'F10' | 'F11' | 'F12' | 'Digit0' | 'Digit1' | 'Digit2' | 'Digit3' | 'Digit4' | 'Digit5' | 'Digit6' | 'Digit7' | 'Digit8' | 'Digit9' | 'KeyA' | 'KeyB' | 'KeyC' | 'KeyD' | 'KeyE' | 'KeyF' | 'KeyG' | 'KeyH' | 'KeyI' | 'KeyJ' | 'KeyK' | 'KeyL' | 'KeyM' | 'KeyN' | 'KeyO' | 'KeyP' | 'KeyQ' | 'KeyR' | 'KeyS' | 'KeyT' | 'KeyU' | 'KeyV' | 'KeyW' | 'KeyX' | 'KeyY' | 'KeyZ' | 'Meta' | 'Escape' | 'Tab' | 'CapsLock' | 'Shift' | 'Control' | 'Alt' | 'Space' | 'ArrowUp' | 'ArrowRight' | 'ArrowDown' | 'ArrowLeft' | 'Enter' | 'Backspace' | 'Delete' | 'Home' | 'PageUp' | 'PageDown' | 'End' | 'Backquote' | 'Minus' | 'Equal' | 'BracketLeft' | 'BracketRight' | 'Comma' | 'Period' | 'Semicolon' | 'Quote' | 'Slash' | 'BackSlash' | 'ShiftLeft' | 'ShiftRight' | 'ControlLeft' | 'ControlRight' | 'AltLeft' | 'AltRight' | 'Numpad0' | 'Numpad1' | 'Numpad2' | 'Numpad3' | 'Numpad4' | 'Numpad5' | 'Numpad6' | 'Numpad7' | 'Numpad8' | 'Numpad9' | 'NumpadAdd' | 'NumpadSubtract' | 'NumpadMultiply' | 'NumpadDivide'
+  key: 'F1' | 'F2' | 'F3' | 'F4' | 'F5' | 'F6' | 'F7' | 'F8' | 'F9' | 'F10' | 'F11' | 'F12' | 'Digit0' | 'Digit1' | 'Digit2' | 'Digit3' | 'Digit4' | 'Digit5' | 'Digit6' | 'Digit7' | 'Digit8' | 'Digit9' | 'KeyA' | 'KeyB' | 'KeyC' | 'KeyD' | 'KeyE' | 'KeyF' | 'KeyG' | 'KeyH' | 'KeyI' | 'KeyJ' | 'KeyK' | 'KeyL' | 'KeyM' | 'KeyN' | 'KeyO' | 'KeyP' | 'KeyQ' | 'KeyR' | 'KeyS' | 'KeyT' | 'KeyU' | 'KeyV' | 'KeyW' | 'KeyX' | 'KeyY' | 'KeyZ' | 'Meta' | 'Escape' | 'Tab' | 'CapsLock' | 'Shift' | 'Control' | 'Alt' | 'Space' | 'ArrowUp' | 'ArrowRight' | 'ArrowDown' | 'ArrowLeft' | 'Enter' | 'Backspace' | 'Delete' | 'Home' | 'PageUp' | 'PageDown' | 'End' | 'Backquote' | 'Minus' | 'Equal' | 'BracketLeft' | 'BracketRight' | 'Comma' | 'Period' | 'Semicolon' | 'Quote' | 'Slash' | 'BackSlash' | 'IntlBackslash' | 'Insert' | 'PrintScreen' | 'Pause' | 'NumLock' | 'ScrollLock' | 'Fn' | 'ShiftLeft' | 'ShiftRight' | 'ControlLeft' | 'ControlRight' | 'AltLeft' | 'AltRight' | 'Numpad0' | 'Numpad1' | 'Numpad2' | 'Numpad3' | 'Numpad4' | 'Numpad5' | 'Numpad6' | 'Numpad7' | 'Numpad8' | 'Numpad9' | 'NumpadAdd' | 'NumpadSubtract' | 'NumpadMultiply' | 'NumpadDivide' | 'NumpadEnter' | 'NumpadDecimal'
   /** 辅助键 见[ExtraKey] */
   extra?: ExtraKey
   /** 是否是按下状态 (默认为 `false`) */
   down?: boolean
+  /** 修饰键与锁定键的详细状态 (仅由 `onKeyAll` 回调提供, 注册时忽略) */
+  state?: KeyState
+  /** 未知按键 (`key` 为 `'Unknown'`) 的原始键码 (仅由 `onKeyAll` 回调提供, 注册时忽略) */
+  code?: number
+  /** 是否是长按产生的自动重复按下 (仅由 `onKeyAll` 回调提供, 注册时忽略) */
+  isRepeat?: boolean
 }
 /** 鼠标事件 (目标键 + 按键状态) */
 export interface MouseEv {
@@ -41,6 +119,49 @@ export interface MouseLocation {
   /** y 方向 (`i32`) */
   y: number
 }
+/** 鼠标移动选项 */
+export interface MouseMoveOptions {
+  /** 是否使用相对定位 (相对当前鼠标位置), 默认 `false` */
+  relative?: boolean
+  /** 坐标相对于指定 id 的屏幕左上角 (仅绝对定位时有效, 默认相对于主屏幕左上角) */
+  screen?: number
+  /** 是否将目标位置限制在可见桌面内, 绝对定位默认 `true`, 相对定位默认 `false` */
+  clamp?: boolean
+}
+/** 鼠标位置选项 */
+export interface MouseLocationOptions {
+  /** 返回相对于指定 id 的屏幕左上角的坐标 (默认相对于主屏幕左上角) */
+  screen?: number
+}
+/** 单个屏幕的截图结果 (`data` 与 `code` 有且仅有一个不为 null) */
+export interface ScreenCapture {
+  /** 屏幕唯一标识 */
+  id: number
+  /** 截图数据 (png) */
+  data?: Array<number>
+  /** 失败原因 (`'no-display' | 'permission-denied' | 'capture-failed' | 'encode-failed' | 'invalid-arg' | 'timeout'`) */
+  code?: string
+  /** 失败详情 */
+  message?: string
+}
+/** 颜色 (RGBA, 各分量 0 ~ 255) */
+export interface Color {
+  r: number
+  g: number
+  b: number
+  a: number
+}
+/** 矩形区域 (虚拟桌面坐标, 以主屏幕左上角为原点) */
+export interface Rect {
+  /** 左上角 x 坐标 */
+  x: number
+  /** 左上角 y 坐标 */
+  y: number
+  /** 宽度 */
+  w: number
+  /** 高度 */
+  h: number
+}
 /** 图像数据 */
 export interface RawImage {
   /** 图像原始宽度 */
@@ -49,6 +170,221 @@ export interface RawImage {
   h: number
   /** 图像字节 */
   bytes: Array<number>
+  /** 每行字节数 (仅原始像素格式 `rgba` / `bgra` 有值) */
+  stride?: number
+  /** 图像格式 (默认为 `rgba`) */
+  format?: 'rgba' | 'bgra' | 'png' | 'jpeg' | 'webp' | 'qoi'
+}
+/** 图像编码选项 */
+export interface EncodeOptions {
+  /** 图像格式 (`rgba` / `bgra` 为原始像素) */
+  format: 'rgba' | 'bgra' | 'png' | 'jpeg' | 'webp' | 'qoi'
+  /** png 压缩等级 (0 ~ 9, 默认为 `6`) */
+  compression?: number
+  /** jpeg / webp 质量 (1 ~ 100, 默认为 `80`; webp 为 `100` 时使用无损编码) */
+  quality?: number
+}
+/** 屏幕信息 */
+export interface ScreenInfo {
+  /** 屏幕唯一标识 */
+  id: number
+  /** 屏幕左上角在虚拟桌面中的 x 坐标 */
+  x: number
+  /** 屏幕左上角在虚拟桌面中的 y 坐标 */
+  y: number
+  /** 屏幕宽度 */
+  width: number
+  /** 屏幕高度 */
+  height: number
+  /** 缩放比例 (如 `1.5` 表示 150%) */
+  scaleFactor: number
+  /** 顺时针旋转角度 (0 / 90 / 180 / 270) */
+  rotation: number
+  /** 刷新率 (Hz) */
+  refreshRate: number
+  /** 是否是主屏幕 */
+  isPrimary: boolean
+}
+/** 物理坐标 (截图中的像素坐标, 相对于所在屏幕的左上角) */
+export interface PhysicalPoint {
+  /** 所在屏幕的 id */
+  screenId: number
+  /** x 方向 (像素) */
+  x: number
+  /** y 方向 (像素) */
+  y: number
+}
+/** 连续截图选项 */
+export interface StreamOptions {
+  /** 屏幕 id (默认为主屏幕, 指定 `region` 时忽略) */
+  screenId?: number
+  /** 帧率 (1 ~ 120, 默认为 `30`) */
+  fps?: number
+  /** 截取区域 (虚拟桌面坐标) */
+  region?: Rect
+  /** 输出格式 (默认为 `rgba`) */
+  format?: EncodeOptions
+  /** 是否绘制光标 (默认为 `false`; 无法读取光标时不绘制) */
+  cursor?: boolean
+}
+/** 连续截图的单帧 */
+export interface StreamFrame {
+  /** 帧序号 (从 `0` 开始, 含被丢弃的帧) */
+  seq: number
+  /** 截取时间 (unix 时间戳, ms) */
+  timestamp: number
+  /** 自上一帧以来因 js 处理不及时而丢弃的帧数 */
+  dropped: number
+  /** 图像数据 */
+  image: RawImage
+}
+/** 录屏选项 */
+export interface RecordOptions {
+  /** 输出文件路径 */
+  path: string
+  /** 屏幕 id (默认为主屏幕, 指定 `region` 时忽略) */
+  screenId?: number
+  /** 录制区域 (虚拟桌面坐标) */
+  region?: Rect
+  /** 帧率 (默认为 `15`; `gif` 最高为 `50`) */
+  fps?: number
+  /** 编码 (`mjpeg` 输出 AVI 文件; 默认按文件扩展名推断, `.gif` 为 `gif`, 其余为 `mjpeg`) */
+  codec?: 'mjpeg' | 'gif'
+  /** `mjpeg` 的压缩质量 (1 ~ 100, 默认为 `80`) */
+  quality?: number
+  /** 是否绘制光标 (默认为 `false`; 无法读取光标时不绘制) */
+  cursor?: boolean
+}
+/** 录制完成的文件 */
+export interface Recording {
+  /** 文件路径 */
+  path: string
+  /** 编码 */
+  codec: string
+  /** 画面宽度 (像素) */
+  w: number
+  /** 画面高度 (像素) */
+  h: number
+  /** 实际截取的帧数 */
+  frames: number
+  /** 时长 (ms) */
+  duration: number
+  /** 是否因文件达到大小上限 (AVI 为 1 GB) 而提前结束 */
+  truncated: boolean
+}
+/** 发生变化的区域 (相对于整帧左上角) */
+export interface DirtyRect {
+  /** 左上角 x 坐标 */
+  x: number
+  /** 左上角 y 坐标 */
+  y: number
+  /** 区域内的图像数据 */
+  image: RawImage
+}
+/** 差异比较模式下的单帧 */
+export interface DiffFrame {
+  /** 帧序号 (从 `0` 开始, 含被丢弃的帧) */
+  seq: number
+  /** 截取时间 (unix 时间戳, ms) */
+  timestamp: number
+  /** 自上一帧以来因 js 处理不及时而丢弃的帧数 */
+  dropped: number
+  /** 整帧宽度 */
+  w: number
+  /** 整帧高度 */
+  h: number
+  /** 与上一帧相比是否有变化 (首帧总为 `true`, 且 `rects` 为整帧) */
+  changed: boolean
+  /** 发生变化的区域 */
+  rects: Array<DirtyRect>
+}
+/** 以图找图选项 */
+export interface FindImageOptions {
+  /** 查找区域 (虚拟桌面坐标, 默认为整个虚拟桌面) */
+  region?: Rect
+  /** 最低匹配度 (0 ~ 1, 默认为 `0.9`; 纯色模板按颜色的接近程度计算) */
+  threshold?: number
+  /** 是否按灰度匹配 (更快, 但忽略颜色差异; 默认为 `false`) */
+  grayscale?: boolean
+  /** 模板的缩放比例 (默认为 `[1]`) */
+  scales?: Array<number>
+}
+/** 以图找图的匹配结果 (虚拟桌面坐标) */
+export interface ImageMatch {
+  /** 左上角 x 坐标 */
+  x: number
+  /** 左上角 y 坐标 */
+  y: number
+  /** 宽度 */
+  w: number
+  /** 高度 */
+  h: number
+  /** 匹配度 (归一化互相关系数, 越接近 `1` 越相似) */
+  confidence: number
+  /** 匹配时模板的缩放比例 */
+  scale: number
+}
+/** 光标信息 */
+export interface CursorInfo {
+  /** 光标位置 x (虚拟桌面坐标, 即热点所在位置) */
+  x: number
+  /** 光标位置 y (虚拟桌面坐标, 即热点所在位置) */
+  y: number
+  /** 热点相对于光标图像左上角的 x 偏移 */
+  hotX: number
+  /** 热点相对于光标图像左上角的 y 偏移 */
+  hotY: number
+  /** 光标图像 (rgba) */
+  image: RawImage
+}
+/** 标注 (坐标均为图像像素坐标, 以图像左上角为原点) */
+export interface Annotation {
+  /** 标注类型: 矩形框 / 箭头 / 文字 / 模糊 / 马赛克 / 裁剪 (裁剪后, 其后的标注以裁剪后的图像为准) */
+  kind: 'rect' | 'arrow' | 'text' | 'blur' | 'pixelate' | 'crop'
+  /** 作用区域 (`rect` / `blur` / `pixelate` / `crop` 必填) */
+  rect?: Rect
+  /** 箭头起点 (`arrow` 必填) */
+  from?: MouseLocation
+  /** 箭头终点, 即箭头所指处 (`arrow` 必填) */
+  to?: MouseLocation
+  /** 文字左上角位置 (`text` 必填) */
+  at?: MouseLocation
+  /** 文字内容 (`text` 必填, 仅支持 ASCII 字符, 其余字符显示为方框; `\n` 换行) */
+  text?: string
+  /** 线条 / 文字颜色 (默认为红色) */
+  color?: Color
+  /** 填充色 (`rect` 的内部 / `text` 的背景, 默认不填充) */
+  fill?: Color
+  /** 线宽 (默认为 `2`) */
+  thickness?: number
+  /** `text`: 字号倍数 (默认为 `2`, 即每个字 10x14 像素); `blur`: 模糊半径 (默认为 `8`); `pixelate`: 色块边长 (默认为 `10`) */
+  size?: number
+}
+/** 文字识别选项 */
+export interface OcrOptions {
+  /** 语言 (默认为 `eng`, 目前仅支持拉丁字母的英文) */
+  lang?: string
+  /** 文字检测模型 (`text-detection.rten`) 的本地路径 */
+  detectionModel?: string
+  /** 文字识别模型 (`text-recognition.rten`) 的本地路径 */
+  recognitionModel?: string
+}
+/** 识别出的单词 (位置为虚拟桌面坐标) */
+export interface RecognizedWord {
+  /** 文字内容 */
+  text: string
+  /** 左上角 x 坐标 */
+  x: number
+  /** 左上角 y 坐标 */
+  y: number
+  /** 宽度 */
+  w: number
+  /** 高度 */
+  h: number
+  /** 置信度 (0 ~ 1, 单词区域内各像素被检测为文字的平均概率) */
+  confidence: number
+  /** 所在行的序号 (从 0 开始, 同一行的单词序号相同) */
+  line: number
 }
 /** 剪切板单项的存储结构 */
 export interface ClipboardItem {
@@ -88,12 +424,14 @@ export class Controller {
   mouseScroll(scale: number, horizontal?: boolean | undefined | null): void
   /**
    * 鼠标 -- 移动
-   * `direction`: 移动方向 (默认为绝对定位: 屏幕左上角为原点,向右向下为正)
-   * `relative`: 是否使用相对定位(相对当前鼠标位置), 默认 `false`
+   * `direction`: 移动方向 (默认为绝对定位: 逻辑坐标, 主屏幕左上角为原点, 向右向下为正, 见 [crate::coords])
+   * `options`: 为 `boolean` 时表示是否使用相对定位(相对当前鼠标位置), 默认 `false`; 其余选项见 [MouseMoveOptions]
    */
-  mouseMove(direction: MouseLocation, relative?: boolean | undefined | null): void
-  /** 鼠标 -- 当前坐标 */
-  mouseLocation(): MouseLocation
+  mouseMove(direction: MouseLocation, options?: boolean | MouseMoveOptions | undefined | null): void
+  /** 鼠标 -- 当前坐标 (逻辑坐标, 默认相对于主屏幕左上角) */
+  mouseLocation(options?: MouseLocationOptions | undefined | null): MouseLocation
+  /** 获取所有屏幕的信息 (与 `Captor.listScreens` 相同) */
+  listScreens(): Array<ScreenInfo>
 }
 export class Observer {
   /** thread-safe function test */
@@ -101,19 +439,104 @@ export class Observer {
   constructor()
   /** 已注册的按键事件 (使用数组返回, 其值可视为集合, 无重复) */
   get registeredKeys(): Array<KeyEv>
-  /** 注册/更新按键监听事件 (支持组合键) */
-  onKey(keys: KeyEv, callback: (err: null | Error) => void): void
-  /** 移除已注册的监听 */
+  /** 注册按键监听事件 (支持组合键; 同一组合键可注册多个回调, 按注册顺序触发), 返回用于 `off` 的句柄 */
+  onKey(keys: KeyEv, callback: (err: null | Error) => void): number
+  /** 注册仅触发一次的按键监听事件, 返回用于 `off` 的句柄 */
+  onceKey(keys: KeyEv, callback: (err: null | Error) => void): number
+  /** 移除该组合键上的全部监听 */
   offKey(keys: KeyEv): void
-  /** 注册/更新对全部按键的监听事件 */
-  onKeyAll(callback: (err: null | Error, key_ev: { key: KeyEv['key'], down: boolean }) => void): void
-  /** 移除对全部按键的监听事件 */
+  /** 注册对全部按键的监听事件, 返回用于 `off` 的句柄 */
+  onKeyAll(callback: (err: null | Error, key_ev: KeyEv) => void): number
+  /** 注册仅触发一次 (下一个按键事件) 的全部按键监听事件, 返回用于 `off` 的句柄 */
+  onceKeyAll(callback: (err: null | Error, key_ev: KeyEv) => void): number
+  /** 移除对全部按键的全部监听 */
   offKeyAll(): void
+  /** 注册按键序列监听 (如 `g g` / `ctrl + K, ctrl + S`, 每一步均在按下时匹配), 返回用于 `off` 的句柄 */
+  onSequence(steps: Array<KeyCombination>, options: SequenceOptions | undefined | null, callback: (err: null | Error) => void): number
+  /** 注册双击监听 (两次按下的间隔不超过 `max_gap_ms`, 期间未按下其他键; `Shift` / `Control` / `Alt` 不区分左右), 返回用于 `off` 的句柄 */
+  onDoubleTap(key: string, maxGapMs: number, callback: (err: null | Error) => void): number
+  /** 注册长按监听 (按下并保持 `hold_ms` 后触发, 每次按下最多触发一次; `Shift` / `Control` / `Alt` 不区分左右), 返回用于 `off` 的句柄 */
+  onLongPress(key: string, holdMs: number, callback: (err: null | Error) => void): number
+  /** 进行中的按键序列 (已按下第一步且未超时) */
+  get pendingSequences(): Array<PendingSequence>
+  /** 按句柄移除单个监听 (返回值表示该句柄是否仍处于注册状态) */
+  off(id: number): boolean
+  /** 注册/更新监听失败的回调 (如 macOS 未授予辅助功能权限; 失败后不会再有按键事件) */
+  onError(callback: (err: null | Error, reason: string) => void): void
+  /** 移除监听失败的回调 */
+  offError(): void
+  /**
+   * 拦截组合键: 按下时不再送达前台应用, 但仍会触发本进程内的监听 (`Shift` / `Control` / `Alt` 不区分左右)
+   * - linux (X11): 组合键已被其他应用抢占时抛出异常
+   * - macos: 需要辅助功能权限
+   */
+  grab(keys: KeyCombination): void
+  /** 解除本实例对组合键的拦截 (返回值表示此前是否已拦截) */
+  ungrab(keys: KeyCombination): boolean
   /** 主动触发已注册的按键事件 (返回值表示该组合键是否已注册) */
   touch(keys: KeyEv): boolean
   /** 结束监听 (必须调用! 否则会由于过度持有引用造成内存泄露) */
   dispose(): void
 }
+export class HotkeyManager {
+  constructor()
+  /**
+   * 注册具名快捷键 (按下 `combination` 时触发), 返回检测到的冲突
+   * 与已注册的快捷键相同或被系统占用时抛出异常, `force` 为 `true` 时强制注册; 已导入同名绑定时以导入的组合键为准
+   */
+  register(name: string, owner: string, combination: KeyCombination, callback: (err: null | Error) => void): Array<HotkeyConflict>
+  /** 移除具名快捷键 (返回值表示此前是否已注册) */
+  unregister(name: string): boolean
+  /** 修改已注册快捷键的组合键 (冲突规则与 `register` 相同) */
+  rebind(name: string, combination: KeyCombination, force?: boolean | undefined | null): Array<HotkeyConflict>
+  /** `combination` 与已注册的快捷键及系统快捷键的冲突 */
+  conflicts(combination: KeyCombination): Array<HotkeyConflict>
+  /** 已注册的快捷键及其所属模块 (按注册顺序) */
+  get bindings(): Array<HotkeyBinding>
+  /** 导出已注册 (及已导入但尚未注册) 的绑定, 格式为 `HotkeyBinding` 数组的 JSON */
+  exportJson(): string
+  /** 导入 `exportJson` 导出的绑定: 已注册的快捷键直接修改 (冲突仅返回, 不拒绝), 其余的在注册同名快捷键时生效 */
+  importJson(json: string): Array<HotkeyConflict>
+  /** 结束监听并释放全部回调 (与 `Observer.dispose` 相同, 必须调用!) */
+  dispose(): void
+}
+export class TextExpander {
+  constructor(options?: ExpanderOptions | undefined | null)
+  /** 添加缩写 (替换缩写相同的规则; 展开文本含未知或格式有误的占位符时抛出异常) */
+  add(expansion: Expansion): void
+  /** 移除缩写 (返回值表示此前是否已添加) */
+  remove(abbreviation: string): boolean
+  /** 已添加的规则 (按添加顺序) */
+  get expansions(): Array<Expansion>
+  /** 是否启用展开 (默认为 `true`) */
+  get enabled(): boolean
+  set enabled(enabled: boolean)
+  /** 前台应用的名称 (即 `apps` / `excludedApps` 匹配的名称, 无法获取时为 null) */
+  static activeApp(): string | null
+  /** 结束监听 (与 `Observer.dispose` 相同, 必须调用!) */
+  dispose(): void
+}
+export class Remapper {
+  constructor(options?: RemapperOptions | undefined | null)
+  /**
+   * 添加规则 (替换同一层中 `from` 相同的规则)
+   * 输出的按键本身被重映射 (会被再次重映射) 或无法拦截源按键时抛出异常
+   */
+  map(rule: RemapRule): void
+  /** 移除 `layer` (默认为基础层) 中 `from` 的规则 (返回值表示此前是否已添加) */
+  unmap(from: KeyCombination, layer?: string | undefined | null): boolean
+  /** 已添加的规则 (按添加顺序) */
+  get rules(): Array<RemapRule>
+  /** 已激活的层 (按激活顺序) */
+  get activeLayers(): Array<string>
+  /** 切换层的激活状态 (与 `toggleLayer` 动作相同), 返回值表示切换后是否已激活 */
+  toggleLayer(layer: string): boolean
+  /** 是否启用重映射 (默认为 `true`; 禁用时解除全部拦截并关闭已激活的层) */
+  get enabled(): boolean
+  set enabled(enabled: boolean)
+  /** 结束重映射并解除全部拦截 (与 `Observer.dispose` 相同, 必须调用!) */
+  dispose(): void
+}
 export class Clipboard {
   /** 队列的当前长度 */
   get len(): number
@@ -159,3 +582,132 @@ export class Clipboard {
    */
   putImage(image: RawImage): number
 }
+export class Captor {
+  /**
+   * Capture images of all of the user's screen
+   *
+   * Throws if any of the screens fails, `err.code` is one of
+   * `'no-display' | 'permission-denied' | 'capture-failed' | 'encode-failed' | 'invalid-arg'`
+   */
+  static captureAllScreen(): Array<Array<number>>
+  /**
+   * Capture images of all of the user's screen one by one, a failed screen does not affect the others
+   *
+   * Each item carries either `data` or `code` + `message`
+   */
+  static captureEachScreen(): Array<ScreenCapture>
+  /** List all of the user's screens (id, bounds, scale factor, rotation, refresh rate, is-primary) */
+  static listScreens(): Array<ScreenInfo>
+  /** Capture image (png) of the screen with the given `id`, see [Captor::list_screens] */
+  static captureScreen(id: number): Array<number>
+  /** Capture image (png) of the `area` in global virtual-desktop coordinates, the area may span several screens */
+  static captureArea(area: Rect): Array<number>
+  /**
+   * Capture image of the screen with the given `id` in the given format, the mouse cursor is drawn if `with_cursor` is `true`
+   *
+   * Raw formats (`rgba` / `bgra`) come with `stride`, encoded formats (`png` / `jpeg` / `webp` / `qoi`) do not
+   */
+  static captureScreenImage(id: number, options: EncodeOptions, withCursor?: boolean | undefined | null): RawImage
+  /** Capture image of the `area` in the given format, see [Captor::capture_area] and [Captor::capture_screen_image] */
+  static captureAreaImage(area: Rect, options: EncodeOptions, withCursor?: boolean | undefined | null): RawImage
+  /**
+   * Read the current mouse cursor: its bitmap (rgba), hotspot and position in global virtual-desktop coordinates
+   *
+   * Returns `null` if the cursor is hidden. Throws with code `unsupported` on platforms where the system cursor
+   * bitmap cannot be read (macOS).
+   */
+  static cursorImage(): CursorInfo | null
+  /**
+   * Draw `annotations` (rectangles, arrows, text labels, blur / pixelate and crop) in order onto `image`
+   * (a [RawImage] or the bytes of a png / jpeg / webp / qoi file), returns the result as png
+   *
+   * Coordinates are pixels of the image, a `crop` affects the annotations after it
+   */
+  static annotate(image: RawImage | Buffer, annotations: Array<Annotation>): Array<number>
+  /**
+   * Recognize text (on-device OCR) in `region` (global virtual-desktop coordinates),
+   * resolves with the words and their bounding boxes in global virtual-desktop coordinates
+   *
+   * Requires the `ocr` cargo feature (rejects with code `unsupported` otherwise) and local model files (see [OcrOptions]):
+   * rejects with code `invalid-arg` if a model path is missing, `model-load-failed` if a model cannot be loaded
+   * and `recognize-failed` if the engine fails on the captured image.
+   */
+  static recognizeText(region: Rect, options?: OcrOptions | undefined | null): Promise<Array<RecognizedWord>>
+  /**
+   * Convert `point` in logical (global virtual-desktop) coordinates to physical pixels of the screen containing it,
+   * i.e. the pixel position inside that screen's capture, see [crate::coords]
+   */
+  static toPhysical(point: MouseLocation): PhysicalPoint
+  /**
+   * Convert `point` in physical pixels of a screen (e.g. a position inside a capture of that screen)
+   * to logical (global virtual-desktop) coordinates, which can be passed to `Controller.mouseMove` directly
+   */
+  static toLogical(point: PhysicalPoint): MouseLocation
+  /** Read the color (RGBA) at `point` in global virtual-desktop coordinates */
+  static pixelAt(point: MouseLocation): Color
+  /** Read the colors (RGBA) at `points` in global virtual-desktop coordinates, in the same order */
+  static pixelsAt(points: Array<MouseLocation>): Array<Color>
+  /**
+   * Find `template` (a [RawImage] or the bytes of a png / jpeg / webp / qoi file) on screen
+   *
+   * Matches are compared by normalized cross-correlation and sorted by `confidence` (highest first),
+   * the rectangles are in global virtual-desktop coordinates so they can be passed to `Controller.mouseMove` directly.
+   */
+  static findImage(template: RawImage | Buffer, options?: FindImageOptions | undefined | null): Array<ImageMatch>
+  /**
+   * Wait until `template` appears on screen, resolves with the best match, see [Captor::find_image]
+   *
+   * The screen is polled natively every `intervalMs` (defaults to `100`),
+   * rejects with code `timeout` if nothing matched within `timeoutMs`.
+   */
+  static waitForImage(template: RawImage | Buffer, timeoutMs: number, options?: FindImageOptions | undefined | null, intervalMs?: number | undefined | null): Promise<ImageMatch>
+  /**
+   * Wait until the color at `point` matches `color`, resolves with the actual color
+   *
+   * `tolerance` is the maximum difference allowed on each of the R / G / B channels (defaults to `0`, alpha is ignored),
+   * rejects with code `timeout` if the color did not match within `timeoutMs`.
+   */
+  static waitForPixel(point: MouseLocation, color: Color, tolerance: number | undefined | null, timeoutMs: number, intervalMs?: number | undefined | null): Promise<Color>
+  /**
+   * Wait until anything changes inside `region`, resolves with the changed rectangles in global virtual-desktop coordinates
+   *
+   * Rejects with code `timeout` if nothing changed within `timeoutMs`.
+   */
+  static waitForRegionChange(region: Rect, timeoutMs: number, intervalMs?: number | undefined | null): Promise<Array<Rect>>
+  /**
+   * Start capturing continuously on a native thread, frames are delivered to `callback`
+   *
+   * Frames are dropped while the previous one has not been handled by js yet (see `frame.dropped`),
+   * call `stop()` on the returned stream to end it.
+   */
+  static startStream(options: StreamOptions, callback: (err: null | Error, frame: StreamFrame) => void): CaptureStream
+  /**
+   * Start capturing continuously like [Captor::start_stream], but only the rectangles that changed since
+   * the previous frame are delivered (compared tile by tile, `tileSize` defaults to `64`)
+   *
+   * `frame.changed` is `false` and `frame.rects` is empty when nothing changed.
+   */
+  static startDiffStream(options: StreamOptions, tileSize: number | undefined | null, callback: (err: null | Error, frame: DiffFrame) => void): CaptureStream
+  /**
+   * Record the screen (or `region`) to `path` as a motion-JPEG AVI or an animated GIF, using pure-Rust encoders
+   *
+   * The first frame is captured and the file is created immediately (throws on a bad argument or path),
+   * call `stop()` on the returned recorder to finish the file, it resolves with the [Recording].
+   * AVI files are limited to 1 GB, the recording ends at that size and `stop()` resolves with `truncated: true`.
+   */
+  static record(options: RecordOptions): ScreenRecorder
+}
+/** 连续截图 (由 [crate::capture::Captor::start_stream] 创建) */
+export class CaptureStream {
+  /** 是否正在截图 (遇到无法恢复的错误 -- 如屏幕不存在 / 没有权限 -- 时, 交付该错误后自行结束) */
+  get running(): boolean
+  /** 结束截图 (必须调用! 否则子线程会一直持有回调函数) */
+  stop(): void
+}
+/** 录屏 (由 [crate::capture::Captor::record] 创建) */
+export class ScreenRecorder {
+  /** 是否正在录制 (录制过程中出错或文件达到大小上限时自动结束) */
+  get running(): boolean
+  /** 结束录制, 写完文件后 resolve (必须调用! 否则会一直录制; 已因文件达到大小上限而结束时, 同样 resolve 录制完成的文件) */
+  stop(): Promise<Recording>
+}
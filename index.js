@@ -246,11 +246,17 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { Controller, Observer, Clipboard, checkKey, checkMouse, helloworld } = nativeBinding
+const { Controller, Observer, HotkeyManager, TextExpander, Remapper, Clipboard, Captor, CaptureStream, ScreenRecorder, checkKey, checkMouse, helloworld } = nativeBinding
 
 module.exports.Controller = Controller
 module.exports.Observer = Observer
+module.exports.HotkeyManager = HotkeyManager
+module.exports.TextExpander = TextExpander
+module.exports.Remapper = Remapper
 module.exports.Clipboard = Clipboard
+module.exports.Captor = Captor
+module.exports.CaptureStream = CaptureStream
+module.exports.ScreenRecorder = ScreenRecorder
 module.exports.checkKey = checkKey
 module.exports.checkMouse = checkMouse
 module.exports.helloworld = helloworld
@@ -0,0 +1,446 @@
+use napi::Error;
+use crate::capture::{CaptureErrorCode, CaptureResult};
+use crate::frame::Frame;
+use crate::utils::{Annotation, Color, MouseLocation, Rect};
+
+/// 默认颜色 (红色)
+const DEFAULT_COLOR: Color = Color { r: 255, g: 0, b: 0, a: 255 };
+/// 默认线宽
+const DEFAULT_THICKNESS: u32 = 2;
+/// 默认字号倍数
+const DEFAULT_FONT_SCALE: u32 = 2;
+/// 默认模糊半径
+const DEFAULT_BLUR_RADIUS: u32 = 8;
+/// 默认马赛克色块边长
+const DEFAULT_BLOCK_SIZE: u32 = 10;
+
+/// 5x7 点阵字体 (ASCII 0x20 ~ 0x7E), 每个字符 5 列, 每列的第 0 位为最上方的像素
+static FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], [0x00, 0x00, 0x5F, 0x00, 0x00], [0x00, 0x07, 0x00, 0x07, 0x00], [0x14, 0x7F, 0x14, 0x7F, 0x14],
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], [0x23, 0x13, 0x08, 0x64, 0x62], [0x36, 0x49, 0x55, 0x22, 0x50], [0x00, 0x05, 0x03, 0x00, 0x00],
+    [0x00, 0x1C, 0x22, 0x41, 0x00], [0x00, 0x41, 0x22, 0x1C, 0x00], [0x08, 0x2A, 0x1C, 0x2A, 0x08], [0x08, 0x08, 0x3E, 0x08, 0x08],
+    [0x00, 0x50, 0x30, 0x00, 0x00], [0x08, 0x08, 0x08, 0x08, 0x08], [0x00, 0x60, 0x60, 0x00, 0x00], [0x20, 0x10, 0x08, 0x04, 0x02],
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], [0x00, 0x42, 0x7F, 0x40, 0x00], [0x42, 0x61, 0x51, 0x49, 0x46], [0x21, 0x41, 0x45, 0x4B, 0x31],
+    [0x18, 0x14, 0x12, 0x7F, 0x10], [0x27, 0x45, 0x45, 0x45, 0x39], [0x3C, 0x4A, 0x49, 0x49, 0x30], [0x01, 0x71, 0x09, 0x05, 0x03],
+    [0x36, 0x49, 0x49, 0x49, 0x36], [0x06, 0x49, 0x49, 0x29, 0x1E], [0x00, 0x36, 0x36, 0x00, 0x00], [0x00, 0x56, 0x36, 0x00, 0x00],
+    [0x08, 0x14, 0x22, 0x41, 0x00], [0x14, 0x14, 0x14, 0x14, 0x14], [0x00, 0x41, 0x22, 0x14, 0x08], [0x02, 0x01, 0x51, 0x09, 0x06],
+    [0x32, 0x49, 0x79, 0x41, 0x3E], [0x7E, 0x11, 0x11, 0x11, 0x7E], [0x7F, 0x49, 0x49, 0x49, 0x36], [0x3E, 0x41, 0x41, 0x41, 0x22],
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], [0x7F, 0x49, 0x49, 0x49, 0x41], [0x7F, 0x09, 0x09, 0x01, 0x01], [0x3E, 0x41, 0x41, 0x51, 0x32],
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], [0x00, 0x41, 0x7F, 0x41, 0x00], [0x20, 0x40, 0x41, 0x3F, 0x01], [0x7F, 0x08, 0x14, 0x22, 0x41],
+    [0x7F, 0x40, 0x40, 0x40, 0x40], [0x7F, 0x02, 0x04, 0x02, 0x7F], [0x7F, 0x04, 0x08, 0x10, 0x7F], [0x3E, 0x41, 0x41, 0x41, 0x3E],
+    [0x7F, 0x09, 0x09, 0x09, 0x06], [0x3E, 0x41, 0x51, 0x21, 0x5E], [0x7F, 0x09, 0x19, 0x29, 0x46], [0x46, 0x49, 0x49, 0x49, 0x31],
+    [0x01, 0x01, 0x7F, 0x01, 0x01], [0x3F, 0x40, 0x40, 0x40, 0x3F], [0x1F, 0x20, 0x40, 0x20, 0x1F], [0x7F, 0x20, 0x18, 0x20, 0x7F],
+    [0x63, 0x14, 0x08, 0x14, 0x63], [0x03, 0x04, 0x78, 0x04, 0x03], [0x61, 0x51, 0x49, 0x45, 0x43], [0x00, 0x00, 0x7F, 0x41, 0x41],
+    [0x02, 0x04, 0x08, 0x10, 0x20], [0x41, 0x41, 0x7F, 0x00, 0x00], [0x04, 0x02, 0x01, 0x02, 0x04], [0x40, 0x40, 0x40, 0x40, 0x40],
+    [0x00, 0x01, 0x02, 0x04, 0x00], [0x20, 0x54, 0x54, 0x54, 0x78], [0x7F, 0x48, 0x44, 0x44, 0x38], [0x38, 0x44, 0x44, 0x44, 0x20],
+    [0x38, 0x44, 0x44, 0x48, 0x7F], [0x38, 0x54, 0x54, 0x54, 0x18], [0x08, 0x7E, 0x09, 0x01, 0x02], [0x08, 0x14, 0x54, 0x54, 0x3C],
+    [0x7F, 0x08, 0x04, 0x04, 0x78], [0x00, 0x44, 0x7D, 0x40, 0x00], [0x20, 0x40, 0x44, 0x3D, 0x00], [0x00, 0x7F, 0x10, 0x28, 0x44],
+    [0x00, 0x41, 0x7F, 0x40, 0x00], [0x7C, 0x04, 0x18, 0x04, 0x78], [0x7C, 0x08, 0x04, 0x04, 0x78], [0x38, 0x44, 0x44, 0x44, 0x38],
+    [0x7C, 0x14, 0x14, 0x14, 0x08], [0x08, 0x14, 0x14, 0x18, 0x7C], [0x7C, 0x08, 0x04, 0x04, 0x08], [0x48, 0x54, 0x54, 0x54, 0x20],
+    [0x04, 0x3F, 0x44, 0x40, 0x20], [0x3C, 0x40, 0x40, 0x20, 0x7C], [0x1C, 0x20, 0x40, 0x20, 0x1C], [0x3C, 0x40, 0x30, 0x40, 0x3C],
+    [0x44, 0x28, 0x10, 0x28, 0x44], [0x0C, 0x50, 0x50, 0x50, 0x3C], [0x44, 0x64, 0x54, 0x4C, 0x44], [0x00, 0x08, 0x36, 0x41, 0x00],
+    [0x00, 0x00, 0x7F, 0x00, 0x00], [0x00, 0x41, 0x36, 0x08, 0x00], [0x02, 0x01, 0x02, 0x04, 0x02],
+];
+/// 无法显示的字符以方框代替
+static MISSING_GLYPH: [u8; 5] = [0x7F, 0x41, 0x41, 0x41, 0x7F];
+
+/// 字符的点阵
+fn glyph(ch: char) -> &'static [u8; 5] {
+    match ch {
+        ' '..='~' => &FONT[ch as usize - 0x20],
+        _ => &MISSING_GLYPH
+    }
+}
+
+/// 将 `color` 按透明度混合到 (x, y) 处 (超出范围时忽略)
+fn put(frame: &mut Frame, x: i64, y: i64, color: &Color) {
+    if x < 0 || y < 0 || x >= frame.w as i64 || y >= frame.h as i64 {
+        return;
+    }
+
+    let idx = ((y * frame.w as i64 + x) * 4) as usize;
+    let alpha = color.a as u32;
+    for (c, src) in [color.r, color.g, color.b].into_iter().enumerate() {
+        let dst = frame.rgba[idx + c] as u32;
+        frame.rgba[idx + c] = ((src as u32 * alpha + dst * (255 - alpha)) / 255) as u8;
+    }
+    frame.rgba[idx + 3] = frame.rgba[idx + 3].max(color.a);
+}
+
+/// 将 (x, y, w, h) 裁剪到图像范围内 (为空时返回 `None`)
+fn clip(frame: &Frame, rect: &Rect) -> Option<(u32, u32, u32, u32)> {
+    let left = (rect.x as i64).clamp(0, frame.w as i64);
+    let top = (rect.y as i64).clamp(0, frame.h as i64);
+    let right = (rect.x as i64 + rect.w as i64).clamp(0, frame.w as i64);
+    let bottom = (rect.y as i64 + rect.h as i64).clamp(0, frame.h as i64);
+
+    if right <= left || bottom <= top {
+        None
+    } else {
+        Some((left as u32, top as u32, (right - left) as u32, (bottom - top) as u32))
+    }
+}
+
+/// 填充矩形
+pub fn fill_rect(frame: &mut Frame, x: i64, y: i64, w: i64, h: i64, color: &Color) {
+    for py in y.max(0)..(y + h).min(frame.h as i64) {
+        for px in x.max(0)..(x + w).min(frame.w as i64) {
+            put(frame, px, py, color);
+        }
+    }
+}
+
+/// 矩形框 (线条在矩形内侧, 各像素只绘制一次, 半透明颜色不会叠加)
+pub fn stroke_rect(frame: &mut Frame, x: i64, y: i64, w: i64, h: i64, thickness: i64, color: &Color) {
+    if thickness * 2 >= w || thickness * 2 >= h {
+        fill_rect(frame, x, y, w, h, color);
+        return;
+    }
+
+    fill_rect(frame, x, y, w, thickness, color);
+    fill_rect(frame, x, y + h - thickness, w, thickness, color);
+    fill_rect(frame, x, y + thickness, thickness, h - thickness * 2, color);
+    fill_rect(frame, x + w - thickness, y + thickness, thickness, h - thickness * 2, color);
+}
+
+/// 线段 (像素中心到线段的距离不超过线宽的一半即绘制)
+pub fn line(frame: &mut Frame, from: (f64, f64), to: (f64, f64), thickness: f64, color: &Color) {
+    let half = (thickness / 2.0).max(0.5);
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length_sq = dx * dx + dy * dy;
+
+    let left = (from.0.min(to.0) - half).floor() as i64;
+    let right = (from.0.max(to.0) + half).ceil() as i64;
+    let top = (from.1.min(to.1) - half).floor() as i64;
+    let bottom = (from.1.max(to.1) + half).ceil() as i64;
+
+    for py in top.max(0)..=bottom.min(frame.h as i64 - 1) {
+        for px in left.max(0)..=right.min(frame.w as i64 - 1) {
+            let (cx, cy) = (px as f64, py as f64);
+            let t = if length_sq == 0.0 { 0.0 } else { (((cx - from.0) * dx + (cy - from.1) * dy) / length_sq).clamp(0.0, 1.0) };
+            let (nx, ny) = (from.0 + t * dx - cx, from.1 + t * dy - cy);
+            if nx * nx + ny * ny <= half * half {
+                put(frame, px, py, color);
+            }
+        }
+    }
+}
+
+/// 填充三角形
+fn fill_triangle(frame: &mut Frame, points: [(f64, f64); 3], color: &Color) {
+    let edge = |a: (f64, f64), b: (f64, f64), p: (f64, f64)| (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0);
+    let [a, b, c] = points;
+
+    let left = a.0.min(b.0).min(c.0).floor() as i64;
+    let right = a.0.max(b.0).max(c.0).ceil() as i64;
+    let top = a.1.min(b.1).min(c.1).floor() as i64;
+    let bottom = a.1.max(b.1).max(c.1).ceil() as i64;
+
+    for py in top.max(0)..=bottom.min(frame.h as i64 - 1) {
+        for px in left.max(0)..=right.min(frame.w as i64 - 1) {
+            let p = (px as f64, py as f64);
+            let (e1, e2, e3) = (edge(a, b, p), edge(b, c, p), edge(c, a, p));
+            if (e1 >= 0.0 && e2 >= 0.0 && e3 >= 0.0) || (e1 <= 0.0 && e2 <= 0.0 && e3 <= 0.0) {
+                put(frame, px, py, color);
+            }
+        }
+    }
+}
+
+/// 箭头 (由 `from` 指向 `to`)
+pub fn arrow(frame: &mut Frame, from: (f64, f64), to: (f64, f64), thickness: f64, color: &Color) {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length < 1.0 {
+        return;
+    }
+
+    // 箭头头部的长度与宽度随线宽增大, 但不超过整体长度
+    let head = (thickness * 4.0).max(10.0).min(length);
+    let (ux, uy) = (dx / length, dy / length);
+    let base = (to.0 - ux * head, to.1 - uy * head);
+    let (nx, ny) = (-uy * head / 2.0, ux * head / 2.0);
+
+    if length > head {
+        line(frame, from, base, thickness, color);
+    }
+    fill_triangle(frame, [to, (base.0 + nx, base.1 + ny), (base.0 - nx, base.1 - ny)], color);
+}
+
+/// 文字 (左上角位于 (x, y), 每个字符占 `6 * scale` x `8 * scale` 像素, 含间距)
+pub fn text(frame: &mut Frame, x: i64, y: i64, content: &str, scale: u32, color: &Color, background: Option<&Color>) {
+    let scale = scale.max(1) as i64;
+    let (advance, line_height) = (6 * scale, 8 * scale);
+
+    if let Some(bg) = background {
+        let columns = content.lines().map(|l| l.chars().count()).max().unwrap_or(0) as i64;
+        let rows = content.lines().count().max(1) as i64;
+        fill_rect(frame, x - scale, y - scale, columns * advance + scale, rows * line_height + scale, bg);
+    }
+
+    for (row, content_line) in content.lines().enumerate() {
+        for (col, ch) in content_line.chars().enumerate() {
+            let (gx, gy) = (x + col as i64 * advance, y + row as i64 * line_height);
+            for (cx, bits) in glyph(ch).iter().enumerate() {
+                for cy in 0..7 {
+                    if bits >> cy & 1 == 1 {
+                        fill_rect(frame, gx + cx as i64 * scale, gy + cy * scale, scale, scale, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 一维盒式模糊 (边缘像素重复; 半径不超过该方向的长度)
+fn box_pass(src: &[u8], dst: &mut [u8], w: usize, h: usize, radius: usize, horizontal: bool) {
+    let (len, lines) = if horizontal { (w, h) } else { (h, w) };
+    let radius = radius.min(len);
+    let at = |line: usize, i: i64| {
+        let i = i.clamp(0, len as i64 - 1) as usize;
+        if horizontal { (line * w + i) * 4 } else { (i * w + line) * 4 }
+    };
+    let span = (radius * 2 + 1) as u64;
+    let r = radius as i64;
+
+    for line in 0..lines {
+        for c in 0..4 {
+            let mut sum: u64 = (-r..=r).map(|i| src[at(line, i) + c] as u64).sum();
+            for i in 0..len as i64 {
+                dst[at(line, i) + c] = (sum / span) as u8;
+                sum += src[at(line, i + r + 1) + c] as u64;
+                sum -= src[at(line, i - r) + c] as u64;
+            }
+        }
+    }
+}
+
+/// 将 `patch` 写回 (x, y) 处
+fn paste(frame: &mut Frame, patch: &Frame, x: u32, y: u32) {
+    for row in 0..patch.h {
+        let from = (row * patch.w * 4) as usize;
+        let to = (((y + row) * frame.w + x) * 4) as usize;
+        frame.rgba[to..to + (patch.w * 4) as usize].copy_from_slice(&patch.rgba[from..from + (patch.w * 4) as usize]);
+    }
+}
+
+/// 模糊区域 (三次盒式模糊, 近似高斯模糊)
+pub fn blur(frame: &mut Frame, rect: &Rect, radius: u32) {
+    let (x, y, w, h) = match clip(frame, rect) {
+        Some(v) => v,
+        None => return
+    };
+
+    let mut patch = frame.crop(x, y, w, h);
+    let mut buffer = patch.rgba.clone();
+    for _ in 0..3 {
+        box_pass(&patch.rgba, &mut buffer, w as usize, h as usize, radius as usize, true);
+        box_pass(&buffer, &mut patch.rgba, w as usize, h as usize, radius as usize, false);
+    }
+
+    paste(frame, &patch, x, y);
+}
+
+/// 马赛克 (每个 `block` x `block` 的色块取均值; 色块不超过区域大小)
+pub fn pixelate(frame: &mut Frame, rect: &Rect, block: u32) {
+    let (x, y, w, h) = match clip(frame, rect) {
+        Some(v) => v,
+        None => return
+    };
+    let block = block.clamp(1, w.max(h));
+
+    for by in (y..y + h).step_by(block as usize) {
+        for bx in (x..x + w).step_by(block as usize) {
+            let (bw, bh) = (block.min(x + w - bx), block.min(y + h - by));
+
+            let mut sum = [0u64; 4];
+            for py in by..by + bh {
+                for px in bx..bx + bw {
+                    let idx = ((py * frame.w + px) * 4) as usize;
+                    for c in 0..4 {
+                        sum[c] += frame.rgba[idx + c] as u64;
+                    }
+                }
+            }
+
+            let n = bw as u64 * bh as u64;
+            let mean = sum.map(|v| (v / n) as u8);
+            for py in by..by + bh {
+                for px in bx..bx + bw {
+                    let idx = ((py * frame.w + px) * 4) as usize;
+                    frame.rgba[idx..idx + 4].copy_from_slice(&mean);
+                }
+            }
+        }
+    }
+}
+
+/// 取出标注的必填项
+fn required<'a, T>(value: &'a Option<T>, idx: usize, item: &Annotation, field: &str) -> CaptureResult<&'a T> {
+    match value {
+        Some(v) => Ok(v),
+        None => Err(Error::new(CaptureErrorCode::InvalidArg, format!("The `{}` of annotation #{} ('{}') is required!", field, idx, item.kind)))
+    }
+}
+
+/// 点的坐标
+fn point_of(location: &MouseLocation) -> (f64, f64) {
+    (location.x as f64, location.y as f64)
+}
+
+/// 依次绘制标注
+pub fn apply(mut frame: Frame, annotations: &[Annotation]) -> CaptureResult<Frame> {
+    for (idx, item) in annotations.iter().enumerate() {
+        let color = match &item.color {
+            Some(v) => v.clone(),
+            None => DEFAULT_COLOR
+        };
+        let thickness = match item.thickness {
+            Some(v) => v.max(1),
+            None => DEFAULT_THICKNESS
+        };
+
+        match item.kind.as_str() {
+            "rect" => {
+                let rect = required(&item.rect, idx, item, "rect")?;
+                let (x, y, w, h) = (rect.x as i64, rect.y as i64, rect.w as i64, rect.h as i64);
+                if let Some(fill) = &item.fill {
+                    fill_rect(&mut frame, x, y, w, h, fill);
+                }
+                stroke_rect(&mut frame, x, y, w, h, thickness as i64, &color);
+            }
+            "arrow" => {
+                let from = required(&item.from, idx, item, "from")?;
+                let to = required(&item.to, idx, item, "to")?;
+                arrow(&mut frame, point_of(from), point_of(to), thickness as f64, &color);
+            }
+            "text" => {
+                let at = required(&item.at, idx, item, "at")?;
+                let content = required(&item.text, idx, item, "text")?;
+                let scale = match item.size {
+                    Some(v) => v,
+                    None => DEFAULT_FONT_SCALE
+                };
+                text(&mut frame, at.x as i64, at.y as i64, content, scale, &color, item.fill.as_ref());
+            }
+            "blur" => {
+                let rect = required(&item.rect, idx, item, "rect")?;
+                let radius = match item.size {
+                    Some(v) => v.max(1),
+                    None => DEFAULT_BLUR_RADIUS
+                };
+                blur(&mut frame, rect, radius);
+            }
+            "pixelate" => {
+                let rect = required(&item.rect, idx, item, "rect")?;
+                let block = match item.size {
+                    Some(v) => v,
+                    None => DEFAULT_BLOCK_SIZE
+                };
+                pixelate(&mut frame, rect, block);
+            }
+            "crop" => {
+                let rect = required(&item.rect, idx, item, "rect")?;
+                frame = match clip(&frame, rect) {
+                    Some((x, y, w, h)) => frame.crop(x, y, w, h),
+                    None => return Err(Error::new(CaptureErrorCode::InvalidArg, format!("The crop area of annotation #{} is outside of the image!", idx)))
+                };
+            }
+            other => return Err(Error::new(CaptureErrorCode::InvalidArg, format!("Unsupported annotation kind: {}!", other)))
+        }
+    }
+
+    Ok(frame)
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    fn annotation(kind: &str) -> Annotation {
+        Annotation {
+            kind: kind.to_string(),
+            rect: None,
+            from: None,
+            to: None,
+            at: None,
+            text: None,
+            color: None,
+            fill: None,
+            thickness: None,
+            size: None,
+        }
+    }
+
+    fn white(w: u32, h: u32) -> Frame {
+        Frame { w, h, rgba: vec![255; (w * h * 4) as usize] }
+    }
+
+    #[test]
+    fn shapes() {
+        let mut frame = white(40, 30);
+        stroke_rect(&mut frame, 5, 5, 20, 10, 2, &DEFAULT_COLOR);
+        assert_eq!(frame.pixel(5, 5), Some(DEFAULT_COLOR));
+        assert_eq!(frame.pixel(6, 14), Some(DEFAULT_COLOR));
+        assert_eq!(frame.pixel(10, 10), Some(Color { r: 255, g: 255, b: 255, a: 255 }));
+
+        let mut frame = white(40, 30);
+        arrow(&mut frame, (2.0, 15.0), (35.0, 15.0), 2.0, &DEFAULT_COLOR);
+        assert_eq!(frame.pixel(35, 15), Some(DEFAULT_COLOR));
+        assert_eq!(frame.pixel(10, 15), Some(DEFAULT_COLOR));
+        assert_eq!(frame.pixel(10, 25), Some(Color { r: 255, g: 255, b: 255, a: 255 }));
+
+        let mut frame = white(40, 30);
+        text(&mut frame, 0, 0, "I", 1, &DEFAULT_COLOR, None);
+        // `I` 的中间一列全部点亮
+        assert!((0..7).all(|y| frame.pixel(2, y) == Some(DEFAULT_COLOR)));
+        assert_eq!(frame.pixel(0, 3), Some(Color { r: 255, g: 255, b: 255, a: 255 }));
+    }
+
+    #[test]
+    fn redact() {
+        let mut frame = white(20, 20);
+        for x in 0..20 {
+            // 黑白相间的竖条纹
+            if x % 2 == 0 {
+                fill_rect(&mut frame, x, 0, 1, 20, &Color { r: 0, g: 0, b: 0, a: 255 });
+            }
+        }
+
+        let mut blurred = frame.clone();
+        blur(&mut blurred, &Rect { x: 0, y: 0, w: 20, h: 10 }, 2);
+        let px = blurred.pixel(10, 5).unwrap();
+        assert!(px.r > 80 && px.r < 180);
+        // 区域外不受影响
+        assert_eq!(blurred.pixel(10, 15), frame.pixel(10, 15));
+
+        let mut pixelated = frame.clone();
+        pixelate(&mut pixelated, &Rect { x: 0, y: 0, w: 20, h: 20 }, 4);
+        assert_eq!(pixelated.pixel(0, 0), pixelated.pixel(3, 3));
+        assert_eq!(pixelated.pixel(0, 0).unwrap().r, 127);
+
+        // 半径 / 色块超出区域时按区域大小处理
+        let mut blurred = frame.clone();
+        blur(&mut blurred, &Rect { x: 0, y: 0, w: 20, h: 10 }, u32::MAX);
+        let px = blurred.pixel(10, 5).unwrap();
+        assert!(px.r > 80 && px.r < 180);
+        let mut pixelated = frame.clone();
+        pixelate(&mut pixelated, &Rect { x: 0, y: 0, w: 20, h: 20 }, u32::MAX);
+        assert_eq!(pixelated.pixel(0, 0), pixelated.pixel(19, 19));
+        assert_eq!(pixelated.pixel(0, 0).unwrap().r, 127);
+    }
+
+    #[test]
+    fn annotations() {
+        let mut crop = annotation("crop");
+        crop.rect = Some(Rect { x: 10, y: 10, w: 100, h: 5 });
+        let frame = apply(white(30, 30), &[annotation("rect"), crop.clone()]);
+        assert_eq!(frame.unwrap_err().status, CaptureErrorCode::InvalidArg);
+
+        let frame = apply(white(30, 30), &[crop]).unwrap();
+        assert_eq!((frame.w, frame.h), (20, 5));
+
+        assert!(apply(white(30, 30), &[annotation("circle")]).is_err());
+    }
+}
@@ -1,20 +1,435 @@
+use std::time::Duration;
+use napi::{Env, Error, JsError, JsFunction, Status, Task};
+use napi::bindgen_prelude::{AsyncTask, Buffer, Either, ToNapiValue, TypeName};
 use screenshots::Screen;
+use crate::annotate;
+use crate::coords;
+use crate::cursor;
+use crate::frame::Frame;
+use crate::matcher;
+use crate::ocr::RecognizeText;
+use crate::record::ScreenRecorder;
+use crate::stream::CaptureStream;
+use crate::wait::{WaitForImage, WaitForPixel, WaitForRegionChange, DEFAULT_INTERVAL};
+use crate::utils::{Annotation, Color, CursorInfo, EncodeOptions, FindImageOptions, ImageMatch, MouseLocation, OcrOptions, PhysicalPoint, RawImage, RecordOptions, Rect, ScreenCapture, ScreenInfo, StreamOptions};
 
-/// 捕获所有屏幕图像
-pub fn capture_all() -> Vec<Vec<u8>> {
+/// 截图错误原因 (即 js 侧异常的 `code` 字段)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureErrorCode {
+    /// 无可用的屏幕 (如无头会话)
+    NoDisplay,
+    /// 没有截图权限 (如 macOS 未授予屏幕录制权限, Wayland 门户拒绝)
+    PermissionDenied,
+    /// 截图失败
+    CaptureFailed,
+    /// 图像编码失败
+    EncodeFailed,
+    /// 参数不合法
+    InvalidArg,
+    /// 等待超时
+    Timeout,
+    /// 功能未启用 (如编译时未开启对应的 cargo feature)
+    Unsupported,
+    /// 文字识别模型加载失败
+    ModelLoadFailed,
+    /// 文字识别失败
+    RecognizeFailed,
+}
+
+impl AsRef<str> for CaptureErrorCode {
+    fn as_ref(&self) -> &str {
+        match self {
+            CaptureErrorCode::NoDisplay => "no-display",
+            CaptureErrorCode::PermissionDenied => "permission-denied",
+            CaptureErrorCode::CaptureFailed => "capture-failed",
+            CaptureErrorCode::EncodeFailed => "encode-failed",
+            CaptureErrorCode::InvalidArg => "invalid-arg",
+            CaptureErrorCode::Timeout => "timeout",
+            CaptureErrorCode::Unsupported => "unsupported",
+            CaptureErrorCode::ModelLoadFailed => "model-load-failed",
+            CaptureErrorCode::RecognizeFailed => "recognize-failed",
+        }
+    }
+}
+
+pub type CaptureResult<T> = napi::Result<T, CaptureErrorCode>;
+
+/// 根据底层错误信息推断失败原因 (权限问题在各平台上没有统一的错误类型, 只能按描述判断)
+fn classify(details: &str, fallback: CaptureErrorCode) -> CaptureErrorCode {
+    let lower = details.to_lowercase();
+    if ["permission", "access denied", "access is denied", "accessibility", "not authorized", "not permitted"].iter().any(|word| lower.contains(word)) {
+        CaptureErrorCode::PermissionDenied
+    } else if ["display", "no screen", "connection"].iter().any(|word| lower.contains(word)) {
+        CaptureErrorCode::NoDisplay
+    } else {
+        fallback
+    }
+}
+
+/// 转换为通用异常 (用于只接受 [Status] 的同步接口), 原因以 `[code]` 前缀保留在描述中
+pub fn to_status(err: Error<CaptureErrorCode>) -> Error {
+    Error::new(Status::GenericFailure, format!("[{}] {}", err.status.as_ref(), err.reason))
+}
+
+/// 带原因码的 js 异常 (`code` 字段为 [CaptureErrorCode])
+pub fn to_js_error(env: Env, err: Error<CaptureErrorCode>) -> napi::JsUnknown {
+    JsError::from(err).into_unknown(env)
+}
+
+/// 在 libuv 工作线程中执行的截图任务 (经 [CodedTask] 交给 [AsyncTask])
+pub trait CaptureTask: Send {
+    type Output: Send + ToNapiValue + TypeName + 'static;
+
+    fn run(&mut self) -> CaptureResult<Self::Output>;
+}
+
+/// [CaptureTask] -> [Task]
+///
+/// `Task::compute` 只接受 [Status], 失败原因暂存后在 `reject` 中以带原因码的异常 reject
+pub struct CodedTask<T: CaptureTask> {
+    task: T,
+    failure: Option<Error<CaptureErrorCode>>,
+}
+
+impl<T: CaptureTask> CodedTask<T> {
+    pub fn new(task: T) -> AsyncTask<Self> {
+        AsyncTask::new(CodedTask { task, failure: None })
+    }
+}
+
+impl<T: CaptureTask> Task for CodedTask<T> {
+    type Output = T::Output;
+    type JsValue = T::Output;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        self.task.run().map_err(|err| {
+            let status = Error::new(Status::GenericFailure, err.reason.clone());
+            self.failure = Some(err);
+            status
+        })
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
+    }
+
+    fn reject(&mut self, env: Env, err: Error) -> napi::Result<Self::JsValue> {
+        match self.failure.take() {
+            Some(failure) => Err(Error::from(to_js_error(env, failure))),
+            None => Err(err)
+        }
+    }
+}
+
+/// 截图失败的异常
+fn capture_failed(id: u32, err: impl std::fmt::Display) -> Error<CaptureErrorCode> {
+    let details = err.to_string();
+    Error::new(classify(&details, CaptureErrorCode::CaptureFailed), format!("Failed to capture screen {}! details: {}", id, details))
+}
+
+/// 捕获单个屏幕图像 (png)
+fn shoot(screen: &Screen) -> CaptureResult<Vec<u8>> {
+    let id = screen.display_info.id;
+
+    let image = match screen.capture() {
+        Ok(v) => v,
+        Err(err) => return Err(capture_failed(id, err))
+    };
+
+    match image.to_png() {
+        Ok(buffer) => Ok(buffer),
+        Err(err) => Err(Error::new(CaptureErrorCode::EncodeFailed, format!("Failed to encode the capture of screen {}! details: {}", id, err)))
+    }
+}
+
+/// 捕获所有屏幕图像 (任一屏幕失败即返回异常)
+pub fn capture_all() -> CaptureResult<Vec<Vec<u8>>> {
     // 获取所有屏幕
-    let screens = Screen::all().unwrap();
+    let screens = all_screens()?;
 
     // 储存所有屏幕截图
     let mut shoots: Vec<Vec<u8>> = vec![];
 
     for screen in screens {
-        let image = screen.capture().unwrap();
-        let buffer = image.to_png().unwrap();
-        shoots.push(buffer);
+        shoots.push(shoot(&screen)?);
+    }
+
+    Ok(shoots)
+}
+
+/// 逐个捕获所有屏幕图像 (单个屏幕失败不影响其他屏幕)
+pub fn capture_each() -> CaptureResult<Vec<ScreenCapture>> {
+    let mut shoots = vec![];
+
+    for screen in all_screens()? {
+        let id = screen.display_info.id;
+        shoots.push(match shoot(&screen) {
+            Ok(buffer) => ScreenCapture { id, data: Some(buffer), code: None, message: None },
+            Err(err) => ScreenCapture { id, data: None, code: Some(err.status.as_ref().to_string()), message: Some(err.reason) },
+        });
+    }
+
+    Ok(shoots)
+}
+
+/// 屏幕 -> 屏幕信息
+fn to_screen_info(screen: &Screen) -> ScreenInfo {
+    let info = screen.display_info;
+    ScreenInfo {
+        id: info.id,
+        x: info.x,
+        y: info.y,
+        width: info.width,
+        height: info.height,
+        scale_factor: info.scale_factor as f64,
+        rotation: info.rotation as f64,
+        refresh_rate: info.frequency as f64,
+        is_primary: info.is_primary,
+    }
+}
+
+/// 获取所有屏幕的信息
+pub fn list_screens() -> CaptureResult<Vec<ScreenInfo>> {
+    Ok(all_screens()?.iter().map(to_screen_info).collect())
+}
+
+/// 获取所有屏幕 (没有任何屏幕时返回异常)
+fn all_screens() -> CaptureResult<Vec<Screen>> {
+    match Screen::all() {
+        Ok(v) if v.is_empty() => Err(Error::new(CaptureErrorCode::NoDisplay, String::from("No screen is available!"))),
+        Ok(v) => Ok(v),
+        Err(err) => {
+            let details = err.to_string();
+            Err(Error::new(classify(&details, CaptureErrorCode::NoDisplay), format!("Failed to enumerate screens! details: {}", details)))
+        }
+    }
+}
+
+/// 按 id 查找屏幕
+pub fn find_screen(id: u32) -> CaptureResult<Screen> {
+    match all_screens()?.into_iter().find(|screen| screen.display_info.id == id) {
+        Some(screen) => Ok(screen),
+        None => Err(Error::new(CaptureErrorCode::InvalidArg, format!("Screen with id {} does not exist!", id)))
+    }
+}
+
+/// 捕获指定屏幕图像
+pub fn capture_one(id: u32) -> CaptureResult<Vec<u8>> {
+    shoot(&find_screen(id)?)
+}
+
+/// 捕获指定屏幕的像素 (`id` 为空时捕获主屏幕)
+pub fn capture_screen_frame(id: Option<u32>) -> CaptureResult<Frame> {
+    Ok(capture_screen_region(id)?.0)
+}
+
+/// 捕获指定屏幕的像素, 同时返回该屏幕在虚拟桌面中的区域 (`id` 为空时捕获主屏幕)
+pub fn capture_screen_region(id: Option<u32>) -> CaptureResult<(Frame, Rect)> {
+    let screen = match id {
+        Some(id) => find_screen(id)?,
+        None => match all_screens()?.into_iter().find(|screen| screen.display_info.is_primary) {
+            Some(screen) => screen,
+            None => return Err(Error::new(CaptureErrorCode::NoDisplay, String::from("No primary screen is available!")))
+        }
+    };
+
+    let info = screen.display_info;
+    match screen.capture() {
+        Ok(image) => Ok((Frame::from_image(&image), Rect { x: info.x, y: info.y, w: info.width, h: info.height })),
+        Err(err) => Err(capture_failed(info.id, err))
+    }
+}
+
+/// 按指定格式捕获指定屏幕图像 (`with_cursor` 为 `true` 时绘制光标)
+pub fn capture_one_as(id: u32, options: &EncodeOptions, with_cursor: bool) -> CaptureResult<RawImage> {
+    let (mut frame, region) = capture_screen_region(Some(id))?;
+    if with_cursor {
+        cursor::composite(&mut frame, &region);
+    }
+    frame.encode(options)
+}
+
+/// 捕获虚拟桌面中的矩形区域 (可跨越多个屏幕, 各屏幕的部分会被拼接在一起)
+///
+/// 输出尺寸按所涉及屏幕中最大的缩放比例换算为物理像素
+pub fn capture_area(area: &Rect) -> CaptureResult<Frame> {
+    if area.w == 0 || area.h == 0 {
+        return Err(Error::new(CaptureErrorCode::InvalidArg, String::from("The width and height of the area must be greater than zero!")));
+    }
+
+    let (right, bottom) = match (i32::try_from(area.x as i64 + area.w as i64), i32::try_from(area.y as i64 + area.h as i64)) {
+        (Ok(right), Ok(bottom)) => (right, bottom),
+        _ => return Err(Error::new(CaptureErrorCode::InvalidArg, String::from("The area exceeds the range of the virtual desktop coordinates!")))
+    };
+
+    // 与区域相交的屏幕及相交部分 (left, top, right, bottom)
+    let mut pieces = vec![];
+    for screen in all_screens()? {
+        let info = screen.display_info;
+        let left = area.x.max(info.x);
+        let top = area.y.max(info.y);
+        let r = right.min(info.x + info.width as i32);
+        let b = bottom.min(info.y + info.height as i32);
+        if r > left && b > top {
+            pieces.push((screen, left, top, r, b));
+        }
+    }
+
+    if pieces.is_empty() {
+        return Err(Error::new(CaptureErrorCode::InvalidArg, String::from("The area is outside of all screens!")));
+    }
+
+    // 单屏幕完整覆盖 -- 直接返回原始分辨率
+    if pieces.len() == 1 {
+        let (screen, left, top, r, b) = &pieces[0];
+        if *left == area.x && *top == area.y && *r == right && *b == bottom {
+            let info = screen.display_info;
+            return match screen.capture_area(area.x - info.x, area.y - info.y, area.w, area.h) {
+                Ok(image) => Ok(Frame::from_image(&image)),
+                Err(err) => Err(capture_failed(info.id, err))
+            };
+        }
+    }
+
+    let scale = pieces.iter().fold(1f32, |acc, piece| acc.max(piece.0.display_info.scale_factor));
+    let physical = |v: i64| (v as f64 * scale as f64).round() as u32;
+
+    let (w, h) = (physical(area.w as i64), physical(area.h as i64));
+    Frame::check_size(w, h)?;
+    let mut canvas = Frame::new(w, h);
+    for (screen, left, top, r, b) in pieces {
+        let info = screen.display_info;
+        let image = match screen.capture_area(left - info.x, top - info.y, (r - left) as u32, (b - top) as u32) {
+            Ok(v) => v,
+            Err(err) => return Err(capture_failed(info.id, err))
+        };
+
+        canvas.blit_scaled(
+            &Frame::from_image(&image),
+            physical(left as i64 - area.x as i64),
+            physical(top as i64 - area.y as i64),
+            physical(r as i64 - left as i64),
+            physical(b as i64 - top as i64),
+        );
+    }
+
+    Ok(canvas)
+}
+
+/// 读取虚拟桌面中各点的颜色
+///
+/// 同一屏幕上的点只截取一次 (截取包含这些点的最小区域)
+pub fn pixels_at(points: &[MouseLocation]) -> CaptureResult<Vec<Color>> {
+    let screens = all_screens()?;
+
+    // 各点所在的屏幕
+    let mut owners = vec![];
+    for point in points {
+        match screens.iter().position(|screen| {
+            let info = screen.display_info;
+            point.x >= info.x && point.x < info.x + info.width as i32 && point.y >= info.y && point.y < info.y + info.height as i32
+        }) {
+            Some(idx) => owners.push(idx),
+            None => return Err(Error::new(CaptureErrorCode::InvalidArg, format!("The point ({}, {}) is outside of all screens!", point.x, point.y)))
+        }
+    }
+
+    let mut colors = vec![Color { r: 0, g: 0, b: 0, a: 0 }; points.len()];
+    for (idx, screen) in screens.iter().enumerate() {
+        let members: Vec<usize> = (0..points.len()).filter(|i| owners[*i] == idx).collect();
+        if members.is_empty() {
+            continue;
+        }
+
+        let info = screen.display_info;
+        let left = members.iter().map(|i| points[*i].x).min().unwrap();
+        let top = members.iter().map(|i| points[*i].y).min().unwrap();
+        let w = (members.iter().map(|i| points[*i].x).max().unwrap() - left + 1) as u32;
+        let h = (members.iter().map(|i| points[*i].y).max().unwrap() - top + 1) as u32;
+
+        let frame = match screen.capture_area(left - info.x, top - info.y, w, h) {
+            Ok(image) => Frame::from_image(&image),
+            Err(err) => return Err(capture_failed(info.id, err))
+        };
+
+        // 截图为物理像素, 需按比例换算
+        for i in members {
+            let x = ((points[i].x - left) as u64 * frame.w as u64 / w as u64) as u32;
+            let y = ((points[i].y - top) as u64 * frame.h as u64 / h as u64) as u32;
+            if let Some(color) = frame.pixel(x, y) {
+                colors[i] = color;
+            }
+        }
     }
 
-    shoots
+    Ok(colors)
+}
+
+/// 虚拟桌面 (所有屏幕) 的外接矩形
+pub fn desktop_bounds() -> CaptureResult<Rect> {
+    let screens = all_screens()?;
+
+    let left = screens.iter().map(|s| s.display_info.x).min().unwrap();
+    let top = screens.iter().map(|s| s.display_info.y).min().unwrap();
+    let right = screens.iter().map(|s| s.display_info.x + s.display_info.width as i32).max().unwrap();
+    let bottom = screens.iter().map(|s| s.display_info.y + s.display_info.height as i32).max().unwrap();
+
+    Ok(Rect { x: left, y: top, w: (right - left) as u32, h: (bottom - top) as u32 })
+}
+
+/// 由 js 传入的图像构造像素缓冲 ([RawImage] 或图像文件的字节)
+pub fn frame_of(image: Either<RawImage, Buffer>) -> CaptureResult<Frame> {
+    match image {
+        Either::A(image) => Frame::from_raw(&image),
+        Either::B(buffer) => Frame::decode(&buffer),
+    }
+}
+
+/// 在 `haystack` (截取自 `region`) 中查找 `template`, 结果换算为虚拟桌面坐标
+pub fn find_in(haystack: &Frame, region: &Rect, template: &Frame, options: &FindImageOptions) -> Vec<ImageMatch> {
+    let threshold = match options.threshold {
+        Some(v) => v.clamp(0.0, 1.0) as f32,
+        None => 0.9
+    };
+    let grayscale = match options.grayscale {
+        Some(v) => v,
+        None => false
+    };
+    let scales: Vec<f32> = match &options.scales {
+        Some(v) => v.iter().map(|s| *s as f32).collect(),
+        None => vec![1.0]
+    };
+
+    // 截图为物理像素, 需换算回虚拟桌面坐标
+    let ratio_x = region.w as f64 / haystack.w.max(1) as f64;
+    let ratio_y = region.h as f64 / haystack.h.max(1) as f64;
+
+    matcher::find(haystack, template, threshold, grayscale, &scales).into_iter().map(|found| ImageMatch {
+        x: region.x + (found.x as f64 * ratio_x).round() as i32,
+        y: region.y + (found.y as f64 * ratio_y).round() as i32,
+        w: (found.w as f64 * ratio_x).round() as u32,
+        h: (found.h as f64 * ratio_y).round() as u32,
+        confidence: found.score as f64,
+        scale: found.scale as f64,
+    }).collect()
+}
+
+/// 在屏幕中查找 `template`
+pub fn find_image(template: &Frame, options: &FindImageOptions) -> CaptureResult<Vec<ImageMatch>> {
+    let region = match &options.region {
+        Some(v) => v.clone(),
+        None => desktop_bounds()?
+    };
+
+    Ok(find_in(&capture_area(&region)?, &region, template, options))
+}
+
+/// 轮询间隔 (默认为 [DEFAULT_INTERVAL])
+fn interval_of(interval_ms: Option<u32>) -> Duration {
+    Duration::from_millis(match interval_ms {
+        Some(v) => v.max(1),
+        None => DEFAULT_INTERVAL
+    } as u64)
 }
 
 #[napi]
@@ -23,9 +438,223 @@ pub struct Captor {}
 #[napi]
 impl Captor {
     /// Capture images of all of the user's screen
+    ///
+    /// Throws if any of the screens fails, `err.code` is one of
+    /// `'no-display' | 'permission-denied' | 'capture-failed' | 'encode-failed' | 'invalid-arg'`
+    #[napi]
+    pub fn capture_all_screen() -> CaptureResult<Vec<Vec<u8>>> {
+        capture_all()
+    }
+
+    /// Capture images of all of the user's screen one by one, a failed screen does not affect the others
+    ///
+    /// Each item carries either `data` or `code` + `message`
+    #[napi]
+    pub fn capture_each_screen() -> CaptureResult<Vec<ScreenCapture>> {
+        capture_each()
+    }
+
+    /// List all of the user's screens (id, bounds, scale factor, rotation, refresh rate, is-primary)
+    #[napi]
+    pub fn list_screens() -> CaptureResult<Vec<ScreenInfo>> {
+        list_screens()
+    }
+
+    /// Capture image (png) of the screen with the given `id`, see [Captor::list_screens]
+    #[napi]
+    pub fn capture_screen(id: u32) -> CaptureResult<Vec<u8>> {
+        capture_one(id)
+    }
+
+    /// Capture image (png) of the `area` in global virtual-desktop coordinates, the area may span several screens
+    #[napi]
+    pub fn capture_area(area: Rect) -> CaptureResult<Vec<u8>> {
+        capture_area(&area)?.to_png()
+    }
+
+    /// Capture image of the screen with the given `id` in the given format, the mouse cursor is drawn if `with_cursor` is `true`
+    ///
+    /// Raw formats (`rgba` / `bgra`) come with `stride`, encoded formats (`png` / `jpeg` / `webp` / `qoi`) do not
+    #[napi]
+    pub fn capture_screen_image(id: u32, options: EncodeOptions, with_cursor: Option<bool>) -> CaptureResult<RawImage> {
+        capture_one_as(id, &options, with_cursor == Some(true))
+    }
+
+    /// Capture image of the `area` in the given format, see [Captor::capture_area] and [Captor::capture_screen_image]
+    #[napi]
+    pub fn capture_area_image(area: Rect, options: EncodeOptions, with_cursor: Option<bool>) -> CaptureResult<RawImage> {
+        let mut frame = capture_area(&area)?;
+        if with_cursor == Some(true) {
+            cursor::composite(&mut frame, &area);
+        }
+        frame.encode(&options)
+    }
+
+    /// Read the current mouse cursor: its bitmap (rgba), hotspot and position in global virtual-desktop coordinates
+    ///
+    /// Returns `null` if the cursor is hidden. Throws with code `unsupported` on platforms where the system cursor
+    /// bitmap cannot be read (macOS).
+    #[napi]
+    pub fn cursor_image() -> CaptureResult<Option<CursorInfo>> {
+        Ok(cursor::current()?.map(|cursor| CursorInfo {
+            x: cursor.x,
+            y: cursor.y,
+            hot_x: cursor.hot_x,
+            hot_y: cursor.hot_y,
+            image: RawImage {
+                w: cursor.image.w,
+                h: cursor.image.h,
+                stride: Some(cursor.image.w * 4),
+                bytes: cursor.image.rgba,
+                format: Some(String::from("rgba")),
+            },
+        }))
+    }
+
+    /// Draw `annotations` (rectangles, arrows, text labels, blur / pixelate and crop) in order onto `image`
+    /// (a [RawImage] or the bytes of a png / jpeg / webp / qoi file), returns the result as png
+    ///
+    /// Coordinates are pixels of the image, a `crop` affects the annotations after it
+    #[napi]
+    pub fn annotate(image: Either<RawImage, Buffer>, annotations: Vec<Annotation>) -> CaptureResult<Vec<u8>> {
+        annotate::apply(frame_of(image)?, &annotations)?.to_png()
+    }
+
+    /// Recognize text (on-device OCR) in `region` (global virtual-desktop coordinates),
+    /// resolves with the words and their bounding boxes in global virtual-desktop coordinates
+    ///
+    /// Requires the `ocr` cargo feature (rejects with code `unsupported` otherwise) and local model files (see [OcrOptions]):
+    /// rejects with code `invalid-arg` if a model path is missing, `model-load-failed` if a model cannot be loaded
+    /// and `recognize-failed` if the engine fails on the captured image.
+    #[napi(ts_return_type = "Promise<Array<RecognizedWord>>")]
+    pub fn recognize_text(region: Rect, options: Option<OcrOptions>) -> AsyncTask<CodedTask<RecognizeText>> {
+        let options = match options {
+            Some(v) => v,
+            None => OcrOptions { lang: None, detection_model: None, recognition_model: None }
+        };
+
+        CodedTask::new(RecognizeText { region, options })
+    }
+
+    /// Convert `point` in logical (global virtual-desktop) coordinates to physical pixels of the screen containing it,
+    /// i.e. the pixel position inside that screen's capture, see [crate::coords]
+    #[napi]
+    pub fn to_physical(point: MouseLocation) -> CaptureResult<PhysicalPoint> {
+        match coords::to_physical(&list_screens()?, &point) {
+            Some(v) => Ok(v),
+            None => Err(Error::new(CaptureErrorCode::InvalidArg, format!("The point ({}, {}) is outside of all screens!", point.x, point.y)))
+        }
+    }
+
+    /// Convert `point` in physical pixels of a screen (e.g. a position inside a capture of that screen)
+    /// to logical (global virtual-desktop) coordinates, which can be passed to `Controller.mouseMove` directly
+    #[napi]
+    pub fn to_logical(point: PhysicalPoint) -> CaptureResult<MouseLocation> {
+        match coords::to_logical(&list_screens()?, &point) {
+            Some(v) => Ok(v),
+            None => Err(Error::new(CaptureErrorCode::InvalidArg, format!("Screen with id {} does not exist!", point.screen_id)))
+        }
+    }
+
+    /// Read the color (RGBA) at `point` in global virtual-desktop coordinates
     #[napi]
-    pub fn capture_all_screen() -> napi::Result<Vec<Vec<u8>>> {
-        Ok(capture_all())
+    pub fn pixel_at(point: MouseLocation) -> CaptureResult<Color> {
+        Ok(pixels_at(&[point])?.remove(0))
+    }
+
+    /// Read the colors (RGBA) at `points` in global virtual-desktop coordinates, in the same order
+    #[napi]
+    pub fn pixels_at(points: Vec<MouseLocation>) -> CaptureResult<Vec<Color>> {
+        pixels_at(&points)
+    }
+
+    /// Find `template` (a [RawImage] or the bytes of a png / jpeg / webp / qoi file) on screen
+    ///
+    /// Matches are compared by normalized cross-correlation and sorted by `confidence` (highest first),
+    /// the rectangles are in global virtual-desktop coordinates so they can be passed to `Controller.mouseMove` directly.
+    #[napi]
+    pub fn find_image(template: Either<RawImage, Buffer>, options: Option<FindImageOptions>) -> CaptureResult<Vec<ImageMatch>> {
+        let options = match options {
+            Some(v) => v,
+            None => FindImageOptions { region: None, threshold: None, grayscale: None, scales: None }
+        };
+
+        find_image(&frame_of(template)?, &options)
+    }
+
+    /// Wait until `template` appears on screen, resolves with the best match, see [Captor::find_image]
+    ///
+    /// The screen is polled natively every `intervalMs` (defaults to `100`),
+    /// rejects with code `timeout` if nothing matched within `timeoutMs`.
+    #[napi(ts_return_type = "Promise<ImageMatch>")]
+    pub fn wait_for_image(template: Either<RawImage, Buffer>, timeout_ms: u32, options: Option<FindImageOptions>, interval_ms: Option<u32>) -> CaptureResult<AsyncTask<CodedTask<WaitForImage>>> {
+        Ok(CodedTask::new(WaitForImage {
+            template: frame_of(template)?,
+            options: match options {
+                Some(v) => v,
+                None => FindImageOptions { region: None, threshold: None, grayscale: None, scales: None }
+            },
+            timeout: Duration::from_millis(timeout_ms as u64),
+            interval: interval_of(interval_ms),
+        }))
+    }
+
+    /// Wait until the color at `point` matches `color`, resolves with the actual color
+    ///
+    /// `tolerance` is the maximum difference allowed on each of the R / G / B channels (defaults to `0`, alpha is ignored),
+    /// rejects with code `timeout` if the color did not match within `timeoutMs`.
+    #[napi(ts_return_type = "Promise<Color>")]
+    pub fn wait_for_pixel(point: MouseLocation, color: Color, tolerance: Option<u32>, timeout_ms: u32, interval_ms: Option<u32>) -> AsyncTask<CodedTask<WaitForPixel>> {
+        CodedTask::new(WaitForPixel {
+            point,
+            color,
+            tolerance: match tolerance {
+                Some(v) => v.min(255) as u8,
+                None => 0
+            },
+            timeout: Duration::from_millis(timeout_ms as u64),
+            interval: interval_of(interval_ms),
+        })
+    }
+
+    /// Wait until anything changes inside `region`, resolves with the changed rectangles in global virtual-desktop coordinates
+    ///
+    /// Rejects with code `timeout` if nothing changed within `timeoutMs`.
+    #[napi(ts_return_type = "Promise<Array<Rect>>")]
+    pub fn wait_for_region_change(region: Rect, timeout_ms: u32, interval_ms: Option<u32>) -> AsyncTask<CodedTask<WaitForRegionChange>> {
+        CodedTask::new(WaitForRegionChange {
+            region,
+            timeout: Duration::from_millis(timeout_ms as u64),
+            interval: interval_of(interval_ms),
+        })
+    }
+
+    /// Start capturing continuously on a native thread, frames are delivered to `callback`
+    ///
+    /// Frames are dropped while the previous one has not been handled by js yet (see `frame.dropped`),
+    /// call `stop()` on the returned stream to end it.
+    #[napi]
+    pub fn start_stream(options: StreamOptions, #[napi(ts_arg_type = "(err: null | Error, frame: StreamFrame) => void")] callback: JsFunction) -> napi::Result<CaptureStream> {
+        CaptureStream::start(options, callback)
+    }
+
+    /// Start capturing continuously like [Captor::start_stream], but only the rectangles that changed since
+    /// the previous frame are delivered (compared tile by tile, `tileSize` defaults to `64`)
+    ///
+    /// `frame.changed` is `false` and `frame.rects` is empty when nothing changed.
+    #[napi]
+    pub fn start_diff_stream(options: StreamOptions, tile_size: Option<u32>, #[napi(ts_arg_type = "(err: null | Error, frame: DiffFrame) => void")] callback: JsFunction) -> napi::Result<CaptureStream> {
+        CaptureStream::start_diff(options, tile_size, callback)
+    }
+
+    /// Record the screen (or `region`) to `path` as a motion-JPEG AVI or an animated GIF, using pure-Rust encoders
+    ///
+    /// The first frame is captured and the file is created immediately (throws on a bad argument or path),
+    /// call `stop()` on the returned recorder to finish the file, it resolves with the [Recording].
+    /// AVI files are limited to 1 GB, the recording ends at that size and `stop()` resolves with `truncated: true`.
+    #[napi]
+    pub fn record(options: RecordOptions) -> CaptureResult<ScreenRecorder> {
+        ScreenRecorder::start(options)
     }
 }
 
@@ -35,12 +664,51 @@ mod unit_test {
     use super::*;
 
     #[test]
-    fn ttt() {
-        shot();
+    fn screens() {
+        let screens = list_screens().unwrap();
+        assert!(!screens.is_empty());
+
+        // 屏幕标识互不相同
+        let mut ids: Vec<u32> = screens.iter().map(|v| v.id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), screens.len());
+    }
+
+    #[test]
+    fn capture() {
+        for screen in list_screens().unwrap() {
+            let frame = capture_screen_frame(Some(screen.id)).unwrap();
+            assert!(frame.w > 0 && frame.h > 0);
+            assert_eq!(frame.rgba.len(), frame.w as usize * frame.h as usize * 4);
+
+            let buffer = capture_one(screen.id).unwrap();
+            assert!(buffer.starts_with(b"\x89PNG"));
+        }
     }
 
     #[test]
-    fn ttt() {
-        shot();
+    fn each() {
+        let screens = list_screens().unwrap();
+        let shoots = capture_each().unwrap();
+
+        // 每个屏幕一项, 且截图与失败原因必有其一
+        assert_eq!(shoots.len(), screens.len());
+        for shoot in shoots {
+            assert!(screens.iter().any(|v| v.id == shoot.id));
+            assert!(shoot.data.is_some() || shoot.code.is_some());
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn error_code() {
+        assert_eq!(classify("Screen Recording permission denied", CaptureErrorCode::CaptureFailed), CaptureErrorCode::PermissionDenied);
+        assert_eq!(classify("Access is denied. (os error 5)", CaptureErrorCode::CaptureFailed), CaptureErrorCode::PermissionDenied);
+        assert_eq!(classify("the process is not trusted for accessibility", CaptureErrorCode::CaptureFailed), CaptureErrorCode::PermissionDenied);
+        // 仅含 "access" 的描述不视为权限问题
+        assert_eq!(classify("failed to access the frame buffer", CaptureErrorCode::CaptureFailed), CaptureErrorCode::CaptureFailed);
+        assert_eq!(classify("DXGI_ERROR_ACCESS_LOST", CaptureErrorCode::CaptureFailed), CaptureErrorCode::CaptureFailed);
+        assert_eq!(classify("cannot open display", CaptureErrorCode::CaptureFailed), CaptureErrorCode::NoDisplay);
+        assert_eq!(classify("unknown failure", CaptureErrorCode::CaptureFailed), CaptureErrorCode::CaptureFailed);
+    }
+}
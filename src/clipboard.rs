@@ -65,6 +65,8 @@ impl Clipboard {
                             w: img.width as u32,
                             h: img.height as u32,
                             bytes: img.bytes.to_vec(),
+                            stride: Some(img.width as u32 * 4),
+                            format: Some(String::from("rgba")),
                         }),
                     }
                 };
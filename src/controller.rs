@@ -1,8 +1,10 @@
 use enigo::{Enigo, Key as EnigoKey, KeyboardControllable, MouseControllable};
 use napi::{Error, Status};
+use napi::bindgen_prelude::Either;
 
+use crate::coords;
 use crate::mapper::EnigoMapper;
-use crate::utils::{KeyCombination, MouseLocation};
+use crate::utils::{KeyCombination, MouseLocation, MouseLocationOptions, MouseMoveOptions, ScreenInfo};
 
 #[napi]
 pub struct Controller {}
@@ -155,30 +157,81 @@ impl Controller {
     }
 
     /// 鼠标 -- 移动
-    /// `direction`: 移动方向 (默认为绝对定位: 屏幕左上角为原点,向右向下为正)
-    /// `relative`: 是否使用相对定位(相对当前鼠标位置), 默认 `false`
+    /// `direction`: 移动方向 (默认为绝对定位: 逻辑坐标, 主屏幕左上角为原点, 向右向下为正, 见 [crate::coords])
+    /// `options`: 为 `boolean` 时表示是否使用相对定位(相对当前鼠标位置), 默认 `false`; 其余选项见 [MouseMoveOptions]
     #[napi]
-    pub fn mouse_move(&self, direction: MouseLocation, relative: Option<bool>) -> napi::Result<()> {
-        let is_relative = match relative {
+    pub fn mouse_move(&self, direction: MouseLocation, #[napi(ts_arg_type = "boolean | MouseMoveOptions")] options: Option<Either<bool, MouseMoveOptions>>) -> napi::Result<()> {
+        let options = match options {
+            Some(Either::A(relative)) => MouseMoveOptions { relative: Some(relative), screen: None, clamp: None },
+            Some(Either::B(v)) => v,
+            None => MouseMoveOptions { relative: None, screen: None, clamp: None }
+        };
+        let is_relative = match options.relative {
             Some(v) => v,
             None => false
         };
-        if is_relative {
-            Enigo::new().mouse_move_relative(direction.x, direction.y);
+        // 相对移动默认不限制 (与系统原生的相对移动一致, 也避免每次移动都读取屏幕信息)
+        let is_clamped = match options.clamp {
+            Some(v) => v,
+            None => !is_relative
+        };
+
+        // 指定屏幕时必须能获取屏幕信息; 仅用于限制范围时获取失败则不做限制
+        let screens = match options.screen {
+            Some(_) if !is_relative => coords::screens()?,
+            _ => if is_clamped { coords::screens().unwrap_or_default() } else { vec![] }
+        };
+        let scale = if screens.is_empty() { coords::input_scale() } else { coords::input_scale_of(&screens) };
+
+        let mut player = Enigo::new();
+        let (x, y) = if is_relative {
+            if !is_clamped {
+                let (dx, dy) = coords::to_input(direction.x, direction.y, scale);
+                player.mouse_move_relative(dx, dy);
+                return Ok(());
+            }
+            let (cx, cy) = player.mouse_location();
+            let (cx, cy) = coords::from_input(cx, cy, scale);
+            (cx + direction.x, cy + direction.y)
         } else {
-            Enigo::new().mouse_move_to(direction.x, direction.y);
-        }
+            match options.screen {
+                Some(id) => {
+                    let screen = coords::screen_by_id(&screens, id)?;
+                    (screen.x + direction.x, screen.y + direction.y)
+                }
+                None => (direction.x, direction.y)
+            }
+        };
+
+        let (x, y) = if is_clamped { coords::clamp(&screens, x, y) } else { (x, y) };
+        let (x, y) = coords::to_input(x, y, scale);
+        player.mouse_move_to(x, y);
         Ok(())
     }
 
-    /// 鼠标 -- 当前坐标
+    /// 鼠标 -- 当前坐标 (逻辑坐标, 默认相对于主屏幕左上角)
     #[napi]
-    pub fn mouse_location(&self) -> napi::Result<MouseLocation> {
+    pub fn mouse_location(&self, options: Option<MouseLocationOptions>) -> napi::Result<MouseLocation> {
         let location = Enigo::new().mouse_location();
-        Ok(MouseLocation {
-            x: location.0,
-            y: location.1,
-        })
+
+        match options.and_then(|v| v.screen) {
+            Some(id) => {
+                let screens = coords::screens()?;
+                let screen = coords::screen_by_id(&screens, id)?;
+                let (x, y) = coords::from_input(location.0, location.1, coords::input_scale_of(&screens));
+                Ok(MouseLocation { x: x - screen.x, y: y - screen.y })
+            }
+            None => {
+                let (x, y) = coords::from_input(location.0, location.1, coords::input_scale());
+                Ok(MouseLocation { x, y })
+            }
+        }
+    }
+
+    /// 获取所有屏幕的信息 (与 `Captor.listScreens` 相同)
+    #[napi]
+    pub fn list_screens(&self) -> napi::Result<Vec<ScreenInfo>> {
+        coords::screens()
     }
 }
 
@@ -191,7 +244,7 @@ mod unit_test {
     #[test]
     fn key_test() {
         thread::sleep(Duration::from_secs(2));
-        let lo = Controller::new().mouse_location().unwrap();
+        let lo = Controller::new().mouse_location(None).unwrap();
         println!("{:?}", lo);
         // thread::sleep(Duration::from_secs(1));
         // Controller::new().mouse_up("Right".to_string()).unwrap();
@@ -0,0 +1,198 @@
+//! 坐标模型
+//!
+//! - 逻辑坐标: 虚拟桌面坐标, 以主屏幕左上角为原点, 单位与 [ScreenInfo] 一致 (未经缩放).
+//!   所有公开接口 (`Controller` 的鼠标位置, `Captor` 的区域 / 点 / 匹配结果等) 均使用逻辑坐标
+//! - 物理坐标: 截图中的像素坐标, 相对于所在屏幕的左上角, 即 `(逻辑坐标 - 屏幕原点) * 缩放比例`
+//! - 输入坐标: 模拟输入后端使用的坐标. Linux (X11) 为全局物理像素; Windows / macOS 与逻辑坐标一致
+
+use napi::{Error, Status};
+use crate::capture::list_screens;
+use crate::utils::{MouseLocation, PhysicalPoint, ScreenInfo};
+
+/// 包含 (x, y) 的屏幕
+pub fn screen_at(screens: &[ScreenInfo], x: i32, y: i32) -> Option<&ScreenInfo> {
+    screens.iter().find(|s| x >= s.x && x < s.x + s.width as i32 && y >= s.y && y < s.y + s.height as i32)
+}
+
+/// 逻辑坐标 -> 物理坐标 (点不在任何屏幕内时返回 `None`)
+pub fn to_physical(screens: &[ScreenInfo], point: &MouseLocation) -> Option<PhysicalPoint> {
+    screen_at(screens, point.x, point.y).map(|screen| PhysicalPoint {
+        screen_id: screen.id,
+        x: ((point.x - screen.x) as f64 * screen.scale_factor).round() as i32,
+        y: ((point.y - screen.y) as f64 * screen.scale_factor).round() as i32,
+    })
+}
+
+/// 物理坐标 -> 逻辑坐标 (屏幕不存在时返回 `None`)
+pub fn to_logical(screens: &[ScreenInfo], point: &PhysicalPoint) -> Option<MouseLocation> {
+    screens.iter().find(|s| s.id == point.screen_id).map(|screen| MouseLocation {
+        x: screen.x + (point.x as f64 / screen.scale_factor).round() as i32,
+        y: screen.y + (point.y as f64 / screen.scale_factor).round() as i32,
+    })
+}
+
+/// 虚拟桌面中的物理像素坐标 -> 逻辑坐标 (点不在任何屏幕内时返回 `None`)
+///
+/// 用于 Windows 系统接口返回的位置 (如光标位置): 屏幕原点与 [ScreenInfo] 一致, 屏幕范围为 `尺寸 * 缩放比例`
+pub fn desktop_to_logical(screens: &[ScreenInfo], x: i32, y: i32) -> Option<MouseLocation> {
+    let screen = screens.iter().find(|s| {
+        let w = (s.width as f64 * s.scale_factor).round() as i32;
+        let h = (s.height as f64 * s.scale_factor).round() as i32;
+        x >= s.x && x < s.x + w && y >= s.y && y < s.y + h
+    })?;
+    to_logical(screens, &PhysicalPoint { screen_id: screen.id, x: x - screen.x, y: y - screen.y })
+}
+
+/// 将 (x, y) 限制在可见桌面内 (不在任何屏幕内时移至距离最近的屏幕边缘; 没有屏幕时原样返回)
+pub fn clamp(screens: &[ScreenInfo], x: i32, y: i32) -> (i32, i32) {
+    let mut best: Option<((i32, i32), i64)> = None;
+
+    for s in screens {
+        let cx = x.clamp(s.x, s.x + s.width.max(1) as i32 - 1);
+        let cy = y.clamp(s.y, s.y + s.height.max(1) as i32 - 1);
+        let distance = (cx - x) as i64 * (cx - x) as i64 + (cy - y) as i64 * (cy - y) as i64;
+        if distance == 0 {
+            return (x, y);
+        }
+        if best.map_or(true, |(_, d)| distance < d) {
+            best = Some(((cx, cy), distance));
+        }
+    }
+
+    match best {
+        Some((point, _)) => point,
+        None => (x, y)
+    }
+}
+
+/// 输入坐标与逻辑坐标之比
+///
+/// X11 的全局缩放比例对所有屏幕一致, 取主屏幕的缩放比例
+#[cfg(target_os = "linux")]
+pub fn input_scale_of(screens: &[ScreenInfo]) -> f64 {
+    match screens.iter().find(|s| s.is_primary).or(screens.first()) {
+        Some(screen) if screen.scale_factor > 0.0 => screen.scale_factor,
+        _ => 1.0
+    }
+}
+
+/// 输入坐标与逻辑坐标之比
+#[cfg(not(target_os = "linux"))]
+pub fn input_scale_of(_screens: &[ScreenInfo]) -> f64 {
+    1.0
+}
+
+/// 缩放比例的缓存时间 (读取屏幕信息需要往返 X server, 连续移动鼠标时不必每次读取)
+#[cfg(target_os = "linux")]
+const SCALE_TTL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// 缓存的缩放比例 (读取时间, 缩放比例)
+#[cfg(target_os = "linux")]
+static SCALE: std::sync::Mutex<Option<(std::time::Instant, f64)>> = std::sync::Mutex::new(None);
+
+/// 输入坐标与逻辑坐标之比 (无法获取屏幕信息时视为 `1`; 缓存 [SCALE_TTL])
+#[cfg(target_os = "linux")]
+pub fn input_scale() -> f64 {
+    let mut cache = SCALE.lock().unwrap();
+    if let Some((time, scale)) = *cache {
+        if time.elapsed() < SCALE_TTL {
+            return scale;
+        }
+    }
+
+    let scale = match list_screens() {
+        Ok(screens) => input_scale_of(&screens),
+        Err(_) => 1.0
+    };
+    *cache = Some((std::time::Instant::now(), scale));
+    scale
+}
+
+/// 输入坐标与逻辑坐标之比
+#[cfg(not(target_os = "linux"))]
+pub fn input_scale() -> f64 {
+    1.0
+}
+
+/// 逻辑坐标 -> 输入坐标
+pub fn to_input(x: i32, y: i32, scale: f64) -> (i32, i32) {
+    ((x as f64 * scale).round() as i32, (y as f64 * scale).round() as i32)
+}
+
+/// 输入坐标 -> 逻辑坐标
+pub fn from_input(x: i32, y: i32, scale: f64) -> (i32, i32) {
+    ((x as f64 / scale).round() as i32, (y as f64 / scale).round() as i32)
+}
+
+/// 获取所有屏幕的信息 (`Captor` 与 `Controller` 共用)
+pub fn screens() -> napi::Result<Vec<ScreenInfo>> {
+    list_screens().map_err(crate::capture::to_status)
+}
+
+/// 指定 id 的屏幕
+pub fn screen_by_id(screens: &[ScreenInfo], id: u32) -> napi::Result<&ScreenInfo> {
+    match screens.iter().find(|s| s.id == id) {
+        Some(v) => Ok(v),
+        None => Err(Error::new(Status::InvalidArg, format!("Screen with id {} does not exist!", id)))
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    fn screen(id: u32, x: i32, width: u32, scale_factor: f64) -> ScreenInfo {
+        ScreenInfo {
+            id,
+            x,
+            y: 0,
+            width,
+            height: 1000,
+            scale_factor,
+            rotation: 0.0,
+            refresh_rate: 60.0,
+            is_primary: id == 1,
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let screens = vec![screen(1, 0, 1280, 1.5), screen(2, 1280, 1920, 1.0)];
+
+        let physical = to_physical(&screens, &MouseLocation { x: 100, y: 200 }).unwrap();
+        assert_eq!(physical, PhysicalPoint { screen_id: 1, x: 150, y: 300 });
+        assert_eq!(to_logical(&screens, &physical).unwrap(), MouseLocation { x: 100, y: 200 });
+
+        let physical = to_physical(&screens, &MouseLocation { x: 1300, y: 10 }).unwrap();
+        assert_eq!(physical, PhysicalPoint { screen_id: 2, x: 20, y: 10 });
+
+        assert!(to_physical(&screens, &MouseLocation { x: -1, y: 0 }).is_none());
+    }
+
+    #[test]
+    fn desktop() {
+        let screens = vec![screen(1, 0, 1280, 1.5), screen(2, 1920, 1920, 1.0)];
+
+        assert_eq!(desktop_to_logical(&screens, 150, 300).unwrap(), MouseLocation { x: 100, y: 200 });
+        assert_eq!(desktop_to_logical(&screens, 1919, 0).unwrap(), MouseLocation { x: 1279, y: 0 });
+        assert_eq!(desktop_to_logical(&screens, 1940, 10).unwrap(), MouseLocation { x: 1940, y: 10 });
+        assert!(desktop_to_logical(&screens, -1, 0).is_none());
+    }
+
+    #[test]
+    fn clamping() {
+        let screens = vec![screen(1, 0, 1280, 1.5), screen(2, 1280, 1920, 1.0)];
+
+        assert_eq!(clamp(&screens, 100, 100), (100, 100));
+        assert_eq!(clamp(&screens, -50, 2000), (0, 999));
+        assert_eq!(clamp(&screens, 5000, 10), (3199, 10));
+        assert_eq!(clamp(&[], -1, -1), (-1, -1));
+    }
+
+    #[test]
+    fn input() {
+        assert_eq!(to_input(100, 201, 1.5), (150, 302));
+        assert_eq!(from_input(150, 302, 1.5), (100, 201));
+    }
+}
@@ -0,0 +1,222 @@
+use napi::Error;
+use crate::capture::{CaptureErrorCode, CaptureResult};
+use crate::frame::Frame;
+use crate::utils::Rect;
+
+/// 当前光标 (位置为逻辑坐标, 见 [crate::coords])
+#[derive(Debug, Clone)]
+pub struct Cursor {
+    /// 光标位置 x (即热点所在位置)
+    pub x: i32,
+    /// 光标位置 y (即热点所在位置)
+    pub y: i32,
+    /// 热点相对于光标图像左上角的 x 偏移
+    pub hot_x: u32,
+    /// 热点相对于光标图像左上角的 y 偏移
+    pub hot_y: u32,
+    /// 光标图像
+    pub image: Frame,
+}
+
+/// 读取当前光标 (光标被隐藏时返回 `None`)
+#[cfg(target_os = "linux")]
+pub fn current() -> CaptureResult<Option<Cursor>> {
+    use std::ptr;
+    use x11::{xfixes, xlib};
+    use crate::coords::{from_input, input_scale};
+
+    unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            return Err(Error::new(CaptureErrorCode::NoDisplay, String::from("Failed to open the X display!")));
+        }
+
+        let raw = xfixes::XFixesGetCursorImage(display);
+        if raw.is_null() {
+            xlib::XCloseDisplay(display);
+            return Err(Error::new(CaptureErrorCode::CaptureFailed, String::from("Failed to read the cursor image (is the XFixes extension available?)!")));
+        }
+
+        let cursor = &*raw;
+        let (w, h) = (cursor.width as u32, cursor.height as u32);
+        let pixels = std::slice::from_raw_parts(cursor.pixels, (w * h) as usize);
+
+        // XFixes 无法查询光标是否隐藏, 隐藏光标的程序设置的是全透明的光标图像
+        if pixels.iter().all(|px| (*px as u32) >> 24 == 0) {
+            xlib::XFree(raw as *mut _);
+            xlib::XCloseDisplay(display);
+            return Ok(None);
+        }
+
+        // 每个像素为预乘透明度的 ARGB (存放于 `c_ulong` 的低 32 位)
+        let mut image = Frame::new(w, h);
+        for (idx, px) in pixels.iter().enumerate() {
+            let v = *px as u32;
+            let a = v >> 24;
+            let unmultiply = |c: u32| if a == 0 { 0 } else { (c * 255 / a).min(255) as u8 };
+            image.rgba[idx * 4..idx * 4 + 4].copy_from_slice(&[
+                unmultiply((v >> 16) & 0xff),
+                unmultiply((v >> 8) & 0xff),
+                unmultiply(v & 0xff),
+                a as u8,
+            ]);
+        }
+
+        // X11 的光标位置为物理像素, 换算为逻辑坐标
+        let (x, y) = from_input(cursor.x as i32, cursor.y as i32, input_scale());
+        let result = Cursor {
+            x,
+            y,
+            hot_x: cursor.xhot as u32,
+            hot_y: cursor.yhot as u32,
+            image,
+        };
+
+        xlib::XFree(raw as *mut _);
+        xlib::XCloseDisplay(display);
+
+        Ok(Some(result))
+    }
+}
+
+/// 读取当前光标 (光标被隐藏时返回 `None`)
+#[cfg(target_os = "windows")]
+pub fn current() -> CaptureResult<Option<Cursor>> {
+    use std::mem::{size_of, zeroed};
+    use std::ptr;
+    use winapi::um::wingdi::{DeleteObject, GetDIBits, GetObjectW, BITMAP, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS};
+    use winapi::um::winuser::{GetCursorInfo, GetDC, GetIconInfo, ReleaseDC, CURSORINFO, CURSOR_SHOWING, ICONINFO};
+    use crate::capture::list_screens;
+    use crate::coords::desktop_to_logical;
+
+    unsafe {
+        let mut info: CURSORINFO = zeroed();
+        info.cbSize = size_of::<CURSORINFO>() as u32;
+        if GetCursorInfo(&mut info) == 0 {
+            return Err(Error::new(CaptureErrorCode::CaptureFailed, String::from("Failed to read the cursor!")));
+        }
+        if info.flags & CURSOR_SHOWING == 0 {
+            return Ok(None);
+        }
+
+        let mut icon: ICONINFO = zeroed();
+        if GetIconInfo(info.hCursor, &mut icon) == 0 {
+            return Err(Error::new(CaptureErrorCode::CaptureFailed, String::from("Failed to read the cursor image!")));
+        }
+
+        // 单色光标没有彩色位图, 其掩码位图为双倍高度 (上半部分为 AND 掩码, 下半部分为 XOR 掩码)
+        let monochrome = icon.hbmColor.is_null();
+        let handle = if monochrome { icon.hbmMask } else { icon.hbmColor };
+
+        let mut bitmap: BITMAP = zeroed();
+        GetObjectW(handle as _, size_of::<BITMAP>() as i32, &mut bitmap as *mut BITMAP as *mut _);
+        let (w, full_h) = (bitmap.bmWidth as u32, bitmap.bmHeight as u32);
+
+        let mut header: BITMAPINFO = zeroed();
+        header.bmiHeader = BITMAPINFOHEADER {
+            biSize: size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: w as i32,
+            // 负数表示自上而下
+            biHeight: -(full_h as i32),
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB,
+            ..zeroed()
+        };
+
+        let mut bgra = vec![0u8; (w * full_h * 4) as usize];
+        let dc = GetDC(ptr::null_mut());
+        let lines = GetDIBits(dc, handle, 0, full_h, bgra.as_mut_ptr() as *mut _, &mut header, DIB_RGB_COLORS);
+        ReleaseDC(ptr::null_mut(), dc);
+
+        if !icon.hbmColor.is_null() {
+            DeleteObject(icon.hbmColor as _);
+        }
+        if !icon.hbmMask.is_null() {
+            DeleteObject(icon.hbmMask as _);
+        }
+
+        if lines == 0 {
+            return Err(Error::new(CaptureErrorCode::CaptureFailed, String::from("Failed to read the cursor image!")));
+        }
+
+        let h = if monochrome { full_h / 2 } else { full_h };
+        let mut image = Frame::new(w, h);
+        let opaque = bgra.chunks_exact(4).any(|px| px[3] != 0);
+        for idx in 0..(w * h) as usize {
+            let px = if monochrome {
+                let and = bgra[idx * 4] != 0;
+                let xor = bgra[(idx + (w * h) as usize) * 4] != 0;
+                match (and, xor) {
+                    (false, false) => [0, 0, 0, 255],
+                    (false, true) => [255, 255, 255, 255],
+                    (true, false) => [0, 0, 0, 0],
+                    // 反色 -- 无法在截图中还原, 以黑色代替
+                    (true, true) => [0, 0, 0, 255],
+                }
+            } else {
+                let src = &bgra[idx * 4..idx * 4 + 4];
+                // 旧式光标的透明通道全为 0, 视为不透明
+                [src[2], src[1], src[0], if opaque { src[3] } else { 255 }]
+            };
+            image.rgba[idx * 4..idx * 4 + 4].copy_from_slice(&px);
+        }
+
+        // 光标位置为虚拟桌面中的物理像素, 换算为逻辑坐标 (无法获取屏幕信息时原样返回)
+        let (x, y) = (info.ptScreenPos.x, info.ptScreenPos.y);
+        let position = match list_screens() {
+            Ok(screens) => desktop_to_logical(&screens, x, y),
+            Err(_) => None
+        };
+
+        Ok(Some(Cursor {
+            x: position.as_ref().map_or(x, |v| v.x),
+            y: position.as_ref().map_or(y, |v| v.y),
+            hot_x: icon.xHotspot,
+            hot_y: icon.yHotspot,
+            image,
+        }))
+    }
+}
+
+/// 读取当前光标 (无法读取系统光标图像)
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn current() -> CaptureResult<Option<Cursor>> {
+    Err(Error::new(CaptureErrorCode::Unsupported, String::from("Reading the cursor image is not supported on this platform!")))
+}
+
+/// 将当前光标绘制到截取自 `region` 的 `frame` 上
+///
+/// 光标被隐藏、不在区域内或无法读取 (如 Wayland 下无法打开 X display) 时不做处理, 不影响截图本身
+pub fn composite(frame: &mut Frame, region: &Rect) {
+    let cursor = match current() {
+        Ok(Some(v)) => v,
+        _ => return
+    };
+
+    // 截图为物理像素, 需按比例换算
+    let ratio_x = frame.w as f64 / region.w.max(1) as f64;
+    let ratio_y = frame.h as f64 / region.h.max(1) as f64;
+    let x = ((cursor.x - region.x) as f64 * ratio_x).round() as i64 - cursor.hot_x as i64;
+    let y = ((cursor.y - region.y) as f64 * ratio_y).round() as i64 - cursor.hot_y as i64;
+
+    frame.blend(&cursor.image, x, y);
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    #[test]
+    fn cursor() {
+        // 光标被隐藏时无可校验的内容
+        if let Some(cursor) = current().unwrap() {
+            let image = &cursor.image;
+            assert!(image.w > 0 && image.h > 0);
+            assert_eq!(image.rgba.len(), image.w as usize * image.h as usize * 4);
+            // 热点位于光标图像内
+            assert!(cursor.hot_x < image.w && cursor.hot_y < image.h);
+        }
+    }
+}
@@ -0,0 +1,738 @@
+//! 缩写展开
+//!
+//! 订阅全局键盘钩子, 以滚动缓冲区记录最近输入的字符; 缓冲区以某个缩写结尾时, 用退格删除缩写并输出展开结果
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    sync::mpsc::Receiver,
+    thread,
+    time::{Duration, Instant},
+};
+use arboard::{Clipboard as ARBoard};
+use chrono::format::{Item, StrftimeItems};
+use chrono::Local;
+use enigo::{Enigo, Key as EnigoKey, KeyboardControllable};
+use napi::{Error, Status};
+use rdev::Key;
+use crate::hook::{KeyInput, Signal, Subscription};
+use crate::utils::{ExpanderOptions, Expansion};
+
+/// 缓冲区最多保留的字符数
+const BUFFER_SIZE: usize = 64;
+/// 等待输出产生的按键事件的最长时间 (超时未到达的不再等待)
+const ECHO_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 展开模板的组成部分
+#[derive(Debug, Clone, PartialEq)]
+enum Part {
+    Text(String),
+    /// 当前时间 (strftime 格式)
+    Date(String),
+    Clipboard,
+    Cursor,
+}
+
+/// 解析展开模板
+fn parse(template: &str) -> Result<Vec<Part>, String> {
+    let mut parts = vec![];
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+    let mut cursor = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(v) => name.push(v),
+                        None => return Err(format!("Unclosed placeholder `{{{}`", name))
+                    }
+                }
+
+                if !text.is_empty() {
+                    parts.push(Part::Text(text.clone()));
+                    text.clear();
+                }
+                parts.push(match name.as_str() {
+                    "date" => Part::Date(String::from("%Y-%m-%d")),
+                    "time" => Part::Date(String::from("%H:%M")),
+                    "clipboard" => Part::Clipboard,
+                    "cursor" if cursor => return Err(format!("Only one `{{cursor}}` is allowed")),
+                    "cursor" => {
+                        cursor = true;
+                        Part::Cursor
+                    }
+                    _ => match name.strip_prefix("date:") {
+                        Some(format) if StrftimeItems::new(format).all(|item| item != Item::Error) => Part::Date(format.to_string()),
+                        Some(format) => return Err(format!("Invalid date format `{}`", format)),
+                        None => return Err(format!("Unknown placeholder `{{{}}}`", name))
+                    }
+                });
+            }
+            '}' => return Err(format!("Unmatched `}}`")),
+            _ => text.push(c)
+        }
+    }
+
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+    Ok(parts)
+}
+
+/// 展开结果的大小写
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Case {
+    AsIs,
+    Capitalize,
+    Upper,
+}
+
+/// 按实际输入的缩写与注册的缩写判断大小写
+fn case_of(abbreviation: &str, typed: &str) -> Case {
+    if typed == abbreviation {
+        return Case::AsIs;
+    }
+
+    let letters: Vec<char> = typed.chars().filter(|c| c.is_alphabetic()).collect();
+    match letters.first() {
+        Some(first) if first.is_uppercase() => {
+            if letters.len() > 1 && letters.iter().all(|c| c.is_uppercase()) {
+                Case::Upper
+            } else {
+                Case::Capitalize
+            }
+        }
+        _ => Case::AsIs
+    }
+}
+
+fn apply_case(text: &str, case: Case, capitalized: &mut bool) -> String {
+    match case {
+        Case::AsIs => text.to_string(),
+        Case::Upper => text.to_uppercase(),
+        Case::Capitalize => {
+            let mut result = String::new();
+            for c in text.chars() {
+                if !*capitalized && c.is_alphabetic() {
+                    *capitalized = true;
+                    result.extend(c.to_uppercase());
+                } else {
+                    result.push(c);
+                }
+            }
+            result
+        }
+    }
+}
+
+/// 生成展开结果: (文本, 输出后需左移光标的字符数)
+fn render(parts: &[Part], case: Case, date: impl Fn(&str) -> String, clipboard: impl Fn() -> String) -> (String, usize) {
+    let mut text = String::new();
+    let mut cursor = None;
+    let mut capitalized = false;
+
+    for part in parts {
+        match part {
+            Part::Text(v) => text.push_str(&apply_case(v, case, &mut capitalized)),
+            Part::Date(format) => text.push_str(&date(format)),
+            Part::Clipboard => text.push_str(&clipboard()),
+            Part::Cursor => cursor = Some(text.chars().count()),
+        }
+    }
+
+    let back = match cursor {
+        Some(v) => text.chars().count() - v,
+        None => 0
+    };
+    (text, back)
+}
+
+/// 应用名的比较形式
+fn normalize_app(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    match name.strip_suffix(".exe") {
+        Some(v) => v.to_string(),
+        None => name
+    }
+}
+
+/// 当前前台应用的名称 (Windows -- 进程名; Linux -- 窗口的 `WM_CLASS`; macOS -- 应用名)
+#[cfg(target_os = "windows")]
+pub fn foreground_app() -> Option<String> {
+    use std::path::Path;
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::processthreadsapi::OpenProcess;
+    use winapi::um::winbase::QueryFullProcessImageNameW;
+    use winapi::um::winnt::PROCESS_QUERY_LIMITED_INFORMATION;
+    use winapi::um::winuser::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    unsafe {
+        let window = GetForegroundWindow();
+        if window.is_null() {
+            return None;
+        }
+
+        let mut pid = 0;
+        GetWindowThreadProcessId(window, &mut pid);
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process.is_null() {
+            return None;
+        }
+
+        let mut buffer = [0u16; 1024];
+        let mut len = buffer.len() as u32;
+        let ok = QueryFullProcessImageNameW(process, 0, buffer.as_mut_ptr(), &mut len);
+        CloseHandle(process);
+        if ok == 0 {
+            return None;
+        }
+
+        let path = String::from_utf16_lossy(&buffer[..len as usize]);
+        Path::new(&path).file_name().map(|name| name.to_string_lossy().to_string())
+    }
+}
+
+/// 当前前台应用的名称 (Windows -- 进程名; Linux -- 窗口的 `WM_CLASS`; macOS -- 应用名)
+#[cfg(target_os = "linux")]
+pub fn foreground_app() -> Option<String> {
+    use std::{ffi::CStr, mem::zeroed, ptr};
+    use x11::xlib;
+
+    unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            return None;
+        }
+
+        let root = xlib::XDefaultRootWindow(display);
+        let atom = xlib::XInternAtom(display, b"_NET_ACTIVE_WINDOW\0".as_ptr() as *const _, xlib::False);
+        let (mut actual_type, mut actual_format, mut items, mut bytes_after) = (0, 0, 0, 0);
+        let mut prop: *mut u8 = ptr::null_mut();
+        let status = xlib::XGetWindowProperty(
+            display, root, atom, 0, 1, xlib::False, xlib::XA_WINDOW,
+            &mut actual_type, &mut actual_format, &mut items, &mut bytes_after, &mut prop,
+        );
+
+        let mut name = None;
+        if status == xlib::Success as i32 && items > 0 && !prop.is_null() {
+            let window = *(prop as *const xlib::Window);
+            let mut hint: xlib::XClassHint = zeroed();
+            if window != 0 && xlib::XGetClassHint(display, window, &mut hint) != 0 {
+                if !hint.res_class.is_null() {
+                    name = Some(CStr::from_ptr(hint.res_class).to_string_lossy().to_string());
+                    xlib::XFree(hint.res_class as *mut _);
+                }
+                if !hint.res_name.is_null() {
+                    xlib::XFree(hint.res_name as *mut _);
+                }
+            }
+        }
+        if !prop.is_null() {
+            xlib::XFree(prop as *mut _);
+        }
+        xlib::XCloseDisplay(display);
+
+        name
+    }
+}
+
+/// 当前前台应用的名称 (Windows -- 进程名; Linux -- 窗口的 `WM_CLASS`; macOS -- 应用名)
+#[cfg(target_os = "macos")]
+pub fn foreground_app() -> Option<String> {
+    use std::process::Command;
+
+    // 仅在缩写匹配时查询, 无需常驻
+    let front = Command::new("lsappinfo").arg("front").output().ok()?;
+    let asn = String::from_utf8_lossy(&front.stdout).trim().to_string();
+    let info = Command::new("lsappinfo").args(["info", "-only", "name", &asn]).output().ok()?;
+
+    // 输出形如 `"LSDisplayName"="Safari"`
+    let info = String::from_utf8_lossy(&info.stdout).to_string();
+    info.split('=').nth(1).map(|name| name.trim().trim_matches('"').to_string())
+}
+
+/// 当前前台应用的名称
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+pub fn foreground_app() -> Option<String> {
+    None
+}
+
+/// 输出产生的按键事件 (经钩子到达时跳过, 不计入缓冲区)
+#[derive(Debug, Clone, PartialEq)]
+enum Echo {
+    Key(Key),
+    Char(char),
+}
+
+impl Echo {
+    fn matches(&self, input: &KeyInput) -> bool {
+        match self {
+            Echo::Key(key) => input.key == *key,
+            Echo::Char('\n') => input.key == Key::Return,
+            Echo::Char('\t') => input.key == Key::Tab,
+            // Windows 以 VK_PACKET (无法识别的按键) 输出 Unicode 字符, macOS 以 keycode 0 (KeyA) 输出
+            Echo::Char(c) => input.text == Some(c.to_string())
+                || matches!(input.key, Key::Unknown(_))
+                || (cfg!(target_os = "macos") && input.key == Key::KeyA)
+        }
+    }
+}
+
+/// 尚未到达的输出产生的按键事件 (按顺序逐个跳过)
+#[derive(Debug, Default)]
+struct Echoes {
+    pending: VecDeque<Echo>,
+    deadline: Option<Instant>,
+}
+
+impl Echoes {
+    fn expect(&mut self, echoes: Vec<Echo>, now: Instant) {
+        self.pending = echoes.into();
+        self.deadline = Some(now + ECHO_TIMEOUT);
+    }
+
+    /// `input` (按下) 是否是输出产生的按键事件; 等待期间的修饰键 (如输出大写字母时按下的 shift) 一并跳过
+    fn skip(&mut self, input: &KeyInput) -> bool {
+        match self.deadline {
+            Some(deadline) if input.time <= deadline && !self.pending.is_empty() => {}
+            _ => {
+                self.pending.clear();
+                self.deadline = None;
+                return false;
+            }
+        }
+
+        let modifier = matches!(input.key, Key::ShiftLeft | Key::ShiftRight | Key::ControlLeft | Key::ControlRight
+            | Key::Alt | Key::AltGr | Key::MetaLeft | Key::MetaRight);
+        if modifier {
+            return true;
+        }
+        // 不匹配的按键为输出期间实际输入的按键
+        match self.pending.front() {
+            Some(echo) if echo.matches(input) => {
+                self.pending.pop_front();
+                true
+            }
+            _ => false
+        }
+    }
+}
+
+/// 最近输入的字符
+#[derive(Debug, Default)]
+struct Buffer {
+    chars: VecDeque<char>,
+}
+
+impl Buffer {
+    fn push(&mut self, text: &str) {
+        for c in text.chars() {
+            if self.chars.len() == BUFFER_SIZE {
+                self.chars.pop_front();
+            }
+            self.chars.push_back(c);
+        }
+    }
+
+    fn pop(&mut self) {
+        self.chars.pop_back();
+    }
+
+    fn clear(&mut self) {
+        self.chars.clear();
+    }
+
+    /// 末尾的 `n` 个字符
+    fn tail(&self, n: usize) -> Option<String> {
+        if n > self.chars.len() {
+            return None;
+        }
+        Some(self.chars.iter().skip(self.chars.len() - n).collect())
+    }
+}
+
+/// 已注册的展开规则
+struct Entry {
+    expansion: Expansion,
+    parts: Vec<Part>,
+    /// 缩写的字符数
+    len: usize,
+    /// 生效的应用 (已规范化)
+    apps: Option<Vec<String>>,
+}
+
+struct State {
+    entries: Vec<Entry>,
+    buffer: Buffer,
+    enabled: bool,
+    paste: bool,
+    propagate_case: bool,
+    excluded_apps: Vec<String>,
+    echoes: Echoes,
+}
+
+impl State {
+    /// 更新缓冲区, 返回是否需要检查缩写
+    fn record(&mut self, input: &KeyInput) -> bool {
+        let m = input.modifiers;
+        match input.key {
+            Key::Backspace => {
+                self.buffer.pop();
+                false
+            }
+            // 光标移动 / 换行 / 快捷键 -- 缓冲区不再对应光标前的文本
+            Key::Return | Key::KpReturn | Key::Tab | Key::Escape | Key::Delete
+            | Key::UpArrow | Key::DownArrow | Key::LeftArrow | Key::RightArrow
+            | Key::Home | Key::End | Key::PageUp | Key::PageDown | Key::Unknown(_) => {
+                self.buffer.clear();
+                false
+            }
+            _ if m.ctrl() || m.meta() || m.alt_left => {
+                self.buffer.clear();
+                false
+            }
+            _ => match &input.text {
+                Some(text) if !text.is_empty() && !text.chars().any(|c| c.is_control()) => {
+                    self.buffer.push(text);
+                    true
+                }
+                _ => false
+            }
+        }
+    }
+
+    /// 在应用 `app` 中生效的、缓冲区末尾匹配的最长的规则:
+    /// (删除的字符数, 展开模板, 大小写, 是否粘贴)
+    fn expansion(&self, app: &Option<String>) -> Option<(usize, Vec<Part>, Case, bool)> {
+        let (idx, typed) = self.candidates().into_iter().find(|(idx, _)| self.allowed(&self.entries[*idx], app))?;
+        let entry = &self.entries[idx];
+        let case = if self.propagate_case { case_of(&entry.expansion.abbreviation, &typed) } else { Case::AsIs };
+
+        Some((entry.len, entry.parts.clone(), case, self.paste))
+    }
+
+    /// 缓冲区末尾匹配的规则 (按缩写长度从长到短), 及实际输入的缩写
+    fn candidates(&self) -> Vec<(usize, String)> {
+        let mut result = vec![];
+        for (idx, entry) in self.entries.iter().enumerate() {
+            let typed = match self.buffer.tail(entry.len) {
+                Some(v) => v,
+                None => continue
+            };
+            let matched = if self.propagate_case {
+                typed.to_lowercase() == entry.expansion.abbreviation.to_lowercase()
+            } else {
+                typed == entry.expansion.abbreviation
+            };
+            if matched {
+                result.push((idx, typed));
+            }
+        }
+
+        result.sort_by(|a, b| self.entries[b.0].len.cmp(&self.entries[a.0].len));
+        result
+    }
+
+    /// 规则是否在应用 `app` 中生效
+    fn allowed(&self, entry: &Entry, app: &Option<String>) -> bool {
+        let app = match app {
+            Some(v) => normalize_app(v),
+            None => return entry.apps.is_none()
+        };
+
+        !self.excluded_apps.contains(&app) && match &entry.apps {
+            Some(apps) => apps.contains(&app),
+            None => true
+        }
+    }
+}
+
+/// 输出展开结果: 删除缩写, 输出文本, 移动光标; 返回由此产生的按键事件
+fn inject(erase: usize, text: &str, back: usize, paste: bool) -> Vec<Echo> {
+    let mut echoes = vec![];
+    let mut player = Enigo::new();
+    for _ in 0..erase {
+        player.key_click(EnigoKey::Backspace);
+        echoes.push(Echo::Key(Key::Backspace));
+    }
+
+    if paste {
+        if let Ok(mut board) = ARBoard::new() {
+            let previous = board.get_text().ok();
+            if board.set_text(text.to_string()).is_ok() {
+                let modifier = if cfg!(target_os = "macos") { EnigoKey::Meta } else { EnigoKey::Control };
+                player.key_down(modifier);
+                player.key_click(EnigoKey::Layout('v'));
+                player.key_up(modifier);
+                echoes.push(Echo::Key(Key::KeyV));
+
+                // 等待目标应用读取剪贴板后再恢复
+                thread::sleep(Duration::from_millis(150));
+                if let Some(previous) = previous {
+                    let _ = board.set_text(previous);
+                }
+            }
+        }
+    } else {
+        player.key_sequence(text);
+        echoes.extend(text.chars().map(Echo::Char));
+    }
+
+    for _ in 0..back {
+        player.key_click(EnigoKey::LeftArrow);
+        echoes.push(Echo::Key(Key::LeftArrow));
+    }
+    echoes
+}
+
+fn clipboard_text() -> String {
+    match ARBoard::new().and_then(|mut board| board.get_text()) {
+        Ok(v) => v,
+        Err(_) => String::new()
+    }
+}
+
+#[napi]
+pub struct TextExpander {
+    /// 全局键盘钩子的订阅 -- 为 `None` 表示已结束
+    subscription: Mutex<Option<Subscription>>,
+
+    state: Arc<Mutex<State>>,
+}
+
+#[napi]
+impl TextExpander {
+    /// 子线程中处理按键事件
+    fn setup(&self, signal: Receiver<Signal>) {
+        let state = self.state.clone();
+
+        thread::spawn(move || {
+            for msg in signal.iter() {
+                let input = match msg {
+                    Signal::Key(v) => v,
+                    Signal::Failed(_) => continue,
+                    Signal::Stop => break
+                };
+
+                {
+                    let mut state = state.lock().unwrap();
+                    if !input.down || !state.enabled || state.echoes.skip(&input) {
+                        continue;
+                    }
+                    if !state.record(&input) || state.candidates().is_empty() {
+                        continue;
+                    }
+                }
+
+                // 查询前台应用及输出期间不持有锁 (不阻塞 js 侧的调用); 事件只在本线程处理, 缓冲区不会因此改变
+                let app = foreground_app();
+                let (erase, parts, case, paste) = match state.lock().unwrap().expansion(&app) {
+                    Some(v) => v,
+                    None => continue
+                };
+                let (text, back) = render(&parts, case, |format| Local::now().format(format).to_string(), clipboard_text);
+                let echoes = inject(erase, &text, back, paste);
+
+                let mut state = state.lock().unwrap();
+                state.buffer.clear();
+                state.echoes.expect(echoes, Instant::now());
+            }
+        });
+    }
+
+    #[napi(constructor)]
+    pub fn new(options: Option<ExpanderOptions>) -> napi::Result<Self> {
+        let (method, propagate_case, excluded_apps) = match options {
+            Some(v) => (v.method, v.propagate_case, v.excluded_apps),
+            None => (None, None, None)
+        };
+        let paste = match method.as_deref() {
+            Some("paste") => true,
+            Some("type") | None => false,
+            Some(v) => return Err(Error::new(Status::InvalidArg, format!("Unsupported method: {}!", v)))
+        };
+
+        let (subscription, signal) = Subscription::new();
+        let instance = TextExpander {
+            subscription: Mutex::new(Some(subscription)),
+            state: Arc::new(Mutex::new(State {
+                entries: vec![],
+                buffer: Buffer::default(),
+                enabled: true,
+                paste,
+                propagate_case: match propagate_case {
+                    Some(v) => v,
+                    None => true
+                },
+                excluded_apps: match excluded_apps {
+                    Some(v) => v.iter().map(|app| normalize_app(app)).collect(),
+                    None => vec![]
+                },
+                echoes: Echoes::default(),
+            })),
+        };
+
+        instance.setup(signal);
+
+        Ok(instance)
+    }
+
+    /// 添加缩写 (替换缩写相同的规则; 展开文本含未知或格式有误的占位符时抛出异常)
+    #[napi]
+    pub fn add(&self, expansion: Expansion) -> napi::Result<()> {
+        if expansion.abbreviation.is_empty() {
+            return Err(Error::new(Status::InvalidArg, String::from("The abbreviation must not be empty!")));
+        }
+        let parts = match parse(&expansion.expansion) {
+            Ok(v) => v,
+            Err(reason) => return Err(Error::new(Status::InvalidArg, format!("Invalid expansion of {}: {}!", expansion.abbreviation, reason)))
+        };
+
+        let mut state = self.state.lock().unwrap();
+        state.entries.retain(|entry| entry.expansion.abbreviation != expansion.abbreviation);
+        state.entries.push(Entry {
+            len: expansion.abbreviation.chars().count(),
+            apps: expansion.apps.as_ref().map(|apps| apps.iter().map(|app| normalize_app(app)).collect()),
+            parts,
+            expansion,
+        });
+
+        Ok(())
+    }
+
+    /// 移除缩写 (返回值表示此前是否已添加)
+    #[napi]
+    pub fn remove(&self, abbreviation: String) -> napi::Result<bool> {
+        let mut state = self.state.lock().unwrap();
+        let count = state.entries.len();
+        state.entries.retain(|entry| entry.expansion.abbreviation != abbreviation);
+
+        Ok(state.entries.len() != count)
+    }
+
+    /// 已添加的规则 (按添加顺序)
+    #[napi(getter)]
+    pub fn expansions(&self) -> napi::Result<Vec<Expansion>> {
+        Ok(self.state.lock().unwrap().entries.iter().map(|entry| entry.expansion.clone()).collect())
+    }
+
+    /// 是否启用展开 (默认为 `true`)
+    #[napi(getter)]
+    pub fn enabled(&self) -> napi::Result<bool> {
+        Ok(self.state.lock().unwrap().enabled)
+    }
+
+    #[napi(setter)]
+    pub fn set_enabled(&self, enabled: bool) -> napi::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.enabled = enabled;
+        state.buffer.clear();
+
+        Ok(())
+    }
+
+    /// 前台应用的名称 (即 `apps` / `excludedApps` 匹配的名称, 无法获取时为 null)
+    #[napi]
+    pub fn active_app() -> Option<String> {
+        foreground_app()
+    }
+
+    /// 结束监听 (与 `Observer.dispose` 相同, 必须调用!)
+    #[napi]
+    pub fn dispose(&self) -> napi::Result<()> {
+        if let Some(subscription) = self.subscription.lock().unwrap().take() {
+            drop(subscription);
+            self.state.lock().unwrap().entries.clear();
+        }
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    #[test]
+    fn template() {
+        assert_eq!(parse("Best, {cursor}\n--{{me}}").unwrap(), vec![
+            Part::Text(String::from("Best, ")),
+            Part::Cursor,
+            Part::Text(String::from("\n--{me}")),
+        ]);
+        assert_eq!(parse("{date:%d/%m}").unwrap(), vec![Part::Date(String::from("%d/%m"))]);
+        assert!(parse("{cursor}{cursor}").is_err());
+        assert!(parse("{unknown}").is_err());
+        assert!(parse("{date").is_err());
+        assert!(parse("a } b").is_err());
+
+        let parts = parse("on {date}, {clipboard}{cursor}!").unwrap();
+        let (text, back) = render(&parts, Case::AsIs, |_| String::from("2024-01-02"), || String::from("copied"));
+        assert_eq!((text.as_str(), back), ("on 2024-01-02, copied!", 1));
+    }
+
+    #[test]
+    fn case() {
+        assert_eq!(case_of(";sig", ";sig"), Case::AsIs);
+        assert_eq!(case_of(";sig", ";Sig"), Case::Capitalize);
+        assert_eq!(case_of(";sig", ";SIG"), Case::Upper);
+
+        let parts = parse("best regards, {clipboard}").unwrap();
+        let clipboard = || String::from("bob");
+        assert_eq!(render(&parts, Case::Capitalize, |_| String::new(), clipboard).0, "Best regards, bob");
+        assert_eq!(render(&parts, Case::Upper, |_| String::new(), clipboard).0, "BEST REGARDS, bob");
+    }
+
+    #[test]
+    fn buffer() {
+        let mut buffer = Buffer::default();
+        buffer.push(";sgi");
+        buffer.pop();
+        buffer.pop();
+        buffer.push("ig");
+        assert_eq!(buffer.tail(4), Some(String::from(";sig")));
+        assert_eq!(buffer.tail(5), None);
+
+        buffer.push(&"x".repeat(BUFFER_SIZE));
+        assert_eq!(buffer.chars.len(), BUFFER_SIZE);
+        assert_eq!(normalize_app(" Code.EXE "), "code");
+    }
+
+    fn press(key: Key, text: Option<&str>, time: Instant) -> KeyInput {
+        KeyInput { key, down: true, repeat: false, modifiers: Default::default(), locks: Default::default(), text: text.map(String::from), held_back: false, time }
+    }
+
+    #[test]
+    fn echoes() {
+        let now = Instant::now();
+        let mut echoes = Echoes::default();
+        echoes.expect(vec![Echo::Key(Key::Backspace), Echo::Char('H'), Echo::Char('i')], now);
+
+        assert!(echoes.skip(&press(Key::Backspace, None, now)));
+        // 输出大写字母时按下的 shift
+        assert!(echoes.skip(&press(Key::ShiftLeft, None, now)));
+        assert!(echoes.skip(&press(Key::KeyH, Some("H"), now)));
+        // 输出期间实际输入的按键
+        assert!(!echoes.skip(&press(Key::KeyX, Some("x"), now)));
+        assert!(echoes.skip(&press(Key::KeyI, Some("i"), now)));
+        assert!(!echoes.skip(&press(Key::KeyI, Some("i"), now)));
+
+        // 超时未到达的不再等待
+        echoes.expect(vec![Echo::Key(Key::Backspace)], now);
+        assert!(!echoes.skip(&press(Key::Backspace, None, now + ECHO_TIMEOUT * 2)));
+        assert!(!echoes.skip(&press(Key::Backspace, None, now)));
+    }
+}
@@ -0,0 +1,414 @@
+use std::io::Cursor;
+use image::{ColorType, ImageEncoder};
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{CompressionType, FilterType, PngEncoder};
+use image::codecs::qoi::QoiEncoder;
+use image::codecs::webp::{WebPEncoder, WebPQuality};
+use napi::Error;
+use screenshots::Image;
+use crate::capture::{CaptureErrorCode, CaptureResult};
+use crate::utils::{Color, EncodeOptions, RawImage};
+
+/// jpeg / webp 默认质量
+const DEFAULT_QUALITY: u32 = 80;
+/// 像素缓冲的最大字节数 (像素偏移以 u32 计算)
+const MAX_BYTES: u64 = u32::MAX as u64;
+
+/// 像素缓冲 (RGBA, 每像素 4 字节, 行间无填充)
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub w: u32,
+    pub h: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Frame {
+    /// 创建全透明的空白缓冲 (尺寸来自外部输入时须先经 [Frame::check_size] 校验)
+    pub fn new(w: u32, h: u32) -> Self {
+        Frame { w, h, rgba: vec![0; w as usize * h as usize * 4] }
+    }
+
+    /// 校验缓冲尺寸, 过大时返回异常
+    pub fn check_size(w: u32, h: u32) -> CaptureResult<()> {
+        if w as u64 * h as u64 * 4 > MAX_BYTES {
+            Err(Error::new(CaptureErrorCode::InvalidArg, format!("The image is too large ({} x {})!", w, h)))
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn from_image(image: &Image) -> Self {
+        Frame { w: image.width(), h: image.height(), rgba: image.rgba().clone() }
+    }
+
+    /// 解码 png / jpeg / webp / qoi 图像
+    pub fn decode(bytes: &[u8]) -> CaptureResult<Self> {
+        match image::load_from_memory(bytes) {
+            Ok(img) => {
+                let rgba = img.to_rgba8();
+                Ok(Frame { w: rgba.width(), h: rgba.height(), rgba: rgba.into_raw() })
+            }
+            Err(err) => Err(Error::new(CaptureErrorCode::InvalidArg, format!("Failed to decode the image! details: {}", err)))
+        }
+    }
+
+    /// 由 [RawImage] 构造 (`format` 为空时视为 `rgba` 原始像素)
+    pub fn from_raw(image: &RawImage) -> CaptureResult<Self> {
+        let bgra = match image.format.as_deref() {
+            None | Some("rgba") => false,
+            Some("bgra") => true,
+            Some(_) => return Frame::decode(&image.bytes),
+        };
+
+        Frame::check_size(image.w, image.h)?;
+        let row = image.w as usize * 4;
+        let stride = match image.stride {
+            Some(v) => v as usize,
+            None => row
+        };
+        if stride < row || image.bytes.len() < stride * image.h as usize {
+            return Err(Error::new(CaptureErrorCode::InvalidArg, String::from("The length of the image bytes does not match its size!")));
+        }
+
+        let mut rgba = Vec::with_capacity(row * image.h as usize);
+        for y in 0..image.h as usize {
+            rgba.extend_from_slice(&image.bytes[y * stride..y * stride + row]);
+        }
+        if bgra {
+            for px in rgba.chunks_exact_mut(4) {
+                px.swap(0, 2);
+            }
+        }
+
+        Ok(Frame { w: image.w, h: image.h, rgba })
+    }
+
+    /// 将 `src` 缩放 (最近邻) 后绘制到本缓冲的 (dx, dy, dw, dh) 区域, 超出部分被裁剪
+    pub fn blit_scaled(&mut self, src: &Frame, dx: u32, dy: u32, dw: u32, dh: u32) {
+        if src.w == 0 || src.h == 0 {
+            return;
+        }
+
+        for y in 0..dh {
+            let ty = dy + y;
+            if ty >= self.h {
+                break;
+            }
+            let sy = (y as u64 * src.h as u64 / dh as u64) as u32;
+
+            for x in 0..dw {
+                let tx = dx + x;
+                if tx >= self.w {
+                    break;
+                }
+                let sx = (x as u64 * src.w as u64 / dw as u64) as u32;
+
+                let from = ((sy * src.w + sx) * 4) as usize;
+                let to = ((ty * self.w + tx) * 4) as usize;
+                self.rgba[to..to + 4].copy_from_slice(&src.rgba[from..from + 4]);
+            }
+        }
+    }
+
+    /// 将 `overlay` 按透明度混合绘制到 (x, y) 处 (可为负数, 超出部分被裁剪)
+    pub fn blend(&mut self, overlay: &Frame, x: i64, y: i64) {
+        for oy in 0..overlay.h as i64 {
+            let ty = y + oy;
+            if ty < 0 || ty >= self.h as i64 {
+                continue;
+            }
+
+            for ox in 0..overlay.w as i64 {
+                let tx = x + ox;
+                if tx < 0 || tx >= self.w as i64 {
+                    continue;
+                }
+
+                let from = ((oy * overlay.w as i64 + ox) * 4) as usize;
+                let to = ((ty * self.w as i64 + tx) * 4) as usize;
+                let alpha = overlay.rgba[from + 3] as u32;
+                for c in 0..3 {
+                    let (src, dst) = (overlay.rgba[from + c] as u32, self.rgba[to + c] as u32);
+                    self.rgba[to + c] = ((src * alpha + dst * (255 - alpha)) / 255) as u8;
+                }
+                self.rgba[to + 3] = self.rgba[to + 3].max(alpha as u8);
+            }
+        }
+    }
+
+    /// 读取 (x, y) 处的像素 (超出范围时返回 `None`)
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.w || y >= self.h {
+            return None;
+        }
+
+        let idx = ((y * self.w + x) * 4) as usize;
+        Some(Color {
+            r: self.rgba[idx],
+            g: self.rgba[idx + 1],
+            b: self.rgba[idx + 2],
+            a: self.rgba[idx + 3],
+        })
+    }
+
+    /// 裁剪出 (x, y, w, h) 区域 (超出部分被裁剪)
+    pub fn crop(&self, x: u32, y: u32, w: u32, h: u32) -> Frame {
+        let x = x.min(self.w);
+        let y = y.min(self.h);
+        let w = w.min(self.w - x);
+        let h = h.min(self.h - y);
+
+        let mut rgba = Vec::with_capacity((w * h * 4) as usize);
+        for row in y..y + h {
+            let from = ((row * self.w + x) * 4) as usize;
+            rgba.extend_from_slice(&self.rgba[from..from + (w * 4) as usize]);
+        }
+
+        Frame { w, h, rgba }
+    }
+
+    /// 与上一帧比较, 返回发生变化的区域 (x, y, w, h)
+    ///
+    /// 按 `tile` x `tile` 的图块逐块比较, 同一行中相邻的变化图块合并为一个区域;
+    /// 尺寸不一致时整帧视为变化
+    pub fn diff(&self, prev: &Frame, tile: u32) -> Vec<(u32, u32, u32, u32)> {
+        if self.w != prev.w || self.h != prev.h {
+            return vec![(0, 0, self.w, self.h)];
+        }
+
+        let tile = tile.max(1);
+        let mut rects = vec![];
+
+        for ty in (0..self.h).step_by(tile as usize) {
+            let th = tile.min(self.h - ty);
+            // 当前行中正在合并的区域 (起始 x, 宽度)
+            let mut run: Option<(u32, u32)> = None;
+
+            for tx in (0..self.w).step_by(tile as usize) {
+                let tw = tile.min(self.w - tx);
+                let changed = (ty..ty + th).any(|row| {
+                    let from = ((row * self.w + tx) * 4) as usize;
+                    let to = from + (tw * 4) as usize;
+                    self.rgba[from..to] != prev.rgba[from..to]
+                });
+
+                run = match (changed, run) {
+                    (true, Some((x, w))) => Some((x, w + tw)),
+                    (true, None) => Some((tx, tw)),
+                    (false, Some((x, w))) => {
+                        rects.push((x, ty, w, th));
+                        None
+                    }
+                    (false, None) => None,
+                };
+            }
+
+            if let Some((x, w)) = run {
+                rects.push((x, ty, w, th));
+            }
+        }
+
+        rects
+    }
+
+    /// 去除透明通道 (jpeg 不支持透明通道)
+    fn to_rgb(&self) -> Vec<u8> {
+        let mut rgb = Vec::with_capacity((self.w * self.h * 3) as usize);
+        for px in self.rgba.chunks_exact(4) {
+            rgb.extend_from_slice(&px[0..3]);
+        }
+        rgb
+    }
+
+    /// 编码为 png
+    pub fn to_png(&self) -> CaptureResult<Vec<u8>> {
+        self.encode_png(CompressionType::Default)
+    }
+
+    fn encode_png(&self, compression: CompressionType) -> CaptureResult<Vec<u8>> {
+        let mut buffer = Cursor::new(vec![]);
+        match PngEncoder::new_with_quality(&mut buffer, compression, FilterType::Adaptive).write_image(&self.rgba, self.w, self.h, ColorType::Rgba8) {
+            Ok(_) => Ok(buffer.into_inner()),
+            Err(err) => Err(encode_failed("png", err))
+        }
+    }
+
+    /// 按 `options` 编码 (原始像素格式会同时返回 `stride`)
+    pub fn encode(&self, options: &EncodeOptions) -> CaptureResult<RawImage> {
+        let quality = match options.quality {
+            Some(v) => v.clamp(1, 100),
+            None => DEFAULT_QUALITY
+        };
+
+        let (bytes, stride) = match options.format.as_str() {
+            "rgba" => (self.rgba.clone(), Some(self.w * 4)),
+            "bgra" => {
+                let mut bgra = self.rgba.clone();
+                for px in bgra.chunks_exact_mut(4) {
+                    px.swap(0, 2);
+                }
+                (bgra, Some(self.w * 4))
+            }
+            "png" => {
+                // 0 ~ 9, 与 zlib 的压缩等级对应
+                let compression = match options.compression {
+                    Some(0..=3) => CompressionType::Fast,
+                    Some(7..=u32::MAX) => CompressionType::Best,
+                    _ => CompressionType::Default,
+                };
+                (self.encode_png(compression)?, None)
+            }
+            "jpeg" => {
+                let mut buffer = Cursor::new(vec![]);
+                match JpegEncoder::new_with_quality(&mut buffer, quality as u8).write_image(&self.to_rgb(), self.w, self.h, ColorType::Rgb8) {
+                    Ok(_) => (buffer.into_inner(), None),
+                    Err(err) => return Err(encode_failed("jpeg", err))
+                }
+            }
+            "webp" => {
+                let mut buffer = Cursor::new(vec![]);
+                // 质量为 100 时使用无损编码
+                #[allow(deprecated)]
+                let encoder = if quality == 100 {
+                    WebPEncoder::new_lossless(&mut buffer)
+                } else {
+                    WebPEncoder::new_with_quality(&mut buffer, WebPQuality::lossy(quality as u8))
+                };
+                match encoder.encode(&self.rgba, self.w, self.h, ColorType::Rgba8) {
+                    Ok(_) => (buffer.into_inner(), None),
+                    Err(err) => return Err(encode_failed("webp", err))
+                }
+            }
+            "qoi" => {
+                let mut buffer = Cursor::new(vec![]);
+                match QoiEncoder::new(&mut buffer).write_image(&self.rgba, self.w, self.h, ColorType::Rgba8) {
+                    Ok(_) => (buffer.into_inner(), None),
+                    Err(err) => return Err(encode_failed("qoi", err))
+                }
+            }
+            other => return Err(Error::new(CaptureErrorCode::InvalidArg, format!("Unsupported image format: {}!", other)))
+        };
+
+        Ok(RawImage {
+            w: self.w,
+            h: self.h,
+            bytes,
+            stride,
+            format: Some(options.format.clone()),
+        })
+    }
+}
+
+/// 编码失败的异常
+fn encode_failed(format: &str, err: impl std::fmt::Display) -> Error<CaptureErrorCode> {
+    Error::new(CaptureErrorCode::EncodeFailed, format!("Failed to encode the capture as {}! details: {}", format, err))
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    fn options(format: &str) -> EncodeOptions {
+        EncodeOptions { format: String::from(format), compression: None, quality: None }
+    }
+
+    #[test]
+    fn blit() {
+        let mut src = Frame::new(2, 2);
+        src.rgba[0..4].copy_from_slice(&[255, 0, 0, 255]);
+
+        let mut canvas = Frame::new(6, 4);
+        canvas.blit_scaled(&src, 2, 0, 4, 4);
+
+        assert_eq!(&canvas.rgba[8..12], &[255, 0, 0, 255]);
+        assert_eq!(&canvas.rgba[12..16], &[255, 0, 0, 255]);
+        assert_eq!(&canvas.rgba[16..20], &[0, 0, 0, 0]);
+        assert_eq!(&canvas.rgba[0..4], &[0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn blend() {
+        let mut frame = Frame::new(2, 2);
+        for px in frame.rgba.chunks_exact_mut(4) {
+            px.copy_from_slice(&[0, 0, 200, 255]);
+        }
+
+        let mut overlay = Frame::new(2, 2);
+        overlay.rgba[0..4].copy_from_slice(&[255, 0, 0, 255]);
+        overlay.rgba[4..8].copy_from_slice(&[255, 0, 0, 0]);
+
+        frame.blend(&overlay, 1, -1);
+        assert_eq!(&frame.rgba[4..8], &[0, 0, 200, 255]);
+
+        frame.blend(&overlay, 1, 0);
+        assert_eq!(&frame.rgba[4..8], &[255, 0, 0, 255]);
+        assert_eq!(&frame.rgba[0..4], &[0, 0, 200, 255]);
+    }
+
+    #[test]
+    fn size() {
+        assert!(Frame::check_size(7680, 4320).is_ok());
+        assert_eq!(Frame::check_size(70000, 70000).unwrap_err().status, CaptureErrorCode::InvalidArg);
+        assert!(Frame::check_size(u32::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn crop() {
+        let mut frame = Frame::new(4, 4);
+        frame.rgba[(4 + 1) * 4] = 9;
+
+        let part = frame.crop(1, 1, 10, 2);
+        assert_eq!((part.w, part.h), (3, 2));
+        assert_eq!(part.rgba[0], 9);
+    }
+
+    #[test]
+    fn diff() {
+        let prev = Frame::new(8, 8);
+        assert!(prev.diff(&prev, 2).is_empty());
+
+        let mut next = prev.clone();
+        next.rgba[0] = 1;
+        next.rgba[(2 * 8 + 3) * 4] = 1;
+        next.rgba[(2 * 8 + 4) * 4] = 1;
+        assert_eq!(next.diff(&prev, 2), vec![(0, 0, 2, 2), (2, 2, 4, 2)]);
+
+        assert_eq!(Frame::new(4, 4).diff(&prev, 2), vec![(0, 0, 4, 4)]);
+    }
+
+    #[test]
+    fn from_raw() {
+        let image = RawImage {
+            w: 1,
+            h: 2,
+            bytes: vec![3, 2, 1, 255, 0, 0, 0, 0, 6, 5, 4, 255, 0, 0, 0, 0],
+            stride: Some(8),
+            format: Some(String::from("bgra")),
+        };
+
+        let frame = Frame::from_raw(&image).unwrap();
+        assert_eq!(frame.rgba, vec![1, 2, 3, 255, 4, 5, 6, 255]);
+
+        let png = frame.encode(&options("png")).unwrap();
+        assert_eq!(Frame::from_raw(&png).unwrap().rgba, frame.rgba);
+    }
+
+    #[test]
+    fn encode() {
+        let mut frame = Frame::new(4, 2);
+        frame.rgba[0..4].copy_from_slice(&[1, 2, 3, 255]);
+
+        let bgra = frame.encode(&options("bgra")).unwrap();
+        assert_eq!(&bgra.bytes[0..4], &[3, 2, 1, 255]);
+        assert_eq!(bgra.stride, Some(16));
+
+        for format in ["png", "jpeg", "webp", "qoi"] {
+            let image = frame.encode(&options(format)).unwrap();
+            assert!(!image.bytes.is_empty());
+            assert_eq!(image.stride, None);
+        }
+
+        assert!(frame.encode(&options("bmp")).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+//! 按键手势识别 (由 [crate::observer::Observer] 的按键事件流驱动的状态机)
+
+use std::time::{Duration, Instant};
+use crate::utils::KeyEvRegister;
+
+/// 是否是修饰键 (修饰键本身的按下不打断按键序列)
+pub fn is_modifier(key: &str) -> bool {
+    matches!(key, "ControlLeft" | "ControlRight" | "ShiftLeft" | "ShiftRight" | "AltLeft" | "AltRight" | "Meta")
+}
+
+/// 按键序列 (如 `g g` / `ctrl + K, ctrl + S`): 每一步须在上一步之后的 `timeout` 内按下
+#[derive(Debug, Clone)]
+pub struct Sequence {
+    steps: Vec<KeyEvRegister>,
+    timeout: Duration,
+    /// 失配表: `failure[i]` 为前 `i + 1` 步中既是前缀又是后缀的最长部分的长度 (不含自身)
+    failure: Vec<usize>,
+    /// 已匹配的步数
+    progress: usize,
+    /// 下一步的截止时间
+    deadline: Option<Instant>,
+}
+
+impl Sequence {
+    pub fn new(steps: Vec<KeyEvRegister>, timeout: Duration) -> Self {
+        let mut failure = vec![0; steps.len()];
+        let mut len = 0;
+        for i in 1..steps.len() {
+            while len > 0 && steps[i] != steps[len] {
+                len = failure[len - 1];
+            }
+            if steps[i] == steps[len] {
+                len += 1;
+            }
+            failure[i] = len;
+        }
+
+        Sequence { steps, timeout, failure, progress: 0, deadline: None }
+    }
+
+    /// 输入一次按下 (`None` 表示无法识别的按键), 返回序列是否完成
+    pub fn feed(&mut self, step: Option<&KeyEvRegister>, time: Instant) -> bool {
+        if let Some(deadline) = self.deadline {
+            if time > deadline {
+                self.reset();
+            }
+        }
+
+        let step = match step {
+            Some(v) => v,
+            None => {
+                self.reset();
+                return false;
+            }
+        };
+
+        // 失配时退回到仍然匹配的最长前缀 (如 `g g x` 中按下 `g g g` 后仍已匹配 `g g`)
+        while self.progress > 0 && self.steps[self.progress] != *step {
+            self.progress = self.failure[self.progress - 1];
+        }
+        if self.steps[self.progress] != *step {
+            self.reset();
+            return false;
+        }
+
+        self.progress += 1;
+        if self.progress == self.steps.len() {
+            self.reset();
+            return true;
+        }
+        self.deadline = Some(time + self.timeout);
+        false
+    }
+
+    /// 进行中的序列: (已匹配的步数, 剩余等待时间)
+    pub fn pending(&self, now: Instant) -> Option<(usize, Duration)> {
+        match self.deadline {
+            Some(deadline) if self.progress > 0 && now <= deadline => Some((self.progress, deadline - now)),
+            _ => None
+        }
+    }
+
+    fn reset(&mut self) {
+        self.progress = 0;
+        self.deadline = None;
+    }
+}
+
+/// 按键名是否匹配目标键 (`Shift` / `Control` / `Alt` 匹配左右两侧)
+pub fn matches_key(target: &str, key: Option<&str>) -> bool {
+    match (target, key) {
+        (_, None) => false,
+        ("Shift", Some(v)) => v == "ShiftLeft" || v == "ShiftRight",
+        ("Control", Some(v)) => v == "ControlLeft" || v == "ControlRight",
+        ("Alt", Some(v)) => v == "AltLeft" || v == "AltRight",
+        (target, Some(v)) => target == v,
+    }
+}
+
+/// 双击: 目标键 按下 -- 释放 -- 按下, 两次按下的间隔不超过 `max_gap`, 期间没有按下其他键
+#[derive(Debug, Clone)]
+pub struct DoubleTap {
+    key: String,
+    max_gap: Duration,
+    /// 第一次按下的时间
+    first: Option<Instant>,
+    /// 第一次按下后是否已释放
+    released: bool,
+}
+
+impl DoubleTap {
+    pub fn new(key: String, max_gap: Duration) -> Self {
+        DoubleTap { key, max_gap, first: None, released: false }
+    }
+
+    /// 输入一次按键事件, 返回是否构成双击
+    pub fn feed(&mut self, key: Option<&str>, down: bool, repeat: bool, time: Instant) -> bool {
+        if !matches_key(&self.key, key) {
+            // 其他按键的按下打断双击
+            if down {
+                self.first = None;
+            }
+            return false;
+        }
+
+        if repeat {
+            // 长按不是点击
+            self.first = None;
+        } else if down {
+            match self.first {
+                Some(first) if self.released && time - first <= self.max_gap => {
+                    self.first = None;
+                    return true;
+                }
+                _ => {
+                    self.first = Some(time);
+                    self.released = false;
+                }
+            }
+        } else if self.first.is_some() {
+            self.released = true;
+        }
+        false
+    }
+}
+
+/// 长按: 目标键按下并保持 `hold` 后触发 (每次按下最多触发一次; 期间按下其他键则取消)
+#[derive(Debug, Clone)]
+pub struct LongPress {
+    key: String,
+    hold: Duration,
+    /// 按下的时间 (尚未触发时)
+    pressed: Option<Instant>,
+}
+
+impl LongPress {
+    pub fn new(key: String, hold: Duration) -> Self {
+        LongPress { key, hold, pressed: None }
+    }
+
+    /// 输入一次按键事件
+    pub fn feed(&mut self, key: Option<&str>, down: bool, repeat: bool, time: Instant) {
+        if matches_key(&self.key, key) {
+            if !down {
+                self.pressed = None;
+            } else if !repeat {
+                self.pressed = Some(time);
+            }
+        } else if down && !repeat {
+            self.pressed = None;
+        }
+    }
+
+    /// 触发时间
+    pub fn deadline(&self) -> Option<Instant> {
+        self.pressed.map(|pressed| pressed + self.hold)
+    }
+
+    /// 返回是否已保持足够时间 (触发后直至下次按下不再触发)
+    pub fn poll(&mut self, now: Instant) -> bool {
+        match self.deadline() {
+            Some(deadline) if now >= deadline => {
+                self.pressed = None;
+                true
+            }
+            _ => false
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    fn down(key: &str, ctrl: bool) -> KeyEvRegister {
+        KeyEvRegister::new(String::from(key), ctrl, false, false, false, true)
+    }
+
+    #[test]
+    fn sequence() {
+        let timeout = Duration::from_millis(500);
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+
+        let mut chord = Sequence::new(vec![down("KeyK", true), down("KeyS", true)], timeout);
+        assert!(!chord.feed(Some(&down("KeyK", true)), at(0)));
+        assert_eq!(chord.pending(at(100)), Some((1, Duration::from_millis(400))));
+        assert!(chord.feed(Some(&down("KeyS", true)), at(200)));
+        assert_eq!(chord.pending(at(200)), None);
+
+        // 超时
+        chord.feed(Some(&down("KeyK", true)), at(1000));
+        assert!(!chord.feed(Some(&down("KeyS", true)), at(1600)));
+
+        // 被其他按键 / 无法识别的按键打断
+        chord.feed(Some(&down("KeyK", true)), at(2000));
+        chord.feed(None, at(2100));
+        assert!(!chord.feed(Some(&down("KeyS", true)), at(2200)));
+
+        // `g g g`: 打断的按键作为新一轮的第一步
+        let mut gg = Sequence::new(vec![down("KeyG", false), down("KeyG", false)], timeout);
+        assert!(!gg.feed(Some(&down("KeyG", false)), at(0)));
+        assert!(gg.feed(Some(&down("KeyG", false)), at(100)));
+        assert!(!gg.feed(Some(&down("KeyX", false)), at(200)));
+        assert!(!gg.feed(Some(&down("KeyG", false)), at(300)));
+        assert!(gg.feed(Some(&down("KeyG", false)), at(400)));
+
+        // `g g x`: 按下 `g g g x` 时保留最长的已匹配前缀
+        let mut ggx = Sequence::new(vec![down("KeyG", false), down("KeyG", false), down("KeyX", false)], timeout);
+        assert!(!ggx.feed(Some(&down("KeyG", false)), at(0)));
+        assert!(!ggx.feed(Some(&down("KeyG", false)), at(100)));
+        assert!(!ggx.feed(Some(&down("KeyG", false)), at(200)));
+        assert_eq!(ggx.pending(at(200)), Some((2, timeout)));
+        assert!(ggx.feed(Some(&down("KeyX", false)), at(300)));
+        assert!(!ggx.feed(Some(&down("KeyG", false)), at(400)));
+        assert!(!ggx.feed(Some(&down("KeyG", false)), at(500)));
+        assert!(ggx.feed(Some(&down("KeyX", false)), at(600)));
+    }
+
+    #[test]
+    fn double_tap() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut tap = DoubleTap::new(String::from("Shift"), Duration::from_millis(300));
+
+        assert!(!tap.feed(Some("ShiftLeft"), true, false, at(0)));
+        assert!(!tap.feed(Some("ShiftLeft"), false, false, at(50)));
+        assert!(tap.feed(Some("ShiftRight"), true, false, at(200)));
+        tap.feed(Some("ShiftRight"), false, false, at(250));
+
+        // 间隔过长
+        tap.feed(Some("ShiftLeft"), true, false, at(1000));
+        tap.feed(Some("ShiftLeft"), false, false, at(1050));
+        assert!(!tap.feed(Some("ShiftLeft"), true, false, at(1400)));
+        tap.feed(Some("ShiftLeft"), false, false, at(1450));
+
+        // 中间按下了其他键 (如 shift + A)
+        tap.feed(Some("ShiftLeft"), true, false, at(2000));
+        tap.feed(Some("KeyA"), true, false, at(2050));
+        tap.feed(Some("ShiftLeft"), false, false, at(2100));
+        assert!(!tap.feed(Some("ShiftLeft"), true, false, at(2150)));
+
+        // 长按
+        let mut tap = DoubleTap::new(String::from("Control"), Duration::from_millis(300));
+        tap.feed(Some("ControlLeft"), true, false, at(0));
+        tap.feed(Some("ControlLeft"), true, true, at(100));
+        tap.feed(Some("ControlLeft"), false, false, at(150));
+        assert!(!tap.feed(Some("ControlLeft"), true, false, at(200)));
+    }
+
+    #[test]
+    fn long_press() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut press = LongPress::new(String::from("Space"), Duration::from_millis(500));
+
+        press.feed(Some("Space"), true, false, at(0));
+        assert_eq!(press.deadline(), Some(at(500)));
+        press.feed(Some("Space"), true, true, at(300));
+        assert!(!press.poll(at(400)));
+        assert!(press.poll(at(500)));
+        // 同一次按下只触发一次
+        assert!(!press.poll(at(600)));
+        press.feed(Some("Space"), false, false, at(700));
+
+        // 提前释放 / 按下其他键
+        press.feed(Some("Space"), true, false, at(1000));
+        press.feed(Some("Space"), false, false, at(1200));
+        assert!(!press.poll(at(1600)));
+        press.feed(Some("Space"), true, false, at(2000));
+        press.feed(Some("KeyA"), true, false, at(2100));
+        assert_eq!(press.deadline(), None);
+    }
+}
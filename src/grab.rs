@@ -0,0 +1,616 @@
+//! 按键拦截
+//!
+//! 被拦截的组合键不再送达前台应用, 但仍会经全局键盘钩子分发给所有订阅者:
+//! - Windows / macOS: 存在拦截目标时安装 rdev 的 `grab` 钩子 (低级键盘钩子 / CGEventTap), 由 [Suppressor] 决定是否吞掉事件
+//! - Linux (X11): 由独立的连接以 `XGrabKey` 抢占组合键, 钩子 (XRecord) 仍能收到事件
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use napi::{Error, Status};
+use rdev::Key;
+use crate::check_key;
+use crate::gesture::matches_key;
+use crate::hook::Modifiers;
+use crate::mapper::RdevMapper;
+use crate::utils::KeyCombination;
+
+/// 拦截目标
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GrabTarget {
+    pub key: String,
+    /// (ctrl, alt, shift, meta) -- `None` 表示不论修饰键状态
+    pub modifiers: Option<(bool, bool, bool, bool)>,
+    /// 按下后同时拦截其他按键, 直至 [release] (X11 下抢占的按键本就独占键盘)
+    pub hold: bool,
+}
+
+impl GrabTarget {
+    /// 恰好为该组合键时拦截
+    pub fn of(combination: &KeyCombination) -> Self {
+        let flag = |v: Option<bool>| match v {
+            Some(v) => v,
+            None => false
+        };
+        let modifiers = match &combination.extra {
+            Some(v) => (flag(v.ctrl), flag(v.alt), flag(v.shift), flag(v.meta)),
+            None => (false, false, false, false)
+        };
+
+        GrabTarget { key: combination.key.clone(), modifiers: Some(modifiers), hold: false }
+    }
+
+    /// 不论修饰键状态, 总是拦截该键
+    pub fn any(key: String) -> Self {
+        GrabTarget { key, modifiers: None, hold: false }
+    }
+
+    /// 按下后同时拦截其他按键 (见 [release])
+    pub fn holding(self) -> Self {
+        GrabTarget { hold: true, ..self }
+    }
+
+    /// `Shift` / `Control` / `Alt` 匹配左右两侧
+    pub fn matches(&self, key: &str, m: &Modifiers) -> bool {
+        matches_key(&self.key, Some(key)) && match self.modifiers {
+            Some(v) => v == (m.ctrl(), m.alt(), m.shift(), m.meta()),
+            None => true
+        }
+    }
+}
+
+/// 拦截目标及其引用计数
+static GRABS: Mutex<Vec<(GrabTarget, usize)>> = Mutex::new(Vec::new());
+/// 是否正在拦截所有按键 (`hold` 目标按下后)
+static HOLDING: AtomicBool = AtomicBool::new(false);
+/// 即将模拟的被拦截按键 (见 [pass])
+static PASSING: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// 拦截 `target` (可重复拦截, 需以相同次数的 [ungrab] 解除)
+pub fn grab(target: GrabTarget) -> napi::Result<()> {
+    let mut grabs = GRABS.lock().unwrap();
+    if let Some(entry) = grabs.iter_mut().find(|(v, _)| *v == target) {
+        entry.1 += 1;
+        return Ok(());
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Err(reason) = x11_grab::grab(&target) {
+            return Err(Error::new(Status::GenericFailure, format!("Failed to grab {}: {}!", target.key, reason)));
+        }
+    }
+    grabs.push((target, 1));
+    #[cfg(not(target_os = "linux"))]
+    if grabs.len() == 1 {
+        crate::hook::grab_hook::install();
+    }
+
+    Ok(())
+}
+
+/// 解除一次拦截 (返回值表示此前是否已拦截)
+pub fn ungrab(target: &GrabTarget) -> bool {
+    let mut grabs = GRABS.lock().unwrap();
+    let idx = match grabs.iter().position(|(v, _)| v == target) {
+        Some(v) => v,
+        None => return false
+    };
+
+    grabs[idx].1 -= 1;
+    if grabs[idx].1 == 0 {
+        grabs.remove(idx);
+        #[cfg(target_os = "linux")]
+        x11_grab::ungrab(target);
+    }
+    if grabs.is_empty() {
+        HOLDING.store(false, Ordering::SeqCst);
+        #[cfg(not(target_os = "linux"))]
+        crate::hook::grab_hook::uninstall();
+    }
+    true
+}
+
+/// 结束拦截目标按下后对键盘的独占, 之后的按键恢复送达前台应用
+/// (在模拟按键前调用, 否则 X11 下模拟的按键同样会被独占)
+pub fn release() {
+    HOLDING.store(false, Ordering::SeqCst);
+    #[cfg(target_os = "linux")]
+    x11_grab::release();
+}
+
+/// 放行接下来模拟的 `key` 的一次按下及释放 (输出被拦截的按键本身时在模拟前调用, 否则输出同样会被拦截)
+pub fn pass(key: &str) {
+    PASSING.lock().unwrap().push(key.to_string());
+    // X11 的抢占对模拟的按键同样生效: 暂时解除, 模拟的释放经过钩子后恢复
+    #[cfg(target_os = "linux")]
+    x11_grab::suspend(targets_of(key));
+}
+
+/// 拦截 `key` 的目标
+#[cfg(target_os = "linux")]
+fn targets_of(key: &str) -> Vec<GrabTarget> {
+    GRABS.lock().unwrap().iter().map(|(target, _)| target).filter(|target| matches_key(&target.key, Some(key))).cloned().collect()
+}
+
+/// 恢复被拦截的锁定键的锁定状态 (X11 下被抢占的锁定键仍会由 XKB 切换锁定状态)
+#[cfg(target_os = "linux")]
+pub fn relock(key: Key, locks: &crate::hook::Locks) {
+    let (name, locked) = match key {
+        Key::CapsLock => ("CapsLock", locks.caps),
+        Key::NumLock => ("NumLock", locks.num),
+        Key::ScrollLock => ("ScrollLock", locks.scroll),
+        _ => return
+    };
+    x11_grab::relock(name, locked);
+}
+
+/// 校验按键名
+pub fn check_target(target: &GrabTarget) -> napi::Result<()> {
+    if check_key(target.key.clone()).unwrap() {
+        Ok(())
+    } else {
+        Err(Error::new(Status::InvalidArg, String::from("Invalid Key!")))
+    }
+}
+
+/// [Suppressor] 的判定结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// 放行
+    Pass,
+    /// 吞掉 (匹配拦截目标)
+    Grabbed,
+    /// 吞掉 (`hold` 目标按下后至 [release] 前按下的其他按键, 由按下 `hold` 目标的一方重放)
+    HeldBack,
+}
+
+/// 决定是否吞掉按键事件 (Windows / macOS; Linux 下由 X11 抢占吞掉按键, 仅用于判定哪些按键被暂时拦截)
+#[derive(Debug, Default)]
+pub struct Suppressor {
+    /// 按下时已被吞掉的键 (其重复按下及释放同样吞掉)
+    swallowed: Vec<Key>,
+    /// 按下时已放行的模拟按键 (见 [pass], 其释放同样放行)
+    passing: Vec<Key>,
+}
+
+impl Suppressor {
+    /// `modifiers`: 事件发生后的修饰键状态
+    pub fn filter(&mut self, key: Key, down: bool, modifiers: &Modifiers) -> Verdict {
+        if let Some(idx) = self.passing.iter().position(|v| *v == key) {
+            if !down {
+                self.passing.remove(idx);
+                #[cfg(target_os = "linux")]
+                if let Some(name) = RdevMapper::encode_key(&key) {
+                    x11_grab::resume(targets_of(&name));
+                }
+            }
+            return Verdict::Pass;
+        }
+        if let Some(idx) = self.swallowed.iter().position(|v| *v == key) {
+            if !down {
+                self.swallowed.remove(idx);
+            }
+            return Verdict::Grabbed;
+        }
+        if !down {
+            return Verdict::Pass;
+        }
+        let name = RdevMapper::encode_key(&key);
+        if let Some(name) = &name {
+            let mut passing = PASSING.lock().unwrap();
+            if let Some(idx) = passing.iter().position(|v| matches_key(v, Some(name))) {
+                passing.remove(idx);
+                self.passing.push(key);
+                return Verdict::Pass;
+            }
+        }
+        // 其释放不吞掉 (重放的按下同样会经过钩子, 不能视为重复按下)
+        if HOLDING.load(Ordering::SeqCst) {
+            return Verdict::HeldBack;
+        }
+
+        let name = match name {
+            Some(v) => v,
+            None => return Verdict::Pass
+        };
+        let grabs = GRABS.lock().unwrap();
+        let matched: Vec<&GrabTarget> = grabs.iter().map(|(target, _)| target).filter(|target| target.matches(&name, modifiers)).collect();
+        if matched.is_empty() {
+            return Verdict::Pass;
+        }
+        if matched.iter().any(|target| target.hold) {
+            HOLDING.store(true, Ordering::SeqCst);
+        }
+        self.swallowed.push(key);
+        Verdict::Grabbed
+    }
+}
+
+/// X11: 由独立线程持有的连接执行 `XGrabKey` (被抢占的按键事件发往该连接, 需持续读取)
+#[cfg(target_os = "linux")]
+mod x11_grab {
+    use std::{
+        mem::zeroed,
+        os::raw::c_int,
+        ptr,
+        sync::Mutex,
+        sync::atomic::{AtomicPtr, AtomicU8, Ordering},
+        sync::mpsc::{self, RecvTimeoutError, Sender},
+        thread,
+        time::Duration,
+    };
+    use x11::{keysym, xlib};
+    use super::GrabTarget;
+
+    /// 任意修饰键 (X.h `AnyModifier`)
+    const ANY_MODIFIER: u32 = 1 << 15;
+    /// 核心键盘 (XKB.h `XkbUseCoreKbd`)
+    const XKB_USE_CORE_KBD: u32 = 0x0100;
+    /// 读取事件的间隔
+    const PUMP_INTERVAL: Duration = Duration::from_millis(20);
+
+    enum Command {
+        Grab(GrabTarget, Sender<Result<(), String>>),
+        Ungrab(GrabTarget),
+        /// 结束被动抢占激活后的键盘独占
+        Release(Sender<()>),
+        /// 暂时解除抢占 (见 [super::pass])
+        Suspend(Vec<GrabTarget>, Sender<()>),
+        Resume(Vec<GrabTarget>),
+        /// 设置锁定键的锁定状态
+        Relock(String, bool),
+    }
+
+    static COMMANDS: Mutex<Option<Sender<Command>>> = Mutex::new(None);
+    /// 最近一次 X 错误码
+    static LAST_ERROR: AtomicU8 = AtomicU8::new(0);
+    /// 执行抢占的连接 (其他连接上的错误交给原有的错误处理)
+    static DISPLAY: AtomicPtr<xlib::Display> = AtomicPtr::new(ptr::null_mut());
+    /// 替换前的错误处理
+    static PREVIOUS: Mutex<ErrorHandler> = Mutex::new(None);
+
+    type ErrorHandler = Option<unsafe extern "C" fn(*mut xlib::Display, *mut xlib::XErrorEvent) -> c_int>;
+
+    /// 记录错误而不是结束进程 (Xlib 默认的错误处理会直接退出)
+    unsafe extern "C" fn on_error(display: *mut xlib::Display, event: *mut xlib::XErrorEvent) -> c_int {
+        if display != DISPLAY.load(Ordering::SeqCst) {
+            if let Some(previous) = *PREVIOUS.lock().unwrap() {
+                return previous(display, event);
+            }
+        }
+        LAST_ERROR.store((*event).error_code, Ordering::SeqCst);
+        0
+    }
+
+    /// 执行 `requests` 并等待其完成, 返回期间的 X 错误码 (`0` 表示没有);
+    /// 错误处理是进程全局的, 仅在此期间替换, 之后恢复原有的处理
+    unsafe fn checked(display: *mut xlib::Display, requests: impl FnOnce()) -> u8 {
+        *PREVIOUS.lock().unwrap() = xlib::XSetErrorHandler(Some(on_error));
+        LAST_ERROR.store(0, Ordering::SeqCst);
+        requests();
+        xlib::XSync(display, xlib::False);
+        xlib::XSetErrorHandler(PREVIOUS.lock().unwrap().take());
+        LAST_ERROR.load(Ordering::SeqCst)
+    }
+
+    /// 按键名 -> keysym
+    fn keysym_of(key: &str) -> Option<u32> {
+        // 字母与数字的 keysym 即其 ASCII 码
+        if let Some(c) = key.strip_prefix("Key").filter(|v| v.len() == 1) {
+            return Some(c.to_ascii_lowercase().chars().next()? as u32);
+        }
+        if let Some(c) = key.strip_prefix("Digit").filter(|v| v.len() == 1) {
+            return Some(c.chars().next()? as u32);
+        }
+        if let Some(n) = key.strip_prefix('F').and_then(|v| v.parse::<u32>().ok()) {
+            return if (1..=12).contains(&n) { Some(keysym::XK_F1 + n - 1) } else { None };
+        }
+        if let Some(n) = key.strip_prefix("Numpad").and_then(|v| v.parse::<u32>().ok()) {
+            return if n <= 9 { Some(keysym::XK_KP_0 + n) } else { None };
+        }
+
+        let sym = match key {
+            "Meta" => keysym::XK_Super_L,
+            "Escape" => keysym::XK_Escape,
+            "Tab" => keysym::XK_Tab,
+            "CapsLock" => keysym::XK_Caps_Lock,
+            "Shift" | "ShiftLeft" => keysym::XK_Shift_L,
+            "ShiftRight" => keysym::XK_Shift_R,
+            "Control" | "ControlLeft" => keysym::XK_Control_L,
+            "ControlRight" => keysym::XK_Control_R,
+            "Alt" | "AltLeft" => keysym::XK_Alt_L,
+            "AltRight" => keysym::XK_Alt_R,
+            "Space" => keysym::XK_space,
+            "ArrowUp" => keysym::XK_Up,
+            "ArrowRight" => keysym::XK_Right,
+            "ArrowDown" => keysym::XK_Down,
+            "ArrowLeft" => keysym::XK_Left,
+            "Enter" => keysym::XK_Return,
+            "Backspace" => keysym::XK_BackSpace,
+            "Delete" => keysym::XK_Delete,
+            "Home" => keysym::XK_Home,
+            "PageUp" => keysym::XK_Prior,
+            "PageDown" => keysym::XK_Next,
+            "End" => keysym::XK_End,
+            "Backquote" => keysym::XK_grave,
+            "Minus" => keysym::XK_minus,
+            "Equal" => keysym::XK_equal,
+            "BracketLeft" => keysym::XK_bracketleft,
+            "BracketRight" => keysym::XK_bracketright,
+            "Comma" => keysym::XK_comma,
+            "Period" => keysym::XK_period,
+            "Semicolon" => keysym::XK_semicolon,
+            "Quote" => keysym::XK_apostrophe,
+            "Slash" => keysym::XK_slash,
+            "BackSlash" => keysym::XK_backslash,
+            "IntlBackslash" => keysym::XK_less,
+            "Insert" => keysym::XK_Insert,
+            "PrintScreen" => keysym::XK_Print,
+            "Pause" => keysym::XK_Pause,
+            "NumLock" => keysym::XK_Num_Lock,
+            "ScrollLock" => keysym::XK_Scroll_Lock,
+            "NumpadAdd" => keysym::XK_KP_Add,
+            "NumpadSubtract" => keysym::XK_KP_Subtract,
+            "NumpadMultiply" => keysym::XK_KP_Multiply,
+            "NumpadDivide" => keysym::XK_KP_Divide,
+            "NumpadEnter" => keysym::XK_KP_Enter,
+            "NumpadDecimal" => keysym::XK_KP_Decimal,
+            _ => return None
+        };
+        Some(sym)
+    }
+
+    /// 修饰键掩码 (含 CapsLock / NumLock 开启时的变体)
+    fn masks_of(target: &GrabTarget) -> Vec<u32> {
+        let (ctrl, alt, shift, meta) = match target.modifiers {
+            Some(v) => v,
+            None => return vec![ANY_MODIFIER]
+        };
+
+        let mut mask = 0;
+        if ctrl { mask |= xlib::ControlMask; }
+        if alt { mask |= xlib::Mod1Mask; }
+        if shift { mask |= xlib::ShiftMask; }
+        if meta { mask |= xlib::Mod4Mask; }
+        [0, xlib::LockMask, xlib::Mod2Mask, xlib::LockMask | xlib::Mod2Mask].iter().map(|lock| mask | lock).collect()
+    }
+
+    /// 启动持有连接的线程 (仅首次调用时启动)
+    fn commands() -> Result<Sender<Command>, String> {
+        let mut commands = COMMANDS.lock().unwrap();
+        if let Some(sender) = commands.as_ref() {
+            return Ok(sender.clone());
+        }
+
+        let (sender, receiver) = mpsc::channel::<Command>();
+        let (ready, started) = mpsc::channel::<Result<(), String>>();
+        thread::spawn(move || unsafe {
+            let display = xlib::XOpenDisplay(ptr::null());
+            if display.is_null() {
+                let _ = ready.send(Err(String::from("cannot open the X display")));
+                return;
+            }
+            DISPLAY.store(display, Ordering::SeqCst);
+            let root = xlib::XDefaultRootWindow(display);
+            let _ = ready.send(Ok(()));
+
+            let apply = |target: &GrabTarget, grab: bool| -> Result<(), String> {
+                let sym = match keysym_of(&target.key) {
+                    Some(v) => v,
+                    None => return Err(String::from("unsupported key"))
+                };
+                let code = xlib::XKeysymToKeycode(display, sym as xlib::KeySym) as c_int;
+                if code == 0 {
+                    return Err(String::from("the key is not on the current keyboard layout"));
+                }
+
+                let error = checked(display, || {
+                    for mask in masks_of(target) {
+                        if grab {
+                            xlib::XGrabKey(display, code, mask, root, xlib::True, xlib::GrabModeAsync, xlib::GrabModeAsync);
+                        } else {
+                            xlib::XUngrabKey(display, code, mask, root);
+                        }
+                    }
+                });
+
+                match error {
+                    0 => Ok(()),
+                    xlib::BadAccess => Err(String::from("already grabbed by another application")),
+                    code => Err(format!("X error {}", code))
+                }
+            };
+
+            let mut event: xlib::XEvent = zeroed();
+            loop {
+                match receiver.recv_timeout(PUMP_INTERVAL) {
+                    Ok(Command::Grab(target, reply)) => {
+                        let result = apply(&target, true);
+                        if result.is_err() {
+                            let _ = apply(&target, false);
+                        }
+                        let _ = reply.send(result);
+                    }
+                    Ok(Command::Ungrab(target)) => {
+                        let _ = apply(&target, false);
+                    }
+                    Ok(Command::Release(reply)) => {
+                        xlib::XUngrabKeyboard(display, xlib::CurrentTime);
+                        xlib::XSync(display, xlib::False);
+                        let _ = reply.send(());
+                    }
+                    Ok(Command::Suspend(targets, reply)) => {
+                        for target in targets.iter() {
+                            let _ = apply(target, false);
+                        }
+                        let _ = reply.send(());
+                    }
+                    Ok(Command::Resume(targets)) => {
+                        for target in targets.iter() {
+                            let _ = apply(target, true);
+                        }
+                    }
+                    Ok(Command::Relock(key, locked)) => {
+                        // 未映射到修饰键的锁定键 (如多数布局下的 ScrollLock) 没有锁定状态
+                        if let Some(sym) = keysym_of(&key) {
+                            let mask = xlib::XkbKeysymToModifiers(display, sym as xlib::KeySym);
+                            if mask != 0 {
+                                checked(display, || {
+                                    xlib::XkbLockModifiers(display, XKB_USE_CORE_KBD, mask, if locked { mask } else { 0 });
+                                });
+                            }
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break
+                }
+
+                // 被抢占的按键事件无需处理 (订阅者已经由钩子收到), 仅需取走
+                while xlib::XPending(display) > 0 {
+                    xlib::XNextEvent(display, &mut event);
+                }
+            }
+            xlib::XCloseDisplay(display);
+        });
+
+        match started.recv() {
+            Ok(Ok(())) => {
+                *commands = Some(sender.clone());
+                Ok(sender)
+            }
+            Ok(Err(reason)) => Err(reason),
+            Err(_) => Err(String::from("the grab thread exited unexpectedly"))
+        }
+    }
+
+    pub fn grab(target: &GrabTarget) -> Result<(), String> {
+        let (reply, result) = mpsc::channel();
+        if commands()?.send(Command::Grab(target.clone(), reply)).is_err() {
+            return Err(String::from("the grab thread exited unexpectedly"));
+        }
+        match result.recv() {
+            Ok(v) => v,
+            Err(_) => Err(String::from("the grab thread exited unexpectedly"))
+        }
+    }
+
+    pub fn ungrab(target: &GrabTarget) {
+        if let Ok(sender) = commands() {
+            let _ = sender.send(Command::Ungrab(target.clone()));
+        }
+    }
+
+    /// 已启动的线程 (以下操作仅在已有抢占时执行, 不为此启动线程)
+    fn running() -> Option<Sender<Command>> {
+        COMMANDS.lock().unwrap().as_ref().cloned()
+    }
+
+    pub fn release() {
+        let sender = match running() {
+            Some(v) => v,
+            None => return
+        };
+        let (reply, done) = mpsc::channel();
+        if sender.send(Command::Release(reply)).is_ok() {
+            let _ = done.recv();
+        }
+    }
+
+    pub fn suspend(targets: Vec<GrabTarget>) {
+        let sender = match running() {
+            Some(v) => v,
+            None => return
+        };
+        let (reply, done) = mpsc::channel();
+        if sender.send(Command::Suspend(targets, reply)).is_ok() {
+            let _ = done.recv();
+        }
+    }
+
+    pub fn resume(targets: Vec<GrabTarget>) {
+        if let Some(sender) = running() {
+            let _ = sender.send(Command::Resume(targets));
+        }
+    }
+
+    pub fn relock(key: &str, locked: bool) {
+        if let Some(sender) = running() {
+            let _ = sender.send(Command::Relock(key.to_string(), locked));
+        }
+    }
+
+    #[cfg(test)]
+    mod unit_test {
+        use super::*;
+
+        #[test]
+        fn keysyms() {
+            assert_eq!(keysym_of("KeyA"), Some(keysym::XK_a));
+            assert_eq!(keysym_of("Digit7"), Some(keysym::XK_7));
+            assert_eq!(keysym_of("F12"), Some(keysym::XK_F12));
+            assert_eq!(keysym_of("F13"), None);
+            assert_eq!(keysym_of("Numpad9"), Some(keysym::XK_KP_9));
+            assert_eq!(keysym_of("Unknown"), None);
+
+            let masks = masks_of(&GrabTarget { key: String::from("KeyA"), modifiers: Some((true, false, true, false)), hold: false });
+            assert_eq!(masks.len(), 4);
+            assert!(masks.iter().all(|mask| mask & (xlib::ControlMask | xlib::ShiftMask) == xlib::ControlMask | xlib::ShiftMask));
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use crate::utils::ExtraKey;
+    use super::*;
+
+    #[test]
+    fn suppressor() {
+        let _guard = crate::hook::TEST_GUARD.lock().unwrap_or_else(|err| err.into_inner());
+        let target = GrabTarget::of(&KeyCombination {
+            key: String::from("KeyQ"),
+            extra: Some(ExtraKey { ctrl: None, alt: Some(true), shift: None, meta: None }),
+        });
+        GRABS.lock().unwrap().push((target.clone(), 1));
+
+        let alt = Modifiers { alt_left: true, ..Default::default() };
+        let mut suppressor = Suppressor::default();
+        assert_eq!(suppressor.filter(Key::Alt, true, &alt), Verdict::Pass);
+        assert_eq!(suppressor.filter(Key::KeyQ, true, &alt), Verdict::Grabbed);
+        // 重复按下与释放同样吞掉 (即使 alt 已先释放)
+        assert_eq!(suppressor.filter(Key::KeyQ, true, &alt), Verdict::Grabbed);
+        assert_eq!(suppressor.filter(Key::Alt, false, &Modifiers::default()), Verdict::Pass);
+        assert_eq!(suppressor.filter(Key::KeyQ, false, &Modifiers::default()), Verdict::Grabbed);
+        // 修饰键不一致
+        assert_eq!(suppressor.filter(Key::KeyQ, true, &Modifiers::default()), Verdict::Pass);
+        assert_eq!(suppressor.filter(Key::KeyQ, false, &Modifiers::default()), Verdict::Pass);
+
+        GRABS.lock().unwrap().retain(|(v, _)| *v != target);
+
+        // 双重功能键: 按下后暂时拦截其他按键的按下, 直至 release
+        let target = GrabTarget::any(String::from("CapsLock")).holding();
+        GRABS.lock().unwrap().push((target.clone(), 1));
+        assert_eq!(suppressor.filter(Key::CapsLock, true, &Modifiers::default()), Verdict::Grabbed);
+        assert_eq!(suppressor.filter(Key::KeyJ, true, &Modifiers::default()), Verdict::HeldBack);
+        assert_eq!(suppressor.filter(Key::KeyK, true, &Modifiers::default()), Verdict::HeldBack);
+        assert_eq!(suppressor.filter(Key::KeyK, false, &Modifiers::default()), Verdict::Pass);
+        release();
+        assert_eq!(suppressor.filter(Key::KeyJ, true, &Modifiers::default()), Verdict::Pass);
+        assert_eq!(suppressor.filter(Key::KeyJ, false, &Modifiers::default()), Verdict::Pass);
+        assert_eq!(suppressor.filter(Key::CapsLock, false, &Modifiers::default()), Verdict::Grabbed);
+
+        GRABS.lock().unwrap().retain(|(v, _)| *v != target);
+
+        // 模拟的被拦截按键: 放行一次按下及释放
+        let target = GrabTarget::any(String::from("Tab"));
+        GRABS.lock().unwrap().push((target.clone(), 1));
+        pass("Tab");
+        assert_eq!(suppressor.filter(Key::Tab, true, &Modifiers::default()), Verdict::Pass);
+        assert_eq!(suppressor.filter(Key::Tab, false, &Modifiers::default()), Verdict::Pass);
+        assert_eq!(suppressor.filter(Key::Tab, true, &Modifiers::default()), Verdict::Grabbed);
+        assert_eq!(suppressor.filter(Key::Tab, false, &Modifiers::default()), Verdict::Grabbed);
+
+        GRABS.lock().unwrap().retain(|(v, _)| *v != target);
+    }
+}
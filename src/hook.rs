@@ -0,0 +1,495 @@
+//! 全局键盘钩子
+//!
+//! 进程内只启动一个监听线程 (rdev, 由系统事件驱动, 不轮询), 按键事件经 channel 广播给所有订阅者;
+//! 修饰键状态由事件流增量维护, 无需在每次按键时重新扫描键盘状态;
+//! 默认以 rdev 的 `listen` 运行. Windows / macOS 下存在拦截目标时另行安装 rdev 的 `grab` 钩子,
+//! 以便吞掉被拦截的组合键 (见 [crate::grab]), 此时由该钩子分发事件
+
+use std::{
+    collections::HashSet,
+    sync::{Mutex, Once},
+    sync::atomic::{AtomicU64, Ordering},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Instant,
+};
+use rdev::{Event, EventType, Key};
+use crate::grab::{Suppressor, Verdict};
+
+/// 修饰键状态 (区分左右)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub ctrl_left: bool,
+    pub ctrl_right: bool,
+    pub alt_left: bool,
+    pub alt_right: bool,
+    pub shift_left: bool,
+    pub shift_right: bool,
+    pub meta_left: bool,
+    pub meta_right: bool,
+}
+
+impl Modifiers {
+    pub fn ctrl(&self) -> bool {
+        self.ctrl_left || self.ctrl_right
+    }
+
+    pub fn alt(&self) -> bool {
+        self.alt_left || self.alt_right
+    }
+
+    pub fn shift(&self) -> bool {
+        self.shift_left || self.shift_right
+    }
+
+    pub fn meta(&self) -> bool {
+        self.meta_left || self.meta_right
+    }
+}
+
+/// 锁定键状态
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Locks {
+    pub caps: bool,
+    pub num: bool,
+    pub scroll: bool,
+}
+
+/// 单次按键事件
+#[derive(Debug, Clone)]
+pub struct KeyInput {
+    pub key: Key,
+    /// 是否是按下
+    pub down: bool,
+    /// 是否是长按产生的重复按下
+    pub repeat: bool,
+    /// 事件发生后的修饰键状态
+    pub modifiers: Modifiers,
+    /// 事件发生后的锁定键状态
+    pub locks: Locks,
+    /// 按下时输入的字符 (取决于系统键盘布局及修饰键; 死键等情况下为 `None`)
+    pub text: Option<String>,
+    /// 是否在双重功能键按下期间被暂时拦截 (需由拦截方重放, 见 [crate::grab::Verdict::HeldBack])
+    pub held_back: bool,
+    /// 事件到达时间
+    pub time: Instant,
+}
+
+/// 发送给订阅者的消息
+#[derive(Debug, Clone)]
+pub enum Signal {
+    Key(KeyInput),
+    /// 监听失败 (如 macOS 未授予辅助功能权限), 之后不会再有按键事件
+    Failed(String),
+    /// 取消订阅
+    Stop,
+}
+
+/// 当前按下的键与锁定键状态 (由事件流增量维护)
+#[derive(Debug, Default)]
+pub struct KeyTracker {
+    pressed: HashSet<Key>,
+    locks: Locks,
+}
+
+impl KeyTracker {
+    /// `locks`: 开始监听时的锁定键状态 (之后在锁定键按下时切换)
+    pub fn new(locks: Locks) -> Self {
+        KeyTracker { pressed: HashSet::new(), locks }
+    }
+
+    /// 记录一次按键, 返回 (是否是重复按下, 事件发生后的修饰键状态, 事件发生后的锁定键状态)
+    pub fn update(&mut self, key: Key, down: bool) -> (bool, Modifiers, Locks) {
+        let repeat = if down { !self.pressed.insert(key) } else { self.pressed.remove(&key); false };
+
+        if down && !repeat {
+            self.toggle(key);
+        }
+
+        (repeat, self.modifiers(), self.locks)
+    }
+
+    /// 撤销锁定键按下时的切换 (被拦截的锁定键不切换锁定状态), 返回撤销后的锁定键状态
+    pub fn untoggle(&mut self, key: Key) -> Locks {
+        self.toggle(key);
+        self.locks
+    }
+
+    fn toggle(&mut self, key: Key) {
+        match key {
+            Key::CapsLock => self.locks.caps = !self.locks.caps,
+            Key::NumLock => self.locks.num = !self.locks.num,
+            Key::ScrollLock => self.locks.scroll = !self.locks.scroll,
+            _ => {}
+        }
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        let held = |key: Key| self.pressed.contains(&key);
+        Modifiers {
+            ctrl_left: held(Key::ControlLeft),
+            ctrl_right: held(Key::ControlRight),
+            alt_left: held(Key::Alt),
+            alt_right: held(Key::AltGr),
+            shift_left: held(Key::ShiftLeft),
+            shift_right: held(Key::ShiftRight),
+            meta_left: held(Key::MetaLeft),
+            meta_right: held(Key::MetaRight),
+        }
+    }
+}
+
+/// 读取当前的锁定键状态 (仅在开始监听时调用一次)
+#[cfg(target_os = "linux")]
+fn current_locks() -> Locks {
+    use std::{mem::zeroed, ptr};
+    use x11::xlib;
+
+    unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            return Locks::default();
+        }
+
+        // 指示灯掩码: 第 0 / 1 / 2 位依次为 Caps / Num / Scroll
+        let mut state: xlib::XKeyboardState = zeroed();
+        xlib::XGetKeyboardControl(display, &mut state);
+        xlib::XCloseDisplay(display);
+
+        let mask = state.led_mask as u64;
+        Locks { caps: mask & 1 != 0, num: mask & 2 != 0, scroll: mask & 4 != 0 }
+    }
+}
+
+/// 读取当前的锁定键状态 (仅在开始监听时调用一次)
+#[cfg(target_os = "windows")]
+fn current_locks() -> Locks {
+    use winapi::um::winuser::{GetKeyState, VK_CAPITAL, VK_NUMLOCK, VK_SCROLL};
+
+    // 最低位为切换状态
+    let toggled = |vk: i32| unsafe { GetKeyState(vk) & 1 != 0 };
+    Locks { caps: toggled(VK_CAPITAL), num: toggled(VK_NUMLOCK), scroll: toggled(VK_SCROLL) }
+}
+
+/// 读取当前的锁定键状态 (仅在开始监听时调用一次; macOS 没有 NumLock / ScrollLock)
+#[cfg(target_os = "macos")]
+fn current_locks() -> Locks {
+    #[link(name = "ApplicationServices", kind = "framework")]
+    extern "C" {
+        fn CGEventSourceFlagsState(state_id: i32) -> u64;
+    }
+    /// kCGEventSourceStateHIDSystemState
+    const HID_SYSTEM_STATE: i32 = 1;
+    /// kCGEventFlagMaskAlphaShift
+    const ALPHA_SHIFT: u64 = 0x0001_0000;
+
+    let flags = unsafe { CGEventSourceFlagsState(HID_SYSTEM_STATE) };
+    Locks { caps: flags & ALPHA_SHIFT != 0, num: false, scroll: false }
+}
+
+/// 读取当前的锁定键状态
+#[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
+fn current_locks() -> Locks {
+    Locks::default()
+}
+
+/// 订阅者
+static SUBSCRIBERS: Mutex<Vec<(u64, Sender<Signal>)>> = Mutex::new(Vec::new());
+/// 按键状态 (监听钩子与拦截钩子共用, 启动监听时初始化)
+static TRACKER: Mutex<Option<KeyTracker>> = Mutex::new(None);
+/// 正在分发事件的拦截钩子的编号 (`0` 表示没有, 由监听钩子分发)
+static GRAB_HOOK: AtomicU64 = AtomicU64::new(0);
+/// 已安装的拦截钩子的编号 (`0` 表示没有)
+static INSTALLED: AtomicU64 = AtomicU64::new(0);
+/// 监听失败的原因 (之后的订阅者在订阅时即收到)
+static FAILURE: Mutex<Option<String>> = Mutex::new(None);
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+static START: Once = Once::new();
+/// 修改全局状态 (订阅者、拦截钩子及 [crate::grab] 的拦截目标) 的测试需串行执行
+#[cfg(test)]
+pub static TEST_GUARD: Mutex<()> = Mutex::new(());
+
+/// 广播给所有订阅者 (同时清理已失效的订阅者)
+fn broadcast(signal: Signal) {
+    SUBSCRIBERS.lock().unwrap().retain(|(_, sender)| sender.send(signal.clone()).is_ok());
+}
+
+/// 维护修饰键状态, 经 `filter` 判定后广播, 返回判定结果 (非按键事件放行)
+fn dispatch(tracker: &mut KeyTracker, event: &Event, filter: impl FnOnce(Key, bool, &Modifiers) -> Verdict) -> Verdict {
+    let (key, down) = match event.event_type {
+        EventType::KeyPress(key) => (key, true),
+        EventType::KeyRelease(key) => (key, false),
+        _ => return Verdict::Pass
+    };
+
+    let (repeat, modifiers, mut locks) = tracker.update(key, down);
+    let verdict = filter(key, down, &modifiers);
+    // 被拦截的锁定键不切换锁定状态 (X11 下仍会切换, 按下及释放时均需恢复)
+    if verdict == Verdict::Grabbed {
+        if down && !repeat {
+            locks = tracker.untoggle(key);
+        }
+        #[cfg(target_os = "linux")]
+        crate::grab::relock(key, &locks);
+    }
+    let text = event.name.clone();
+    let held_back = verdict == Verdict::HeldBack;
+    broadcast(Signal::Key(KeyInput { key, down, repeat, modifiers, locks, text, held_back, time: Instant::now() }));
+    verdict
+}
+
+/// 以共用的按键状态分发
+fn dispatch_shared(event: &Event, filter: impl FnOnce(Key, bool, &Modifiers) -> Verdict) -> Verdict {
+    let mut tracker = TRACKER.lock().unwrap();
+    dispatch(tracker.get_or_insert_with(|| KeyTracker::new(current_locks())), event, filter)
+}
+
+/// 监听键盘 (阻塞; 拦截钩子分发事件期间不再分发)
+fn run() -> Result<(), String> {
+    // X11 下由抢占吞掉按键, 此处仅判定哪些按键被暂时拦截
+    #[cfg(target_os = "linux")]
+    let mut suppressor = Suppressor::default();
+
+    rdev::listen(move |event: Event| {
+        if GRAB_HOOK.load(Ordering::SeqCst) == 0 {
+            #[cfg(target_os = "linux")]
+            dispatch_shared(&event, |key, down, modifiers| suppressor.filter(key, down, modifiers));
+            #[cfg(not(target_os = "linux"))]
+            dispatch_shared(&event, |_, _, _| Verdict::Pass);
+        }
+    }).map_err(|err| format!("{:?}", err))
+}
+
+/// 拦截钩子的回调: 分发事件并决定是否吞掉 (`hook` 为该钩子的编号, 已卸载的钩子直接放行)
+#[cfg_attr(target_os = "linux", allow(dead_code))]
+fn intercept(hook: u64, suppressor: &mut Suppressor, event: Event) -> Option<Event> {
+    if INSTALLED.load(Ordering::SeqCst) != hook {
+        return Some(event);
+    }
+    // 钩子收到第一个事件时才接管分发, 安装期间的事件仍由监听钩子分发
+    GRAB_HOOK.store(hook, Ordering::SeqCst);
+
+    match dispatch_shared(&event, |key, down, modifiers| suppressor.filter(key, down, modifiers)) {
+        Verdict::Pass => Some(event),
+        _ => None
+    }
+}
+
+/// 拦截钩子 (Windows / macOS; 由 [crate::grab] 在出现第一个拦截目标时安装, 全部解除后卸载)
+#[cfg(not(target_os = "linux"))]
+pub mod grab_hook {
+    use std::sync::Mutex;
+    use std::sync::atomic::Ordering;
+    use std::thread;
+    use rdev::Event;
+    use super::{intercept, Suppressor, GRAB_HOOK, INSTALLED};
+
+    /// 上一个拦截钩子的编号
+    static LAST: Mutex<u64> = Mutex::new(0);
+    /// 钩子所在线程 (结束线程即卸载钩子)
+    #[cfg(target_os = "windows")]
+    static THREAD_ID: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+
+    /// 安装拦截钩子 (已安装时不做处理); 安装失败 (如 macOS 未授予辅助功能权限) 时仅失去拦截效果, 监听不受影响
+    pub fn install() {
+        let mut last = LAST.lock().unwrap();
+        if INSTALLED.load(Ordering::SeqCst) != 0 {
+            return;
+        }
+        *last += 1;
+        let hook = *last;
+        INSTALLED.store(hook, Ordering::SeqCst);
+
+        #[cfg(target_os = "windows")]
+        let (ready, started) = std::sync::mpsc::channel::<()>();
+        thread::spawn(move || {
+            #[cfg(target_os = "windows")]
+            unsafe {
+                use winapi::um::processthreadsapi::GetCurrentThreadId;
+                use winapi::um::winuser::{PeekMessageW, MSG, PM_NOREMOVE, WM_USER};
+
+                // 先创建线程的消息队列, 卸载时才能收到 `WM_QUIT`
+                let mut msg: MSG = std::mem::zeroed();
+                PeekMessageW(&mut msg, std::ptr::null_mut(), WM_USER, WM_USER, PM_NOREMOVE);
+                THREAD_ID.store(GetCurrentThreadId(), Ordering::SeqCst);
+                let _ = ready.send(());
+            }
+
+            let mut suppressor = Suppressor::default();
+            if rdev::grab(move |event: Event| intercept(hook, &mut suppressor, event)).is_err() {
+                // 安装失败: 交还监听钩子分发
+                let _ = INSTALLED.compare_exchange(hook, 0, Ordering::SeqCst, Ordering::SeqCst);
+                let _ = GRAB_HOOK.compare_exchange(hook, 0, Ordering::SeqCst, Ordering::SeqCst);
+            }
+        });
+        #[cfg(target_os = "windows")]
+        let _ = started.recv();
+    }
+
+    /// 卸载拦截钩子 (结束钩子所在线程的消息循环, 线程结束时系统随即卸载钩子)
+    #[cfg(target_os = "windows")]
+    pub fn uninstall() {
+        use winapi::um::winuser::{PostThreadMessageW, WM_QUIT};
+
+        let _last = LAST.lock().unwrap();
+        if INSTALLED.swap(0, Ordering::SeqCst) == 0 {
+            return;
+        }
+        GRAB_HOOK.store(0, Ordering::SeqCst);
+        unsafe {
+            PostThreadMessageW(THREAD_ID.load(Ordering::SeqCst), WM_QUIT, 0, 0);
+        }
+    }
+
+    /// 卸载拦截钩子: rdev 无法移除已创建的 CGEventTap, 钩子保留并继续分发 (没有拦截目标时放行所有事件)
+    #[cfg(not(target_os = "windows"))]
+    pub fn uninstall() {}
+}
+
+/// 启动监听线程 (仅首次调用时启动, 随进程结束)
+fn start() {
+    START.call_once(|| {
+        thread::spawn(|| {
+            TRACKER.lock().unwrap().get_or_insert_with(|| KeyTracker::new(current_locks()));
+            if let Err(err) = run() {
+                let reason = format!("Failed to listen to the keyboard: {}!", err);
+                *FAILURE.lock().unwrap() = Some(reason.clone());
+                broadcast(Signal::Failed(reason));
+            }
+        });
+    });
+}
+
+/// 订阅按键事件 (释放时取消订阅, 接收端随即收到 [Signal::Stop])
+pub struct Subscription {
+    id: u64,
+    sender: Sender<Signal>,
+}
+
+impl Subscription {
+    pub fn new() -> (Self, Receiver<Signal>) {
+        start();
+
+        let (sender, receiver) = mpsc::channel();
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+        if let Some(reason) = FAILURE.lock().unwrap().clone() {
+            let _ = sender.send(Signal::Failed(reason));
+        }
+        SUBSCRIBERS.lock().unwrap().push((id, sender.clone()));
+
+        (Subscription { id, sender }, receiver)
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        SUBSCRIBERS.lock().unwrap().retain(|(id, _)| *id != self.id);
+        let _ = self.sender.send(Signal::Stop);
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use std::time::{Duration, SystemTime};
+    use super::*;
+
+    #[test]
+    fn modifiers() {
+        let mut tracker = KeyTracker::default();
+
+        let (repeat, modifiers, _) = tracker.update(Key::ControlLeft, true);
+        assert!(!repeat);
+        assert_eq!(modifiers, Modifiers { ctrl_left: true, ..Default::default() });
+        let (_, modifiers, _) = tracker.update(Key::ShiftRight, true);
+        assert!(modifiers.ctrl() && modifiers.shift() && !modifiers.shift_left);
+        // 长按
+        assert_eq!(tracker.update(Key::KeyA, true).0, false);
+        assert_eq!(tracker.update(Key::KeyA, true).0, true);
+        assert_eq!(tracker.update(Key::KeyA, false).0, false);
+
+        assert_eq!(tracker.update(Key::ControlLeft, false).1, Modifiers { shift_right: true, ..Default::default() });
+        assert_eq!(tracker.update(Key::ShiftRight, false).1, Modifiers::default());
+    }
+
+    #[test]
+    fn locks() {
+        let mut tracker = KeyTracker::new(Locks { caps: false, num: true, scroll: false });
+
+        assert_eq!(tracker.update(Key::CapsLock, true).2, Locks { caps: true, num: true, scroll: false });
+        // 长按不切换
+        assert_eq!(tracker.update(Key::CapsLock, true).2.caps, true);
+        assert_eq!(tracker.update(Key::CapsLock, false).2.caps, true);
+        assert_eq!(tracker.update(Key::NumLock, true).2.num, false);
+        tracker.update(Key::CapsLock, true);
+        assert_eq!(tracker.update(Key::CapsLock, false).2, Locks::default());
+        // 被拦截的锁定键
+        tracker.update(Key::CapsLock, true);
+        assert_eq!(tracker.untoggle(Key::CapsLock), Locks::default());
+    }
+
+    #[test]
+    fn grab_text() {
+        let _guard = TEST_GUARD.lock().unwrap_or_else(|err| err.into_inner());
+        let (sender, receiver) = mpsc::channel();
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+        SUBSCRIBERS.lock().unwrap().push((id, sender));
+
+        let hook = u64::MAX;
+        let mut suppressor = Suppressor::default();
+        let event = Event { event_type: EventType::KeyPress(Key::KeyQ), time: SystemTime::now(), name: Some(String::from("q")) };
+        INSTALLED.store(hook, Ordering::SeqCst);
+        intercept(hook, &mut suppressor, event.clone());
+        // 已卸载的钩子不再分发
+        INSTALLED.store(0, Ordering::SeqCst);
+        GRAB_HOOK.store(0, Ordering::SeqCst);
+        intercept(hook, &mut suppressor, event);
+        SUBSCRIBERS.lock().unwrap().retain(|(v, _)| *v != id);
+
+        // 拦截钩子分发的事件同样带有输入的字符
+        match receiver.try_recv() {
+            Ok(Signal::Key(input)) => assert_eq!((input.key, input.text), (Key::KeyQ, Some(String::from("q")))),
+            other => panic!("unexpected signal: {:?}", other),
+        }
+        assert!(receiver.try_recv().is_err());
+    }
+
+    /// 事件经 [dispatch] (修饰键状态维护 + 判定 + 广播) 至订阅者收到的耗时:
+    /// `cargo test --release dispatch_latency -- --ignored --nocapture`
+    /// (此前每次按键都新建 `DeviceState` 并以 `get_keys` 重新扫描键盘, X11 下即一次连接及 `XQueryKeymap` 往返)
+    #[test]
+    #[ignore]
+    fn dispatch_latency() {
+        let _guard = TEST_GUARD.lock().unwrap_or_else(|err| err.into_inner());
+        const EVENTS: u32 = 100_000;
+        let (sender, receiver) = mpsc::channel();
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+        SUBSCRIBERS.lock().unwrap().push((id, sender));
+        let consumer = thread::spawn(move || {
+            let mut total = Duration::ZERO;
+            for _ in 0..EVENTS {
+                match receiver.recv() {
+                    Ok(Signal::Key(input)) => total += input.time.elapsed(),
+                    other => panic!("unexpected signal: {:?}", other),
+                }
+            }
+            total / EVENTS
+        });
+
+        let mut tracker = KeyTracker::default();
+        let keys = [Key::ControlLeft, Key::KeyA, Key::KeyS, Key::ShiftLeft, Key::KeyD];
+        let started = Instant::now();
+        for i in 0..EVENTS as usize {
+            let key = keys[i % keys.len()];
+            let event_type = if i % 2 == 0 { EventType::KeyPress(key) } else { EventType::KeyRelease(key) };
+            dispatch(&mut tracker, &Event { event_type, time: SystemTime::now(), name: None }, |_, _, _| Verdict::Pass);
+        }
+        let dispatching = started.elapsed() / EVENTS;
+        let delivering = consumer.join().unwrap();
+        SUBSCRIBERS.lock().unwrap().retain(|(v, _)| *v != id);
+
+        println!("per event: dispatch {:?} | deliver {:?}", dispatching, delivering);
+        assert!(dispatching < Duration::from_millis(1));
+        assert!(delivering < Duration::from_millis(1));
+    }
+}
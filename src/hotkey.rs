@@ -0,0 +1,419 @@
+//! 具名快捷键管理
+//!
+//! 在 [Observer] 之上按名称注册快捷键 (在目标键按下时触发), 注册时检测与已注册的快捷键及系统快捷键的冲突;
+//! 绑定可导出/导入为 JSON, 导入的绑定会覆盖同名快捷键的组合键 (尚未注册的名称在注册时生效)
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+};
+use napi::{Error, JsFunction, Status};
+use napi::threadsafe_function::ThreadsafeFunction;
+use crate::check_key;
+use crate::observer::Observer;
+use crate::utils::{ExtraKey, HotkeyBinding, HotkeyConflict, KeyCombination, KeyEvRegister};
+
+const CTRL: u8 = 1;
+const ALT: u8 = 1 << 1;
+const SHIFT: u8 = 1 << 2;
+const META: u8 = 1 << 3;
+
+/// 系统快捷键 (修饰键, 目标键, 说明)
+#[cfg(target_os = "windows")]
+const SYSTEM_SHORTCUTS: &[(u8, &str, &str)] = &[
+    (ALT, "Tab", "Switch windows"),
+    (ALT, "F4", "Close window"),
+    (ALT, "Escape", "Cycle windows"),
+    (CTRL | ALT, "Delete", "Security options"),
+    (CTRL | SHIFT, "Escape", "Task Manager"),
+    (CTRL, "Escape", "Start menu"),
+    (META, "KeyA", "Quick settings"),
+    (META, "KeyD", "Show desktop"),
+    (META, "KeyE", "File Explorer"),
+    (META, "KeyI", "Settings"),
+    (META, "KeyL", "Lock screen"),
+    (META, "KeyR", "Run dialog"),
+    (META, "KeyV", "Clipboard history"),
+    (META, "Tab", "Task view"),
+    (META | SHIFT, "KeyS", "Screen snip"),
+];
+
+/// 系统快捷键 (修饰键, 目标键, 说明)
+#[cfg(target_os = "macos")]
+const SYSTEM_SHORTCUTS: &[(u8, &str, &str)] = &[
+    (META, "Tab", "Switch applications"),
+    (META, "Space", "Spotlight"),
+    (META, "KeyH", "Hide application"),
+    (META, "KeyM", "Minimize window"),
+    (META, "KeyQ", "Quit application"),
+    (META | ALT, "Escape", "Force quit"),
+    (META | CTRL, "KeyQ", "Lock screen"),
+    (META | SHIFT, "Digit3", "Screenshot"),
+    (META | SHIFT, "Digit4", "Screenshot selection"),
+    (META | SHIFT, "Digit5", "Screenshot toolbar"),
+    (CTRL, "Space", "Switch input source"),
+    (CTRL, "ArrowUp", "Mission Control"),
+    (CTRL, "ArrowDown", "Application windows"),
+    (CTRL, "ArrowLeft", "Move left a space"),
+    (CTRL, "ArrowRight", "Move right a space"),
+];
+
+/// 系统快捷键 (修饰键, 目标键, 说明) -- 常见桌面环境 (GNOME / KDE) 的默认值
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const SYSTEM_SHORTCUTS: &[(u8, &str, &str)] = &[
+    (ALT, "Tab", "Switch windows"),
+    (ALT, "F2", "Run command"),
+    (ALT, "F4", "Close window"),
+    (CTRL | ALT, "Delete", "Log out"),
+    (CTRL | ALT, "KeyT", "Terminal"),
+    (CTRL | ALT, "ArrowLeft", "Previous workspace"),
+    (CTRL | ALT, "ArrowRight", "Next workspace"),
+    (META, "KeyA", "Show applications"),
+    (META, "KeyL", "Lock screen"),
+    (META, "Tab", "Switch applications"),
+    (META, "Space", "Switch input source"),
+];
+
+/// 归一化的组合键
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Combo {
+    key: String,
+    /// 修饰键位掩码
+    mods: u8,
+}
+
+impl Combo {
+    fn of(combination: &KeyCombination) -> Self {
+        let flag = |v: Option<bool>, mask: u8| match v {
+            Some(true) => mask,
+            _ => 0
+        };
+        let mods = match &combination.extra {
+            Some(v) => flag(v.ctrl, CTRL) | flag(v.alt, ALT) | flag(v.shift, SHIFT) | flag(v.meta, META),
+            None => 0
+        };
+
+        Combo { key: combination.key.clone(), mods }
+    }
+
+    fn to_combination(&self) -> KeyCombination {
+        KeyCombination {
+            key: self.key.clone(),
+            extra: Some(ExtraKey {
+                ctrl: Some(self.mods & CTRL != 0),
+                alt: Some(self.mods & ALT != 0),
+                shift: Some(self.mods & SHIFT != 0),
+                meta: Some(self.mods & META != 0),
+            }),
+        }
+    }
+
+    fn to_register(&self) -> KeyEvRegister {
+        let m = self.mods;
+        KeyEvRegister::new(self.key.clone(), m & CTRL != 0, m & ALT != 0, m & SHIFT != 0, m & META != 0, true)
+    }
+
+    /// 目标键相同, 且一方的修饰键是另一方的真子集
+    fn overlaps(&self, other: &Combo) -> bool {
+        let common = self.mods & other.mods;
+        self.key == other.key && self.mods != other.mods && (common == self.mods || common == other.mods)
+    }
+}
+
+/// 已注册的快捷键
+struct Entry {
+    binding: HotkeyBinding,
+    combo: Combo,
+}
+
+/// 检测 `combo` 的冲突 (跳过名为 `except` 的快捷键)
+fn find_conflicts(entries: &[Entry], combo: &Combo, except: Option<&str>) -> Vec<HotkeyConflict> {
+    let mut conflicts = vec![];
+
+    for entry in entries {
+        if Some(entry.binding.name.as_str()) == except {
+            continue;
+        }
+
+        let kind = if entry.combo == *combo {
+            "duplicate"
+        } else if entry.combo.overlaps(combo) {
+            "subsumed"
+        } else {
+            continue;
+        };
+        conflicts.push(HotkeyConflict {
+            kind: String::from(kind),
+            name: Some(entry.binding.name.clone()),
+            owner: Some(entry.binding.owner.clone()),
+            combination: entry.combo.to_combination(),
+            description: format!("Conflicts with \"{}\" registered by \"{}\"", entry.binding.name, entry.binding.owner),
+        });
+    }
+
+    for (mods, key, description) in SYSTEM_SHORTCUTS {
+        let system = Combo { key: key.to_string(), mods: *mods };
+        if system == *combo {
+            conflicts.push(HotkeyConflict {
+                kind: String::from("system"),
+                name: None,
+                owner: None,
+                combination: system.to_combination(),
+                description: format!("Reserved by the system: {}", description),
+            });
+        }
+    }
+
+    conflicts
+}
+
+/// 是否存在阻止注册的冲突 (`subsumed` 仅作提示)
+fn is_blocking(conflicts: &[HotkeyConflict]) -> bool {
+    conflicts.iter().any(|conflict| conflict.kind != "subsumed")
+}
+
+fn conflict_error(name: &str, conflicts: &[HotkeyConflict]) -> Error {
+    let reasons: Vec<String> = conflicts.iter().map(|conflict| conflict.description.clone()).collect();
+    Error::new(Status::InvalidArg, format!("Hotkey \"{}\" conflicts: {}!", name, reasons.join("; ")))
+}
+
+fn check_combination(combination: &KeyCombination) -> napi::Result<()> {
+    if check_key(combination.key.clone()).unwrap() {
+        Ok(())
+    } else {
+        Err(Error::new(Status::InvalidArg, String::from("Invalid Key!")))
+    }
+}
+
+#[napi]
+pub struct HotkeyManager {
+    observer: Observer,
+
+    /// 已注册的快捷键 (按注册顺序)
+    entries: Mutex<Vec<Entry>>,
+
+    /// 快捷键名称 -> (监听句柄, 回调函数)
+    listeners: Mutex<HashMap<String, (u32, ThreadsafeFunction<()>)>>,
+
+    /// 导入的、尚未注册的绑定 (注册同名快捷键时使用)
+    overrides: Mutex<HashMap<String, HotkeyBinding>>,
+}
+
+#[napi]
+impl HotkeyManager {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        HotkeyManager {
+            observer: Observer::new(),
+            entries: Mutex::new(vec![]),
+            listeners: Mutex::new(HashMap::new()),
+            overrides: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 注册具名快捷键 (按下 `combination` 时触发), 返回检测到的冲突
+    /// 与已注册的快捷键相同或被系统占用时抛出异常, `force` 为 `true` 时强制注册; 已导入同名绑定时以导入的组合键为准
+    #[napi]
+    pub fn register(
+        &self,
+        name: String,
+        owner: String,
+        combination: KeyCombination,
+        #[napi(ts_arg_type = "(err: null | Error) => void")] callback: JsFunction,
+        force: Option<bool>,
+    ) -> napi::Result<Vec<HotkeyConflict>> {
+        let combination = match self.overrides.lock().unwrap().get(&name) {
+            Some(v) => v.combination.clone(),
+            None => combination
+        };
+        check_combination(&combination)?;
+
+        let mut entries = self.entries.lock().unwrap();
+        if entries.iter().any(|entry| entry.binding.name == name) {
+            return Err(Error::new(Status::InvalidArg, format!("Hotkey \"{}\" is already registered!", name)));
+        }
+
+        let combo = Combo::of(&combination);
+        let conflicts = find_conflicts(&entries, &combo, None);
+        let force = match force {
+            Some(v) => v,
+            None => false
+        };
+        if !force && is_blocking(&conflicts) {
+            return Err(conflict_error(&name, &conflicts));
+        }
+
+        let tsfn: ThreadsafeFunction<()> = callback.create_threadsafe_function(0, |ctx| {
+            Ok(vec![ctx.value])
+        })?;
+        let id = self.observer.add_listener(combo.to_register(), tsfn.clone(), false);
+        self.listeners.lock().unwrap().insert(name.clone(), (id, tsfn));
+        self.overrides.lock().unwrap().remove(&name);
+        entries.push(Entry { binding: HotkeyBinding { name, owner, combination: combo.to_combination() }, combo });
+
+        Ok(conflicts)
+    }
+
+    /// 移除具名快捷键 (返回值表示此前是否已注册)
+    #[napi]
+    pub fn unregister(&self, name: String) -> napi::Result<bool> {
+        let mut entries = self.entries.lock().unwrap();
+        let count = entries.len();
+        entries.retain(|entry| entry.binding.name != name);
+
+        if let Some((id, _)) = self.listeners.lock().unwrap().remove(&name) {
+            self.observer.off(id)?;
+        }
+
+        Ok(entries.len() != count)
+    }
+
+    /// 修改已注册快捷键的组合键 (冲突规则与 `register` 相同)
+    #[napi]
+    pub fn rebind(&self, name: String, combination: KeyCombination, force: Option<bool>) -> napi::Result<Vec<HotkeyConflict>> {
+        check_combination(&combination)?;
+
+        let mut entries = self.entries.lock().unwrap();
+        let combo = Combo::of(&combination);
+        let conflicts = find_conflicts(&entries, &combo, Some(&name));
+        let force = match force {
+            Some(v) => v,
+            None => false
+        };
+        if !force && is_blocking(&conflicts) {
+            return Err(conflict_error(&name, &conflicts));
+        }
+
+        let entry = match entries.iter_mut().find(|entry| entry.binding.name == name) {
+            Some(v) => v,
+            None => return Err(Error::new(Status::InvalidArg, format!("Hotkey \"{}\" is not registered!", name)))
+        };
+
+        let mut listeners = self.listeners.lock().unwrap();
+        if let Some((id, tsfn)) = listeners.get_mut(&name) {
+            self.observer.off(*id)?;
+            *id = self.observer.add_listener(combo.to_register(), tsfn.clone(), false);
+        }
+        entry.binding.combination = combo.to_combination();
+        entry.combo = combo;
+
+        Ok(conflicts)
+    }
+
+    /// `combination` 与已注册的快捷键及系统快捷键的冲突
+    #[napi]
+    pub fn conflicts(&self, combination: KeyCombination) -> napi::Result<Vec<HotkeyConflict>> {
+        check_combination(&combination)?;
+        Ok(find_conflicts(&self.entries.lock().unwrap(), &Combo::of(&combination), None))
+    }
+
+    /// 已注册的快捷键及其所属模块 (按注册顺序)
+    #[napi(getter)]
+    pub fn bindings(&self) -> napi::Result<Vec<HotkeyBinding>> {
+        Ok(self.entries.lock().unwrap().iter().map(|entry| entry.binding.clone()).collect())
+    }
+
+    /// 导出已注册 (及已导入但尚未注册) 的绑定, 格式为 `HotkeyBinding` 数组的 JSON
+    #[napi]
+    pub fn export_json(&self) -> napi::Result<String> {
+        let mut bindings = self.bindings()?;
+        bindings.extend(self.overrides.lock().unwrap().values().cloned());
+
+        match serde_json::to_string_pretty(&bindings) {
+            Ok(v) => Ok(v),
+            Err(err) => Err(Error::new(Status::GenericFailure, format!("Failed to export hotkeys: {}!", err)))
+        }
+    }
+
+    /// 导入 `exportJson` 导出的绑定: 已注册的快捷键直接修改 (冲突仅返回, 不拒绝), 其余的在注册同名快捷键时生效
+    #[napi]
+    pub fn import_json(&self, json: String) -> napi::Result<Vec<HotkeyConflict>> {
+        let bindings: Vec<HotkeyBinding> = match serde_json::from_str(&json) {
+            Ok(v) => v,
+            Err(err) => return Err(Error::new(Status::InvalidArg, format!("Invalid hotkey JSON: {}!", err)))
+        };
+        for binding in &bindings {
+            check_combination(&binding.combination)?;
+        }
+
+        let mut conflicts = vec![];
+        for binding in bindings {
+            let registered = self.entries.lock().unwrap().iter().any(|entry| entry.binding.name == binding.name);
+            if registered {
+                conflicts.extend(self.rebind(binding.name, binding.combination, Some(true))?);
+            } else {
+                self.overrides.lock().unwrap().insert(binding.name.clone(), binding);
+            }
+        }
+
+        Ok(conflicts)
+    }
+
+    /// 结束监听并释放全部回调 (与 `Observer.dispose` 相同, 必须调用!)
+    #[napi]
+    pub fn dispose(&mut self) -> napi::Result<()> {
+        self.entries.lock().unwrap().clear();
+        self.listeners.lock().unwrap().clear();
+        self.observer.dispose()
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    fn combination(key: &str, ctrl: bool, shift: bool) -> KeyCombination {
+        KeyCombination {
+            key: String::from(key),
+            extra: Some(ExtraKey { ctrl: Some(ctrl), alt: None, shift: Some(shift), meta: None }),
+        }
+    }
+
+    fn entry(name: &str, combination: KeyCombination) -> Entry {
+        Entry {
+            binding: HotkeyBinding { name: String::from(name), owner: String::from("test"), combination: combination.clone() },
+            combo: Combo::of(&combination),
+        }
+    }
+
+    #[test]
+    fn conflicts() {
+        let entries = vec![entry("save", combination("KeyS", true, false)), entry("kill", combination("KeyK", true, false))];
+
+        let duplicate = find_conflicts(&entries, &Combo::of(&combination("KeyS", true, false)), None);
+        assert_eq!(duplicate.len(), 1);
+        assert_eq!(duplicate[0].kind, "duplicate");
+        assert!(is_blocking(&duplicate));
+        // 修改自身的组合键不算冲突
+        assert!(find_conflicts(&entries, &Combo::of(&combination("KeyS", true, false)), Some("save")).is_empty());
+
+        let subsumed = find_conflicts(&entries, &Combo::of(&combination("KeyK", true, true)), None);
+        assert_eq!(subsumed[0].kind, "subsumed");
+        assert_eq!(subsumed[0].name, Some(String::from("kill")));
+        assert!(!is_blocking(&subsumed));
+
+        assert!(find_conflicts(&entries, &Combo::of(&combination("KeyJ", true, false)), None).is_empty());
+
+        let (mods, key, _) = SYSTEM_SHORTCUTS[0];
+        let system = find_conflicts(&entries, &Combo { key: key.to_string(), mods }, None);
+        assert_eq!(system[0].kind, "system");
+    }
+
+    #[test]
+    fn json() {
+        let binding = HotkeyBinding {
+            name: String::from("save"),
+            owner: String::from("editor"),
+            combination: Combo::of(&combination("KeyS", true, false)).to_combination(),
+        };
+
+        let json = serde_json::to_string(&vec![binding.clone()]).unwrap();
+        let parsed: Vec<HotkeyBinding> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, vec![binding]);
+
+        // 省略的辅助键视为未按下
+        let parsed: Vec<HotkeyBinding> = serde_json::from_str(r#"[{"name": "a", "owner": "b", "combination": {"key": "KeyA"}}]"#).unwrap();
+        assert_eq!(Combo::of(&parsed[0].combination), Combo { key: String::from("KeyA"), mods: 0 });
+    }
+}
@@ -8,8 +8,23 @@ mod mapper;
 pub mod utils;
 pub mod controller;
 pub mod observer;
+pub mod hook;
+pub mod hotkey;
+pub mod gesture;
+pub mod expander;
+pub mod grab;
+pub mod remapper;
 pub mod clipboard;
 pub mod capture;
+pub mod frame;
+pub mod stream;
+pub mod matcher;
+pub mod wait;
+pub mod cursor;
+pub mod coords;
+pub mod annotate;
+pub mod ocr;
+pub mod record;
 
 /// 检查键盘按键名是否合法
 #[napi]
@@ -1,5 +1,5 @@
 mod enigo_mapper;
-mod dq_mapper;
+mod rdev_mapper;
 
 pub use enigo_mapper::EnigoMapper;
-pub use dq_mapper::DQMapper;
\ No newline at end of file
+pub use rdev_mapper::RdevMapper;
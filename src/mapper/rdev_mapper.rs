@@ -0,0 +1,267 @@
+use rdev::Key as RKey;
+
+pub struct RdevMapper {}
+
+#[allow(unused)]
+impl RdevMapper {
+    /// rdev -> String
+    pub fn encode_key(key: &RKey) -> Option<String> {
+        match key {
+            RKey::F1 => Some(String::from("F1")),
+            RKey::F2 => Some(String::from("F2")),
+            RKey::F3 => Some(String::from("F3")),
+            RKey::F4 => Some(String::from("F4")),
+            RKey::F5 => Some(String::from("F5")),
+            RKey::F6 => Some(String::from("F6")),
+            RKey::F7 => Some(String::from("F7")),
+            RKey::F8 => Some(String::from("F8")),
+            RKey::F9 => Some(String::from("F9")),
+            RKey::F10 => Some(String::from("F10")),
+            RKey::F11 => Some(String::from("F11")),
+            RKey::F12 => Some(String::from("F12")),
+            RKey::Num0 => Some(String::from("Digit0")),
+            RKey::Num1 => Some(String::from("Digit1")),
+            RKey::Num2 => Some(String::from("Digit2")),
+            RKey::Num3 => Some(String::from("Digit3")),
+            RKey::Num4 => Some(String::from("Digit4")),
+            RKey::Num5 => Some(String::from("Digit5")),
+            RKey::Num6 => Some(String::from("Digit6")),
+            RKey::Num7 => Some(String::from("Digit7")),
+            RKey::Num8 => Some(String::from("Digit8")),
+            RKey::Num9 => Some(String::from("Digit9")),
+            RKey::KeyA => Some(String::from("KeyA")),
+            RKey::KeyB => Some(String::from("KeyB")),
+            RKey::KeyC => Some(String::from("KeyC")),
+            RKey::KeyD => Some(String::from("KeyD")),
+            RKey::KeyE => Some(String::from("KeyE")),
+            RKey::KeyF => Some(String::from("KeyF")),
+            RKey::KeyG => Some(String::from("KeyG")),
+            RKey::KeyH => Some(String::from("KeyH")),
+            RKey::KeyI => Some(String::from("KeyI")),
+            RKey::KeyJ => Some(String::from("KeyJ")),
+            RKey::KeyK => Some(String::from("KeyK")),
+            RKey::KeyL => Some(String::from("KeyL")),
+            RKey::KeyM => Some(String::from("KeyM")),
+            RKey::KeyN => Some(String::from("KeyN")),
+            RKey::KeyO => Some(String::from("KeyO")),
+            RKey::KeyP => Some(String::from("KeyP")),
+            RKey::KeyQ => Some(String::from("KeyQ")),
+            RKey::KeyR => Some(String::from("KeyR")),
+            RKey::KeyS => Some(String::from("KeyS")),
+            RKey::KeyT => Some(String::from("KeyT")),
+            RKey::KeyU => Some(String::from("KeyU")),
+            RKey::KeyV => Some(String::from("KeyV")),
+            RKey::KeyW => Some(String::from("KeyW")),
+            RKey::KeyX => Some(String::from("KeyX")),
+            RKey::KeyY => Some(String::from("KeyY")),
+            RKey::KeyZ => Some(String::from("KeyZ")),
+            RKey::MetaLeft | RKey::MetaRight => Some(String::from("Meta")),
+            RKey::Escape => Some(String::from("Escape")),
+            RKey::Tab => Some(String::from("Tab")),
+            RKey::CapsLock => Some(String::from("CapsLock")),
+            RKey::ShiftLeft => Some(String::from("ShiftLeft")),
+            RKey::ShiftRight => Some(String::from("ShiftRight")),
+            RKey::ControlLeft => Some(String::from("ControlLeft")),
+            RKey::ControlRight => Some(String::from("ControlRight")),
+            RKey::Alt => Some(String::from("AltLeft")),
+            RKey::AltGr => Some(String::from("AltRight")),
+            RKey::Space => Some(String::from("Space")),
+            RKey::UpArrow => Some(String::from("ArrowUp")),
+            RKey::RightArrow => Some(String::from("ArrowRight")),
+            RKey::DownArrow => Some(String::from("ArrowDown")),
+            RKey::LeftArrow => Some(String::from("ArrowLeft")),
+            RKey::Return => Some(String::from("Enter")),
+            RKey::Backspace => Some(String::from("Backspace")),
+            RKey::Delete => Some(String::from("Delete")),
+            RKey::Home => Some(String::from("Home")),
+            RKey::PageUp => Some(String::from("PageUp")),
+            RKey::PageDown => Some(String::from("PageDown")),
+            RKey::End => Some(String::from("End")),
+            RKey::BackQuote => Some(String::from("Backquote")),
+            RKey::Minus => Some(String::from("Minus")),
+            RKey::Equal => Some(String::from("Equal")),
+            RKey::LeftBracket => Some(String::from("BracketLeft")),
+            RKey::RightBracket => Some(String::from("BracketRight")),
+            RKey::Comma => Some(String::from("Comma")),
+            RKey::Dot => Some(String::from("Period")),
+            RKey::SemiColon => Some(String::from("Semicolon")),
+            RKey::Quote => Some(String::from("Quote")),
+            RKey::Slash => Some(String::from("Slash")),
+            RKey::BackSlash => Some(String::from("BackSlash")),
+            RKey::IntlBackslash => Some(String::from("IntlBackslash")),
+            RKey::Insert => Some(String::from("Insert")),
+            RKey::PrintScreen => Some(String::from("PrintScreen")),
+            RKey::Pause => Some(String::from("Pause")),
+            RKey::NumLock => Some(String::from("NumLock")),
+            RKey::ScrollLock => Some(String::from("ScrollLock")),
+            RKey::Function => Some(String::from("Fn")),
+            // 小键盘的按键不建议使用
+            RKey::Kp0 => Some(String::from("Numpad0")),
+            RKey::Kp1 => Some(String::from("Numpad1")),
+            RKey::Kp2 => Some(String::from("Numpad2")),
+            RKey::Kp3 => Some(String::from("Numpad3")),
+            RKey::Kp4 => Some(String::from("Numpad4")),
+            RKey::Kp5 => Some(String::from("Numpad5")),
+            RKey::Kp6 => Some(String::from("Numpad6")),
+            RKey::Kp7 => Some(String::from("Numpad7")),
+            RKey::Kp8 => Some(String::from("Numpad8")),
+            RKey::Kp9 => Some(String::from("Numpad9")),
+            RKey::KpPlus => Some(String::from("NumpadAdd")),
+            RKey::KpMinus => Some(String::from("NumpadSubtract")),
+            RKey::KpMultiply => Some(String::from("NumpadMultiply")),
+            RKey::KpDivide => Some(String::from("NumpadDivide")),
+            RKey::KpReturn => Some(String::from("NumpadEnter")),
+            RKey::KpDelete => Some(String::from("NumpadDecimal")),
+            // 不列出通配分支, 保证除 `Unknown` 外的按键均有名称
+            RKey::Unknown(_) => None
+        }
+    }
+
+    /// 未映射按键的原始键码 (即 [RdevMapper::encode_key] 无法命名的按键: rdev 无法识别的按键, 如媒体键 / F13 及以上)
+    pub fn raw_code(key: &RKey) -> Option<u32> {
+        match key {
+            RKey::Unknown(code) => Some(*code),
+            _ => None
+        }
+    }
+
+    /// String -> rdev
+    pub fn decode_key(key: String) -> Option<RKey> {
+        match &key[..] {
+            "F1" => Some(RKey::F1),
+            "F2" => Some(RKey::F2),
+            "F3" => Some(RKey::F3),
+            "F4" => Some(RKey::F4),
+            "F5" => Some(RKey::F5),
+            "F6" => Some(RKey::F6),
+            "F7" => Some(RKey::F7),
+            "F8" => Some(RKey::F8),
+            "F9" => Some(RKey::F9),
+            "F10" => Some(RKey::F10),
+            "F11" => Some(RKey::F11),
+            "F12" => Some(RKey::F12),
+            "Digit0" => Some(RKey::Num0),
+            "Digit1" => Some(RKey::Num1),
+            "Digit2" => Some(RKey::Num2),
+            "Digit3" => Some(RKey::Num3),
+            "Digit4" => Some(RKey::Num4),
+            "Digit5" => Some(RKey::Num5),
+            "Digit6" => Some(RKey::Num6),
+            "Digit7" => Some(RKey::Num7),
+            "Digit8" => Some(RKey::Num8),
+            "Digit9" => Some(RKey::Num9),
+            "KeyA" => Some(RKey::KeyA),
+            "KeyB" => Some(RKey::KeyB),
+            "KeyC" => Some(RKey::KeyC),
+            "KeyD" => Some(RKey::KeyD),
+            "KeyE" => Some(RKey::KeyE),
+            "KeyF" => Some(RKey::KeyF),
+            "KeyG" => Some(RKey::KeyG),
+            "KeyH" => Some(RKey::KeyH),
+            "KeyI" => Some(RKey::KeyI),
+            "KeyJ" => Some(RKey::KeyJ),
+            "KeyK" => Some(RKey::KeyK),
+            "KeyL" => Some(RKey::KeyL),
+            "KeyM" => Some(RKey::KeyM),
+            "KeyN" => Some(RKey::KeyN),
+            "KeyO" => Some(RKey::KeyO),
+            "KeyP" => Some(RKey::KeyP),
+            "KeyQ" => Some(RKey::KeyQ),
+            "KeyR" => Some(RKey::KeyR),
+            "KeyS" => Some(RKey::KeyS),
+            "KeyT" => Some(RKey::KeyT),
+            "KeyU" => Some(RKey::KeyU),
+            "KeyV" => Some(RKey::KeyV),
+            "KeyW" => Some(RKey::KeyW),
+            "KeyX" => Some(RKey::KeyX),
+            "KeyY" => Some(RKey::KeyY),
+            "KeyZ" => Some(RKey::KeyZ),
+            "Meta" => Some(RKey::MetaLeft),
+            "Escape" => Some(RKey::Escape),
+            "Tab" => Some(RKey::Tab),
+            "CapsLock" => Some(RKey::CapsLock),
+            "Shift" => Some(RKey::ShiftLeft),
+            "Control" => Some(RKey::ControlLeft),
+            "Alt" => Some(RKey::Alt),
+            "Space" => Some(RKey::Space),
+            "ArrowUp" => Some(RKey::UpArrow),
+            "ArrowRight" => Some(RKey::RightArrow),
+            "ArrowDown" => Some(RKey::DownArrow),
+            "ArrowLeft" => Some(RKey::LeftArrow),
+            "Enter" => Some(RKey::Return),
+            "Backspace" => Some(RKey::Backspace),
+            "Delete" => Some(RKey::Delete),
+            "Home" => Some(RKey::Home),
+            "PageUp" => Some(RKey::PageUp),
+            "PageDown" => Some(RKey::PageDown),
+            "End" => Some(RKey::End),
+            "Backquote" => Some(RKey::BackQuote),
+            "Minus" => Some(RKey::Minus),
+            "Equal" => Some(RKey::Equal),
+            "BracketLeft" => Some(RKey::LeftBracket),
+            "BracketRight" => Some(RKey::RightBracket),
+            "Comma" => Some(RKey::Comma),
+            "Period" => Some(RKey::Dot),
+            "Semicolon" => Some(RKey::SemiColon),
+            "Quote" => Some(RKey::Quote),
+            "Slash" => Some(RKey::Slash),
+            "BackSlash" => Some(RKey::BackSlash),
+            "IntlBackslash" => Some(RKey::IntlBackslash),
+            "Insert" => Some(RKey::Insert),
+            "PrintScreen" => Some(RKey::PrintScreen),
+            "Pause" => Some(RKey::Pause),
+            "NumLock" => Some(RKey::NumLock),
+            "ScrollLock" => Some(RKey::ScrollLock),
+            "Fn" => Some(RKey::Function),
+            // 以下按键可明确指定左右
+            "ShiftLeft" => Some(RKey::ShiftLeft),
+            "ShiftRight" => Some(RKey::ShiftRight),
+            "ControlLeft" => Some(RKey::ControlLeft),
+            "ControlRight" => Some(RKey::ControlRight),
+            "AltLeft" => Some(RKey::Alt),
+            "AltRight" => Some(RKey::AltGr),
+            // 小键盘的按键不建议使用
+            "Numpad0" => Some(RKey::Kp0),
+            "Numpad1" => Some(RKey::Kp1),
+            "Numpad2" => Some(RKey::Kp2),
+            "Numpad3" => Some(RKey::Kp3),
+            "Numpad4" => Some(RKey::Kp4),
+            "Numpad5" => Some(RKey::Kp5),
+            "Numpad6" => Some(RKey::Kp6),
+            "Numpad7" => Some(RKey::Kp7),
+            "Numpad8" => Some(RKey::Kp8),
+            "Numpad9" => Some(RKey::Kp9),
+            "NumpadAdd" => Some(RKey::KpPlus),
+            "NumpadSubtract" => Some(RKey::KpMinus),
+            "NumpadMultiply" => Some(RKey::KpMultiply),
+            "NumpadDivide" => Some(RKey::KpDivide),
+            "NumpadEnter" => Some(RKey::KpReturn),
+            "NumpadDecimal" => Some(RKey::KpDelete),
+            _ => None
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    #[test]
+    fn unknown() {
+        assert_eq!(RdevMapper::encode_key(&RKey::Unknown(173)), None);
+        assert_eq!(RdevMapper::raw_code(&RKey::Unknown(173)), Some(173));
+        assert_eq!(RdevMapper::raw_code(&RKey::KeyA), None);
+        assert_eq!(RdevMapper::encode_key(&RKey::AltGr), Some(String::from("AltRight")));
+
+        // 具名的按键可以互相转换
+        let keys = [
+            RKey::Insert, RKey::PrintScreen, RKey::Pause, RKey::NumLock, RKey::ScrollLock,
+            RKey::KpReturn, RKey::KpDelete, RKey::Function, RKey::IntlBackslash,
+        ];
+        for key in keys {
+            let name = RdevMapper::encode_key(&key).unwrap();
+            assert_eq!(RdevMapper::decode_key(name), Some(key));
+        }
+    }
+}
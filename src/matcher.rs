@@ -0,0 +1,359 @@
+use crate::frame::Frame;
+
+/// 粗匹配时模板较短边的目标长度 (像素)
+const COARSE_SIDE: u32 = 12;
+/// 每个尺度下精匹配的最大候选数
+const MAX_CANDIDATES: usize = 32;
+/// 判定两个匹配结果重叠的交并比
+const OVERLAP_IOU: f32 = 0.3;
+/// 视为纯色的方差 (每像素)
+const FLAT_VARIANCE: f64 = 1e-6;
+/// 纯色模板通道按颜色评分: 窗口与模板颜色的均方根差达到该值时分数为 0
+const FLAT_TOLERANCE: f64 = 32.0;
+
+/// 单通道图像
+struct Plane {
+    w: u32,
+    h: u32,
+    data: Vec<f32>,
+}
+
+impl Plane {
+    /// 拆分为灰度单通道或 R / G / B 三个通道
+    fn split(frame: &Frame, grayscale: bool) -> Vec<Plane> {
+        let size = (frame.w * frame.h) as usize;
+
+        if grayscale {
+            let mut data = Vec::with_capacity(size);
+            for px in frame.rgba.chunks_exact(4) {
+                data.push(0.299 * px[0] as f32 + 0.587 * px[1] as f32 + 0.114 * px[2] as f32);
+            }
+            vec![Plane { w: frame.w, h: frame.h, data }]
+        } else {
+            (0..3).map(|c| Plane {
+                w: frame.w,
+                h: frame.h,
+                data: frame.rgba.chunks_exact(4).map(|px| px[c] as f32).collect(),
+            }).collect()
+        }
+    }
+
+    /// 按 `factor` 降采样 (取块均值)
+    fn downsample(&self, factor: u32) -> Plane {
+        if factor <= 1 {
+            return Plane { w: self.w, h: self.h, data: self.data.clone() };
+        }
+
+        let (w, h) = (self.w / factor, self.h / factor);
+        let mut data = Vec::with_capacity((w * h) as usize);
+        let area = (factor * factor) as f32;
+        for y in 0..h {
+            for x in 0..w {
+                let mut sum = 0f32;
+                for dy in 0..factor {
+                    let row = ((y * factor + dy) * self.w + x * factor) as usize;
+                    sum += self.data[row..row + factor as usize].iter().sum::<f32>();
+                }
+                data.push(sum / area);
+            }
+        }
+
+        Plane { w, h, data }
+    }
+}
+
+/// 积分图 (用于 O(1) 求任意窗口的和与平方和)
+struct Integral {
+    w: u32,
+    sum: Vec<f64>,
+    sq: Vec<f64>,
+}
+
+impl Integral {
+    fn new(plane: &Plane) -> Self {
+        let w = plane.w + 1;
+        let size = (w * (plane.h + 1)) as usize;
+        let (mut sum, mut sq) = (vec![0f64; size], vec![0f64; size]);
+
+        for y in 0..plane.h {
+            let (mut row_sum, mut row_sq) = (0f64, 0f64);
+            for x in 0..plane.w {
+                let v = plane.data[(y * plane.w + x) as usize] as f64;
+                row_sum += v;
+                row_sq += v * v;
+                let idx = ((y + 1) * w + x + 1) as usize;
+                sum[idx] = sum[idx - w as usize] + row_sum;
+                sq[idx] = sq[idx - w as usize] + row_sq;
+            }
+        }
+
+        Integral { w, sum, sq }
+    }
+
+    /// 窗口 (x, y, w, h) 的和与平方和
+    fn window(&self, x: u32, y: u32, w: u32, h: u32) -> (f64, f64) {
+        let at = |x: u32, y: u32| (y * self.w + x) as usize;
+        let (a, b, c, d) = (at(x, y), at(x + w, y), at(x, y + h), at(x + w, y + h));
+        (
+            self.sum[d] - self.sum[b] - self.sum[c] + self.sum[a],
+            self.sq[d] - self.sq[b] - self.sq[c] + self.sq[a],
+        )
+    }
+}
+
+/// 去均值后的模板通道
+struct Template {
+    w: u32,
+    h: u32,
+    data: Vec<f32>,
+    mean: f64,
+    /// 去均值后的平方和的平方根
+    norm: f64,
+}
+
+impl Template {
+    fn new(plane: &Plane) -> Self {
+        let mean = plane.data.iter().map(|v| *v as f64).sum::<f64>() / plane.data.len().max(1) as f64;
+        let data: Vec<f32> = plane.data.iter().map(|v| (*v as f64 - mean) as f32).collect();
+        let norm = data.iter().map(|v| (*v as f64) * (*v as f64)).sum::<f64>().sqrt();
+        Template { w: plane.w, h: plane.h, data, mean, norm }
+    }
+
+    /// 纯色 (无法计算互相关)
+    fn flat(&self) -> bool {
+        self.norm * self.norm / self.data.len().max(1) as f64 <= FLAT_VARIANCE
+    }
+}
+
+/// 单个通道的图像与积分图
+struct Channel {
+    plane: Plane,
+    integral: Integral,
+}
+
+impl Channel {
+    fn new(plane: Plane) -> Self {
+        let integral = Integral::new(&plane);
+        Channel { plane, integral }
+    }
+}
+
+/// (x, y) 处的归一化互相关系数 (各通道取平均, 范围 -1 ~ 1)
+///
+/// 纯色的模板通道改为比较颜色 (分数随均方根差从 1 降至 0); 纯色窗口与有纹理的模板通道计 0 分
+fn ncc(channels: &[Channel], templates: &[Template], x: u32, y: u32) -> f32 {
+    let mut total = 0f64;
+
+    for (channel, tpl) in channels.iter().zip(templates) {
+        let n = (tpl.w * tpl.h) as f64;
+        let (sum, sq) = channel.integral.window(x, y, tpl.w, tpl.h);
+        if tpl.flat() {
+            let c = tpl.mean;
+            let rms = ((sq - 2.0 * c * sum + n * c * c).max(0.0) / n).sqrt();
+            total += (1.0 - rms / FLAT_TOLERANCE).max(0.0);
+            continue;
+        }
+        let variance = sq - sum * sum / n;
+        if variance / n <= FLAT_VARIANCE {
+            continue;
+        }
+
+        // 模板已去均值, 窗口均值项的贡献为零
+        let mut cross = 0f64;
+        for ty in 0..tpl.h {
+            let row = ((y + ty) * channel.plane.w + x) as usize;
+            let window = &channel.plane.data[row..row + tpl.w as usize];
+            let pattern = &tpl.data[(ty * tpl.w) as usize..((ty + 1) * tpl.w) as usize];
+            cross += window.iter().zip(pattern).map(|(a, b)| (*a * *b) as f64).sum::<f64>();
+        }
+
+        total += cross / (variance.sqrt() * tpl.norm);
+    }
+
+    (total / channels.len() as f64) as f32
+}
+
+/// 匹配结果 (以被搜索图像的像素为单位)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Found {
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+    /// 归一化互相关系数
+    pub score: f32,
+    /// 模板的缩放比例
+    pub scale: f32,
+}
+
+impl Found {
+    /// 交并比
+    fn iou(&self, other: &Found) -> f32 {
+        let left = self.x.max(other.x);
+        let top = self.y.max(other.y);
+        let right = (self.x + self.w).min(other.x + other.w);
+        let bottom = (self.y + self.h).min(other.y + other.h);
+        if right <= left || bottom <= top {
+            return 0.0;
+        }
+
+        let inter = ((right - left) * (bottom - top)) as f32;
+        inter / ((self.w * self.h + other.w * other.h) as f32 - inter)
+    }
+}
+
+/// 按分数从高到低保留互不重叠的结果
+fn suppress(mut found: Vec<Found>, limit: usize) -> Vec<Found> {
+    found.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    let mut kept: Vec<Found> = vec![];
+    for item in found {
+        if kept.len() >= limit {
+            break;
+        }
+        if kept.iter().all(|k| k.iou(&item) < OVERLAP_IOU) {
+            kept.push(item);
+        }
+    }
+    kept
+}
+
+/// 在 `haystack` 中查找 `template`, 返回分数不低于 `threshold` 的结果 (按分数从高到低)
+///
+/// 先在降采样后的图像上粗匹配, 再在原始分辨率上对候选位置的邻域精匹配
+pub fn find(haystack: &Frame, template: &Frame, threshold: f32, grayscale: bool, scales: &[f32]) -> Vec<Found> {
+    let fine: Vec<Channel> = Plane::split(haystack, grayscale).into_iter().map(Channel::new).collect();
+    let mut found = vec![];
+
+    for &scale in scales {
+        let (tw, th) = ((template.w as f32 * scale).round() as u32, (template.h as f32 * scale).round() as u32);
+        if scale <= 0.0 || tw == 0 || th == 0 || tw > haystack.w || th > haystack.h {
+            continue;
+        }
+
+        let mut resized = Frame::new(tw, th);
+        resized.blit_scaled(template, 0, 0, tw, th);
+        let tpl_planes = Plane::split(&resized, grayscale);
+
+        let factor = (tw.min(th) / COARSE_SIDE).max(1);
+
+        // 粗匹配
+        let coarse: Vec<Channel> = fine.iter().map(|c| Channel::new(c.plane.downsample(factor))).collect();
+        let coarse_tpl: Vec<Template> = tpl_planes.iter().map(|p| Template::new(&p.downsample(factor))).collect();
+        let (cw, ch) = (coarse_tpl[0].w, coarse_tpl[0].h);
+        if cw == 0 || ch == 0 || cw > coarse[0].plane.w || ch > coarse[0].plane.h {
+            continue;
+        }
+
+        // 降采样后的分数只是近似值 (受对齐影响可能明显偏低), 因此粗匹配只保留正相关的位置, 取分数最高的若干个
+        let floor = if factor == 1 { threshold } else { f32::EPSILON };
+        let mut candidates = vec![];
+        for y in 0..=coarse[0].plane.h - ch {
+            for x in 0..=coarse[0].plane.w - cw {
+                let score = ncc(&coarse, &coarse_tpl, x, y);
+                if score >= floor {
+                    candidates.push(Found { x, y, w: cw, h: ch, score, scale });
+                }
+            }
+        }
+        let candidates = suppress(candidates, MAX_CANDIDATES);
+
+        if factor == 1 {
+            found.extend(candidates);
+            continue;
+        }
+
+        // 精匹配
+        let fine_tpl: Vec<Template> = tpl_planes.iter().map(Template::new).collect();
+        for candidate in candidates {
+            let mut best: Option<Found> = None;
+            let (cx, cy) = (candidate.x * factor, candidate.y * factor);
+            for y in cy.saturating_sub(factor)..=(cy + factor).min(haystack.h - th) {
+                for x in cx.saturating_sub(factor)..=(cx + factor).min(haystack.w - tw) {
+                    let score = ncc(&fine, &fine_tpl, x, y);
+                    if best.as_ref().map_or(true, |b| score > b.score) {
+                        best = Some(Found { x, y, w: tw, h: th, score, scale });
+                    }
+                }
+            }
+
+            if let Some(b) = best {
+                if b.score >= threshold {
+                    found.push(b);
+                }
+            }
+        }
+    }
+
+    suppress(found, usize::MAX)
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    /// 生成带有纹理的测试图像
+    fn texture(w: u32, h: u32, seed: u32) -> Frame {
+        let mut frame = Frame::new(w, h);
+        for y in 0..h {
+            for x in 0..w {
+                let v = ((x.wrapping_mul(73856093) ^ y.wrapping_mul(19349663) ^ seed.wrapping_mul(83492791)).wrapping_mul(2654435761) >> 24) as u8;
+                let idx = ((y * w + x) * 4) as usize;
+                frame.rgba[idx..idx + 4].copy_from_slice(&[v, v / 2, 255 - v, 255]);
+            }
+        }
+        frame
+    }
+
+    #[test]
+    fn exact() {
+        let haystack = texture(120, 80, 7);
+        let template = haystack.crop(40, 30, 20, 16);
+
+        let found = find(&haystack, &template, 0.95, false, &[1.0]);
+        assert_eq!((found[0].x, found[0].y), (40, 30));
+        assert!(found[0].score > 0.99);
+    }
+
+    #[test]
+    fn coarse_to_fine() {
+        let haystack = texture(300, 200, 3);
+        let template = haystack.crop(123, 77, 48, 40);
+
+        let found = find(&haystack, &template, 0.9, true, &[1.0]);
+        assert_eq!((found[0].x, found[0].y), (123, 77));
+    }
+
+    #[test]
+    fn solid() {
+        // 纯色模板: 按颜色匹配
+        let mut haystack = texture(120, 80, 7);
+        let mut block = Frame::new(12, 10);
+        for px in block.rgba.chunks_exact_mut(4) {
+            px.copy_from_slice(&[255, 0, 0, 255]);
+        }
+        haystack.blit_scaled(&block, 50, 20, 12, 10);
+
+        let found = find(&haystack, &block, 0.95, false, &[1.0]);
+        assert_eq!((found[0].x, found[0].y), (50, 20));
+        assert!(found[0].score > 0.99);
+        block.rgba.chunks_exact_mut(4).for_each(|px| px[1] = 128);
+        assert!(find(&haystack, &block, 0.95, false, &[1.0]).is_empty());
+
+        // 部分通道为纯色
+        let mut haystack = texture(120, 80, 5);
+        haystack.rgba.chunks_exact_mut(4).for_each(|px| px[2] = 0);
+        let template = haystack.crop(30, 40, 20, 16);
+        let found = find(&haystack, &template, 0.95, false, &[1.0]);
+        assert_eq!((found[0].x, found[0].y), (30, 40));
+    }
+
+    #[test]
+    fn missing() {
+        let haystack = texture(120, 80, 7);
+        let template = texture(20, 16, 99);
+
+        assert!(find(&haystack, &template, 0.95, true, &[1.0]).is_empty());
+    }
+}
@@ -2,10 +2,11 @@ use std::{
     collections::HashMap,
     ops::Deref,
     sync::{Arc, Mutex},
-    time::Duration,
+    sync::atomic::{AtomicU32, Ordering},
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    time::{Duration, Instant},
     thread,
 };
-use device_query::{DeviceState, DeviceEvents, DeviceQuery, Keycode as DQKey};
 use napi::{Error, JsFunction, Status};
 use napi::threadsafe_function::{
     ErrorStrategy,
@@ -13,22 +14,97 @@ use napi::threadsafe_function::{
     ThreadsafeFunctionCallMode,
 };
 use crate::check_key;
-use crate::mapper::DQMapper;
-use crate::utils::{KeyEv, KeyEvRegister};
+use crate::gesture::{is_modifier, DoubleTap, LongPress, Sequence};
+use crate::grab::{self, GrabTarget};
+use crate::hook::{Signal, Subscription};
+use crate::mapper::RdevMapper;
+use crate::utils::{ExtraKey, KeyCombination, KeyEv, KeyEvRegister, KeyState, PendingSequence, SequenceOptions};
+
+/// 单个监听回调
+struct Listener<T: 'static> {
+    /// 注册时返回的句柄
+    id: u32,
+    /// 是否仅触发一次
+    once: bool,
+    tsfn: ThreadsafeFunction<T>,
+}
+
+/// 按键序列的监听
+struct SequenceListener {
+    id: u32,
+    steps: Vec<KeyCombination>,
+    state: Sequence,
+    tsfn: ThreadsafeFunction<()>,
+}
+
+/// 按键手势
+enum Gesture {
+    DoubleTap(DoubleTap),
+    LongPress(LongPress),
+}
+
+/// 按键手势的监听
+struct GestureListener {
+    id: u32,
+    gesture: Gesture,
+    tsfn: ThreadsafeFunction<()>,
+}
+
+/// 触发已到时的长按
+fn poll_long_presses(gestures: &mut [GestureListener], now: Instant) {
+    for listener in gestures.iter_mut() {
+        if let Gesture::LongPress(press) = &mut listener.gesture {
+            if press.poll(now) {
+                listener.tsfn.call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+        }
+    }
+}
 
-/// 子线程检查间隔 -- ms
-const LOOP_GAP: u64 = 100;
+/// 最早的长按触发时间
+fn next_deadline(gestures: &[GestureListener]) -> Option<Instant> {
+    gestures.iter().filter_map(|listener| match &listener.gesture {
+        Gesture::LongPress(press) => press.deadline(),
+        _ => None
+    }).min()
+}
+
+/// 依次调用回调, 并移除仅触发一次的回调
+fn fire<T: Clone + 'static>(listeners: &mut Vec<Listener<T>>, value: T) {
+    for listener in listeners.iter() {
+        listener.tsfn.call(Ok(value.clone()), ThreadsafeFunctionCallMode::NonBlocking);
+    }
+    listeners.retain(|listener| !listener.once);
+}
 
 #[napi]
 pub struct Observer {
-    /// 是否监听 -- 为 `false` 表示结束
-    guard: Arc<Mutex<bool>>,
+    /// 全局键盘钩子的订阅 -- 为 `None` 表示已结束
+    subscription: Mutex<Option<Subscription>>,
 
-    /// 按键事件监听注册表
-    key_evs: Arc<Mutex<HashMap<KeyEvRegister, ThreadsafeFunction<()>>>>,
+    /// 按键事件监听注册表 (同一组合键可注册多个回调)
+    key_evs: Arc<Mutex<HashMap<KeyEvRegister, Vec<Listener<()>>>>>,
 
     /// 监听全部事件的回调函数
-    global_key_cb: Arc<Mutex<Option<ThreadsafeFunction<KeyEv>>>>,
+    global_key_cb: Arc<Mutex<Vec<Listener<KeyEv>>>>,
+
+    /// 按键序列监听
+    sequences: Arc<Mutex<Vec<SequenceListener>>>,
+
+    /// 双击 / 长按监听
+    gestures: Arc<Mutex<Vec<GestureListener>>>,
+
+    /// 本实例拦截的组合键 (结束监听时解除)
+    grabs: Mutex<Vec<GrabTarget>>,
+
+    /// 下一个监听句柄
+    next_id: AtomicU32,
+
+    /// 监听失败时的回调函数
+    error_cb: Arc<Mutex<Option<ThreadsafeFunction<String>>>>,
+
+    /// 监听失败的原因 (在注册失败回调前失败时暂存, 注册时立即回调)
+    failure: Arc<Mutex<Option<String>>>,
 }
 
 #[napi]
@@ -62,123 +138,164 @@ impl Observer {
         Ok(())
     }
 
-    /// 初始化 -- 子线程中监听
-    fn setup(&self) {
-        // 终止信号
-        let signal = self.guard.clone();
+    /// 初始化 -- 子线程中分发全局键盘钩子的按键事件
+    fn setup(&self, signal: Receiver<Signal>) {
         // 全部按键事件监听回调
-        let keydown_cb_all = self.global_key_cb.clone();
-        let keyup_cb_all = self.global_key_cb.clone();
+        let cb_all = self.global_key_cb.clone();
         // 特定按键事件监听回调
-        let keydown_cb_spec = self.key_evs.clone();
-        let keyup_cb_spec = self.key_evs.clone();
+        let cb_spec = self.key_evs.clone();
+        // 按键序列监听回调
+        let cb_seq = self.sequences.clone();
+        // 双击 / 长按监听回调
+        let cb_gesture = self.gestures.clone();
+        // 监听失败回调
+        let cb_error = self.error_cb.clone();
+        let failure = self.failure.clone();
 
         thread::spawn(move || {
-            // 状态监听
-            let listener = DeviceState::new();
-
-            // 按键按下监听
-            let _guard = listener.on_key_down(move |keycode| {
-                // 状态扫描
-                let scanner = DeviceState::new();
+            // 收到终止信号或订阅被释放时结束 (无需轮询; 有待触发的长按时等待至其触发时间)
+            loop {
+                let deadline = next_deadline(&cb_gesture.lock().unwrap());
+                let msg = match deadline {
+                    Some(deadline) => match signal.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                        Ok(v) => v,
+                        Err(RecvTimeoutError::Timeout) => {
+                            poll_long_presses(&mut cb_gesture.lock().unwrap(), Instant::now());
+                            continue;
+                        }
+                        Err(RecvTimeoutError::Disconnected) => break
+                    },
+                    None => match signal.recv() {
+                        Ok(v) => v,
+                        Err(_) => break
+                    }
+                };
 
-                // 对全部事件的监听
-                match keydown_cb_all.lock().unwrap().deref() {
-                    Some(cb) => {
-                        cb.call(Ok(KeyEv {
-                            key: match DQMapper::encode_key(keycode) {
-                                Some(v) => v,
-                                None => String::from("Unknown")
-                            },
-                            extra: None,
-                            down: Some(true),
-                        }), ThreadsafeFunctionCallMode::NonBlocking);
+                let input = match msg {
+                    Signal::Key(v) => v,
+                    Signal::Failed(reason) => {
+                        match cb_error.lock().unwrap().deref() {
+                            Some(cb) => {
+                                cb.call(Ok(reason), ThreadsafeFunctionCallMode::NonBlocking);
+                            }
+                            None => *failure.lock().unwrap() = Some(reason)
+                        };
+                        continue;
                     }
-                    None => {}
+                    Signal::Stop => break
                 };
 
-                // 对注册事件的监听
-                let key = DQMapper::encode_key(keycode).unwrap();
-                let (mut ctrl, mut alt, mut shift, mut meta) = (false, false, false, false);
-                for _key in scanner.get_keys() {
-                    match _key {
-                        DQKey::LControl | DQKey::RControl => ctrl = true,
-                        DQKey::LAlt | DQKey::RAlt => alt = true,
-                        DQKey::LShift | DQKey::RShift => shift = true,
-                        DQKey::Meta => meta = true,
-                        _ => {}
+                // 未映射的按键以 `Unknown` + 原始键码交付, 不中断监听
+                let key = RdevMapper::encode_key(&input.key);
+                let (m, l) = (input.modifiers, input.locks);
+
+                // 对双击 / 长按的监听
+                let mut gestures = cb_gesture.lock().unwrap();
+                for listener in gestures.iter_mut() {
+                    let matched = match &mut listener.gesture {
+                        Gesture::DoubleTap(tap) => tap.feed(key.as_deref(), input.down, input.repeat, input.time),
+                        Gesture::LongPress(press) => {
+                            press.feed(key.as_deref(), input.down, input.repeat, input.time);
+                            false
+                        }
                     };
-                }
-                let register_ev = KeyEvRegister::new(key, ctrl, alt, shift, meta, true);
-
-                match keydown_cb_spec.lock().unwrap().get(&register_ev) {
-                    Some(cb) => {
-                        cb.call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
+                    if matched {
+                        listener.tsfn.call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
                     }
-                    None => {}
                 }
-            });
-
-            // 按键释放监听
-            let _guard = listener.on_key_up(move |keycode| {
-                // 状态扫描
-                let scanner = DeviceState::new();
+                poll_long_presses(&mut gestures, Instant::now());
+                drop(gestures);
 
                 // 对全部事件的监听
-                match keyup_cb_all.lock().unwrap().deref() {
-                    Some(cb) => {
-                        cb.call(Ok(KeyEv {
-                            key: match DQMapper::encode_key(keycode) {
-                                Some(v) => v,
-                                None => String::from("Unknown")
-                            },
-                            extra: None,
-                            down: Some(false),
-                        }), ThreadsafeFunctionCallMode::NonBlocking);
-                    }
-                    None => {}
-                };
+                let mut all = cb_all.lock().unwrap();
+                if !all.is_empty() {
+                    fire(&mut all, KeyEv {
+                        key: match &key {
+                            Some(v) => v.clone(),
+                            None => String::from("Unknown")
+                        },
+                        extra: Some(ExtraKey {
+                            ctrl: Some(m.ctrl()),
+                            alt: Some(m.alt()),
+                            shift: Some(m.shift()),
+                            meta: Some(m.meta()),
+                        }),
+                        down: Some(input.down),
+                        state: Some(KeyState {
+                            ctrl_left: m.ctrl_left,
+                            ctrl_right: m.ctrl_right,
+                            alt_left: m.alt_left,
+                            alt_right: m.alt_right,
+                            shift_left: m.shift_left,
+                            shift_right: m.shift_right,
+                            meta_left: m.meta_left,
+                            meta_right: m.meta_right,
+                            caps_lock: l.caps,
+                            num_lock: l.num,
+                            scroll_lock: l.scroll,
+                        }),
+                        code: RdevMapper::raw_code(&input.key),
+                        is_repeat: Some(input.repeat),
+                    });
+                }
+                drop(all);
 
-                // 对注册事件的监听
-                let key = DQMapper::encode_key(keycode).unwrap();
-                let (mut ctrl, mut alt, mut shift, mut meta) = (false, false, false, false);
-                for _key in scanner.get_keys() {
-                    match _key {
-                        DQKey::LControl | DQKey::RControl => ctrl = true,
-                        DQKey::LAlt | DQKey::RAlt => alt = true,
-                        DQKey::LShift | DQKey::RShift => shift = true,
-                        DQKey::Meta => meta = true,
-                        _ => {}
-                    };
+                // 长按产生的重复按下仅交付给对全部事件的监听
+                if input.repeat {
+                    continue;
                 }
-                let register_ev = KeyEvRegister::new(key, ctrl, alt, shift, meta, false);
 
-                match keyup_cb_spec.lock().unwrap().get(&register_ev) {
-                    Some(cb) => {
-                        cb.call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
+                let register_ev = match &key {
+                    Some(v) => Some(KeyEvRegister::new(v.clone(), m.ctrl(), m.alt(), m.shift(), m.meta(), input.down)),
+                    None => None
+                };
+
+                // 对按键序列的监听 (仅按下事件; 修饰键本身不打断序列)
+                let is_modifier_key = match &key {
+                    Some(v) => is_modifier(v),
+                    None => false
+                };
+                if input.down && !is_modifier_key {
+                    for seq in cb_seq.lock().unwrap().iter_mut() {
+                        if seq.state.feed(register_ev.as_ref(), input.time) {
+                            seq.tsfn.call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
+                        }
                     }
-                    None => {}
                 }
-            });
 
-            // 监听结束判断
-            while *signal.lock().unwrap() {
-                thread::sleep(Duration::from_millis(LOOP_GAP));
-            };
+                // 对注册事件的监听 (修饰键状态由钩子增量维护)
+                let register_ev = match register_ev {
+                    Some(v) => v,
+                    None => continue
+                };
 
-            println!("listen finished.")
+                let mut evs = cb_spec.lock().unwrap();
+                if let Some(listeners) = evs.get_mut(&register_ev) {
+                    fire(listeners, ());
+                    if listeners.is_empty() {
+                        evs.remove(&register_ev);
+                    }
+                }
+            }
         });
     }
 
     #[napi(constructor)]
     pub fn new() -> Self {
+        let (subscription, signal) = Subscription::new();
         let instance = Observer {
-            guard: Arc::new(Mutex::new(true)),
+            subscription: Mutex::new(Some(subscription)),
             key_evs: Arc::new(Mutex::new(HashMap::new())),
-            global_key_cb: Arc::new(Mutex::new(None)),
+            global_key_cb: Arc::new(Mutex::new(vec![])),
+            sequences: Arc::new(Mutex::new(vec![])),
+            gestures: Arc::new(Mutex::new(vec![])),
+            grabs: Mutex::new(vec![]),
+            next_id: AtomicU32::new(1),
+            error_cb: Arc::new(Mutex::new(None)),
+            failure: Arc::new(Mutex::new(None)),
         };
 
-        instance.setup();
+        instance.setup(signal);
 
         instance
     }
@@ -196,25 +313,41 @@ impl Observer {
         Ok(_key_evs)
     }
 
-    /// 注册/更新按键监听事件 (支持组合键)
-    #[napi]
-    pub fn on_key(&mut self, keys: KeyEv, #[napi(ts_arg_type = "(err: null | Error) => void")] callback: JsFunction) -> napi::Result<()> {
-        if check_key(keys.key.clone()).unwrap() {
-            let mut evs = self.key_evs.lock().unwrap();
+    /// 注册监听回调, 返回句柄
+    pub(crate) fn add_listener(&self, register_ev: KeyEvRegister, tsfn: ThreadsafeFunction<()>, once: bool) -> u32 {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
 
-            let register_ev = KeyEvRegister::from_key_ev(keys);
-            evs.insert(register_ev, callback.create_threadsafe_function(0, |ctx| {
+        let mut evs = self.key_evs.lock().unwrap();
+        evs.entry(register_ev).or_insert_with(Vec::new).push(Listener { id, once, tsfn });
+        id
+    }
+
+    fn add_key(&self, keys: KeyEv, callback: JsFunction, once: bool) -> napi::Result<u32> {
+        if check_key(keys.key.clone()).unwrap() {
+            let tsfn = callback.create_threadsafe_function(0, |ctx| {
                 Ok(vec![ctx.value])
-            })?);
-            Ok(())
+            })?;
+            Ok(self.add_listener(KeyEvRegister::from_key_ev(keys), tsfn, once))
         } else {
             Err(Error::new(Status::InvalidArg, format!("Invalid Key!")))
         }
     }
 
-    /// 移除已注册的监听
+    /// 注册按键监听事件 (支持组合键; 同一组合键可注册多个回调, 按注册顺序触发), 返回用于 `off` 的句柄
+    #[napi]
+    pub fn on_key(&self, keys: KeyEv, #[napi(ts_arg_type = "(err: null | Error) => void")] callback: JsFunction) -> napi::Result<u32> {
+        self.add_key(keys, callback, false)
+    }
+
+    /// 注册仅触发一次的按键监听事件, 返回用于 `off` 的句柄
     #[napi]
-    pub fn off_key(&mut self, keys: KeyEv) -> napi::Result<()> {
+    pub fn once_key(&self, keys: KeyEv, #[napi(ts_arg_type = "(err: null | Error) => void")] callback: JsFunction) -> napi::Result<u32> {
+        self.add_key(keys, callback, true)
+    }
+
+    /// 移除该组合键上的全部监听
+    #[napi]
+    pub fn off_key(&self, keys: KeyEv) -> napi::Result<()> {
         if check_key(keys.key.clone()).unwrap() {
             let mut evs = self.key_evs.lock().unwrap();
             let register_ev = KeyEvRegister::from_key_ev(keys);
@@ -225,35 +358,220 @@ impl Observer {
         }
     }
 
-    /// 注册/更新对全部按键的监听事件
+    fn add_key_all(&self, callback: JsFunction, once: bool) -> napi::Result<u32> {
+        let tsfn = callback.create_threadsafe_function(0, |ctx| {
+            Ok(vec![ctx.value])
+        })?;
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+
+        self.global_key_cb.lock().unwrap().push(Listener { id, once, tsfn });
+
+        Ok(id)
+    }
+
+    /// 注册对全部按键的监听事件, 返回用于 `off` 的句柄
+    #[napi]
+    pub fn on_key_all(&self, #[napi(ts_arg_type = "(err: null | Error, key_ev: KeyEv) => void")] callback: JsFunction) -> napi::Result<u32> {
+        self.add_key_all(callback, false)
+    }
+
+    /// 注册仅触发一次 (下一个按键事件) 的全部按键监听事件, 返回用于 `off` 的句柄
+    #[napi]
+    pub fn once_key_all(&self, #[napi(ts_arg_type = "(err: null | Error, key_ev: KeyEv) => void")] callback: JsFunction) -> napi::Result<u32> {
+        self.add_key_all(callback, true)
+    }
+
+    /// 移除对全部按键的全部监听
+    #[napi]
+    pub fn off_key_all(&self) -> napi::Result<()> {
+        self.global_key_cb.lock().unwrap().clear();
+
+        Ok(())
+    }
+
+    /// 注册按键序列监听 (如 `g g` / `ctrl + K, ctrl + S`, 每一步均在按下时匹配), 返回用于 `off` 的句柄
     #[napi]
-    pub fn on_key_all(&self, #[napi(ts_arg_type = "(err: null | Error, key_ev: { key: KeyEv['key'], down: boolean }) => void")] callback: JsFunction) -> napi::Result<()> {
+    pub fn on_sequence(
+        &self,
+        steps: Vec<KeyCombination>,
+        options: Option<SequenceOptions>,
+        #[napi(ts_arg_type = "(err: null | Error) => void")] callback: JsFunction,
+    ) -> napi::Result<u32> {
+        if steps.is_empty() {
+            return Err(Error::new(Status::InvalidArg, String::from("The sequence must not be empty!")));
+        }
+        for step in &steps {
+            if !check_key(step.key.clone()).unwrap() {
+                return Err(Error::new(Status::InvalidArg, String::from("Invalid Key!")));
+            }
+        }
+
+        let timeout = match options.and_then(|v| v.timeout_ms) {
+            Some(v) => v,
+            None => 1000
+        };
         let tsfn = callback.create_threadsafe_function(0, |ctx| {
             Ok(vec![ctx.value])
         })?;
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+
+        let registers = steps.iter().map(|step| KeyEvRegister::from_combination(step.clone())).collect();
+        let state = Sequence::new(registers, Duration::from_millis(timeout as u64));
+        self.sequences.lock().unwrap().push(SequenceListener { id, steps, state, tsfn });
 
-        *self.global_key_cb.lock().unwrap() = Some(tsfn);
+        Ok(id)
+    }
+
+    fn add_gesture(&self, key: &str, gesture: Gesture, callback: JsFunction) -> napi::Result<u32> {
+        if !check_key(key.to_string()).unwrap() {
+            return Err(Error::new(Status::InvalidArg, String::from("Invalid Key!")));
+        }
+
+        let tsfn = callback.create_threadsafe_function(0, |ctx| {
+            Ok(vec![ctx.value])
+        })?;
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.gestures.lock().unwrap().push(GestureListener { id, gesture, tsfn });
+
+        Ok(id)
+    }
+
+    /// 注册双击监听 (两次按下的间隔不超过 `max_gap_ms`, 期间未按下其他键; `Shift` / `Control` / `Alt` 不区分左右), 返回用于 `off` 的句柄
+    #[napi]
+    pub fn on_double_tap(&self, key: String, max_gap_ms: u32, #[napi(ts_arg_type = "(err: null | Error) => void")] callback: JsFunction) -> napi::Result<u32> {
+        let gesture = Gesture::DoubleTap(DoubleTap::new(key.clone(), Duration::from_millis(max_gap_ms as u64)));
+        self.add_gesture(&key, gesture, callback)
+    }
+
+    /// 注册长按监听 (按下并保持 `hold_ms` 后触发, 每次按下最多触发一次; `Shift` / `Control` / `Alt` 不区分左右), 返回用于 `off` 的句柄
+    #[napi]
+    pub fn on_long_press(&self, key: String, hold_ms: u32, #[napi(ts_arg_type = "(err: null | Error) => void")] callback: JsFunction) -> napi::Result<u32> {
+        let gesture = Gesture::LongPress(LongPress::new(key.clone(), Duration::from_millis(hold_ms as u64)));
+        self.add_gesture(&key, gesture, callback)
+    }
+
+    /// 进行中的按键序列 (已按下第一步且未超时)
+    #[napi(getter)]
+    pub fn pending_sequences(&self) -> napi::Result<Vec<PendingSequence>> {
+        let now = Instant::now();
+        let mut pending = vec![];
+
+        for seq in self.sequences.lock().unwrap().iter() {
+            if let Some((progress, remaining)) = seq.state.pending(now) {
+                pending.push(PendingSequence {
+                    id: seq.id,
+                    matched: seq.steps[..progress].to_vec(),
+                    next: seq.steps[progress].clone(),
+                    remaining_ms: remaining.as_millis() as u32,
+                });
+            }
+        }
+
+        Ok(pending)
+    }
+
+    /// 按句柄移除单个监听 (返回值表示该句柄是否仍处于注册状态)
+    #[napi]
+    pub fn off(&self, id: u32) -> napi::Result<bool> {
+        let mut all = self.global_key_cb.lock().unwrap();
+        let count = all.len();
+        all.retain(|listener| listener.id != id);
+        if all.len() != count {
+            return Ok(true);
+        }
+        drop(all);
+
+        let mut sequences = self.sequences.lock().unwrap();
+        let count = sequences.len();
+        sequences.retain(|seq| seq.id != id);
+        if sequences.len() != count {
+            return Ok(true);
+        }
+        drop(sequences);
+
+        let mut gestures = self.gestures.lock().unwrap();
+        let count = gestures.len();
+        gestures.retain(|listener| listener.id != id);
+        if gestures.len() != count {
+            return Ok(true);
+        }
+        drop(gestures);
+
+        let mut evs = self.key_evs.lock().unwrap();
+        let mut removed = false;
+        for listeners in evs.values_mut() {
+            let count = listeners.len();
+            listeners.retain(|listener| listener.id != id);
+            removed = removed || listeners.len() != count;
+        }
+        evs.retain(|_, listeners| !listeners.is_empty());
+
+        Ok(removed)
+    }
+
+    /// 注册/更新监听失败的回调 (如 macOS 未授予辅助功能权限; 失败后不会再有按键事件)
+    #[napi]
+    pub fn on_error(&self, #[napi(ts_arg_type = "(err: null | Error, reason: string) => void")] callback: JsFunction) -> napi::Result<()> {
+        let tsfn: ThreadsafeFunction<String> = callback.create_threadsafe_function(0, |ctx| {
+            Ok(vec![ctx.value])
+        })?;
+
+        // 注册前已失败 -- 立即回调
+        if let Some(reason) = self.failure.lock().unwrap().take() {
+            tsfn.call(Ok(reason), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+        *self.error_cb.lock().unwrap() = Some(tsfn);
 
         Ok(())
     }
 
-    /// 移除对全部按键的监听事件
+    /// 移除监听失败的回调
     #[napi]
-    pub fn off_key_all(&self) -> napi::Result<()> {
-        *self.global_key_cb.lock().unwrap() = None;
+    pub fn off_error(&self) -> napi::Result<()> {
+        *self.error_cb.lock().unwrap() = None;
+
+        Ok(())
+    }
+
+    /// 拦截组合键: 按下时不再送达前台应用, 但仍会触发本进程内的监听 (`Shift` / `Control` / `Alt` 不区分左右)
+    /// - linux (X11): 组合键已被其他应用抢占时抛出异常
+    /// - macos: 需要辅助功能权限
+    #[napi]
+    pub fn grab(&self, keys: KeyCombination) -> napi::Result<()> {
+        let target = GrabTarget::of(&keys);
+        grab::check_target(&target)?;
+        grab::grab(target.clone())?;
+        self.grabs.lock().unwrap().push(target);
 
         Ok(())
     }
 
+    /// 解除本实例对组合键的拦截 (返回值表示此前是否已拦截)
+    #[napi]
+    pub fn ungrab(&self, keys: KeyCombination) -> napi::Result<bool> {
+        let target = GrabTarget::of(&keys);
+        let mut grabs = self.grabs.lock().unwrap();
+        match grabs.iter().position(|v| *v == target) {
+            Some(idx) => {
+                grabs.remove(idx);
+                Ok(grab::ungrab(&target))
+            }
+            None => Ok(false)
+        }
+    }
+
     /// 主动触发已注册的按键事件 (返回值表示该组合键是否已注册)
     #[napi]
     pub fn touch(&self, keys: KeyEv) -> napi::Result<bool> {
         if check_key(keys.key.clone()).unwrap() {
-            let evs = self.key_evs.lock().unwrap();
+            let mut evs = self.key_evs.lock().unwrap();
             let register_ev = KeyEvRegister::from_key_ev(keys);
-            match evs.get(&register_ev) {
-                Some(tsfn) => {
-                    tsfn.call(Ok(()), ThreadsafeFunctionCallMode::NonBlocking);
+            match evs.get_mut(&register_ev) {
+                Some(listeners) => {
+                    fire(listeners, ());
+                    if listeners.is_empty() {
+                        evs.remove(&register_ev);
+                    }
                     Ok(true)
                 }
                 None => Ok(false)
@@ -266,15 +584,23 @@ impl Observer {
     /// 结束监听 (必须调用! 否则会由于过度持有引用造成内存泄露)
     #[napi]
     pub fn dispose(&mut self) -> napi::Result<()> {
-        let mut guard = self.guard.lock().unwrap();
-
         // 已结束 -- 直接返回
-        if *guard {
-            // 发送停止信号
-            *guard = false;
+        if let Some(subscription) = self.subscription.lock().unwrap().take() {
+            // 取消订阅即通知子线程结束
+            drop(subscription);
 
             // 释放全部按键的回调函数
-            *self.global_key_cb.lock().unwrap() = None;
+            self.global_key_cb.lock().unwrap().clear();
+            // 释放按键序列的回调函数
+            self.sequences.lock().unwrap().clear();
+            // 释放双击 / 长按的回调函数
+            self.gestures.lock().unwrap().clear();
+            // 释放监听失败的回调函数
+            *self.error_cb.lock().unwrap() = None;
+            // 解除拦截
+            for target in self.grabs.lock().unwrap().drain(..) {
+                grab::ungrab(&target);
+            }
             // 释放注册表中的回调函数
             let mut evs = self.key_evs.lock().unwrap();
             evs.clear();
@@ -289,25 +615,24 @@ mod unit_test {
     use super::*;
     use std::thread;
     use std::time::Duration;
-    use device_query::DeviceQuery;
 
     #[test]
     fn test() {
-        let dq = DeviceState::new();
-        let _guard = dq.on_key_down(|ev| {
-            let scanner = DeviceState::new();
-            println!("keydown: {} | ctrl: {}", ev, scanner.get_keys().contains(&DQKey::LControl));
-        });
-        let _guard = dq.on_key_up(|ev| {
-            let scanner = DeviceState::new();
-            println!("keyup {} | ctrl: {}", ev, scanner.get_keys().contains(&DQKey::LControl));
+        let (subscription, signal) = Subscription::new();
+        let handle = thread::spawn(move || {
+            for msg in signal.iter() {
+                match msg {
+                    Signal::Key(input) => println!("{}: {:?} | ctrl: {}", if input.down { "keydown" } else { "keyup" }, input.key, input.modifiers.ctrl()),
+                    Signal::Failed(reason) => println!("{}", reason),
+                    Signal::Stop => break,
+                }
+            }
         });
 
         thread::sleep(Duration::from_secs(5));
 
-        drop(_guard);//aabasfazasdfgqwert123456123ac
-
-        thread::sleep(Duration::from_secs(5));
+        drop(subscription);
+        handle.join().unwrap();
     }
 
     #[test]
@@ -0,0 +1,184 @@
+//! 文字识别
+//!
+//! 需启用 `ocr` feature. 基于纯 Rust 实现的 [ocrs](https://github.com/robertknight/ocrs) 引擎, 在本地运行, 不联网;
+//! 模型文件 (`text-detection.rten` / `text-recognition.rten`) 由调用方下载后通过 [OcrOptions] 指定路径
+
+use napi::Error;
+use crate::capture::{CaptureErrorCode, CaptureResult, CaptureTask};
+use crate::utils::{OcrOptions, Rect, RecognizedWord};
+
+/// 默认语言
+const DEFAULT_LANG: &str = "eng";
+/// 支持的语言
+const SUPPORTED_LANGS: [&str; 1] = ["eng"];
+
+/// 校验语言
+fn check_lang(options: &OcrOptions) -> CaptureResult<()> {
+    let lang = match &options.lang {
+        Some(v) => v.as_str(),
+        None => DEFAULT_LANG
+    };
+
+    if SUPPORTED_LANGS.contains(&lang) {
+        Ok(())
+    } else {
+        Err(Error::new(CaptureErrorCode::InvalidArg, format!("Unsupported language: {} (supported: {})!", lang, SUPPORTED_LANGS.join(", "))))
+    }
+}
+
+#[cfg(feature = "ocr")]
+mod engine {
+    use std::sync::{Arc, Mutex};
+    use napi::Error;
+    use ocrs::{ImageSource, OcrEngine, OcrEngineParams};
+    use rten::Model;
+    use rten_tensor::prelude::*;
+    use rten_tensor::NdTensor;
+    use crate::capture::{capture_area, CaptureErrorCode, CaptureResult};
+    use crate::utils::{OcrOptions, Rect, RecognizedWord};
+
+    /// 已加载的引擎 (模型路径相同时复用, 避免每次识别都重新加载模型)
+    static ENGINE: Mutex<Option<(String, String, Arc<OcrEngine>)>> = Mutex::new(None);
+
+    fn ocr_failed(err: impl std::fmt::Display) -> Error<CaptureErrorCode> {
+        Error::new(CaptureErrorCode::RecognizeFailed, format!("Text recognition failed: {}!", err))
+    }
+
+    /// 模型路径 (必填)
+    fn model_path<'a>(path: &'a Option<String>, name: &str) -> CaptureResult<&'a str> {
+        match path {
+            Some(v) => Ok(v.as_str()),
+            None => Err(Error::new(CaptureErrorCode::InvalidArg, format!("The path of the {} model is required!", name)))
+        }
+    }
+
+    fn load(detection: &str, recognition: &str) -> CaptureResult<Arc<OcrEngine>> {
+        let mut cache = ENGINE.lock().unwrap();
+        if let Some((d, r, engine)) = cache.as_ref() {
+            if d == detection && r == recognition {
+                return Ok(engine.clone());
+            }
+        }
+
+        let model = |path: &str| match Model::load_file(path) {
+            Ok(v) => Ok(v),
+            Err(err) => Err(Error::new(CaptureErrorCode::ModelLoadFailed, format!("Failed to load the model {}: {}!", path, err)))
+        };
+        let engine = OcrEngine::new(OcrEngineParams {
+            detection_model: Some(model(detection)?),
+            recognition_model: Some(model(recognition)?),
+            ..Default::default()
+        }).map_err(ocr_failed)?;
+
+        let engine = Arc::new(engine);
+        *cache = Some((detection.to_string(), recognition.to_string(), engine.clone()));
+        Ok(engine)
+    }
+
+    /// `rect` (输入图像坐标) 内各像素属于文字的平均概率
+    fn confidence(probs: &NdTensor<f32, 2>, left: i32, top: i32, width: i32, height: i32) -> f64 {
+        let (rows, cols) = (probs.size(0) as i32, probs.size(1) as i32);
+        let (x0, x1) = (left.clamp(0, cols), (left + width).clamp(0, cols));
+        let (y0, y1) = (top.clamp(0, rows), (top + height).clamp(0, rows));
+
+        let mut sum = 0.0;
+        for y in y0..y1 {
+            for x in x0..x1 {
+                sum += probs[[y as usize, x as usize]] as f64;
+            }
+        }
+        let count = (x1 - x0) as i64 * (y1 - y0) as i64;
+        if count > 0 { (sum / count as f64).clamp(0.0, 1.0) } else { 0.0 }
+    }
+
+    pub fn recognize(region: &Rect, options: &OcrOptions) -> CaptureResult<Vec<RecognizedWord>> {
+        let engine = load(
+            model_path(&options.detection_model, "detection")?,
+            model_path(&options.recognition_model, "recognition")?,
+        )?;
+
+        let frame = capture_area(region)?;
+        let source = ImageSource::from_bytes(&frame.rgba, (frame.w, frame.h)).map_err(ocr_failed)?;
+        let input = engine.prepare_input(source).map_err(ocr_failed)?;
+        let words = engine.detect_words(&input).map_err(ocr_failed)?;
+        // ocrs 不输出识别的置信度, 以检测阶段的文字概率代替
+        let probs = engine.detect_text_pixels(&input).map_err(ocr_failed)?;
+        let lines = engine.find_text_lines(&input, &words);
+        let texts = engine.recognize_text(&input, &lines).map_err(ocr_failed)?;
+
+        // 截图为物理像素, 需换算回虚拟桌面坐标
+        let ratio_x = region.w as f64 / frame.w.max(1) as f64;
+        let ratio_y = region.h as f64 / frame.h.max(1) as f64;
+
+        let mut result = vec![];
+        for (idx, line) in texts.iter().enumerate() {
+            let line = match line {
+                Some(v) => v,
+                None => continue
+            };
+
+            for word in line.words() {
+                let text = word.to_string();
+                if text.trim().is_empty() {
+                    continue;
+                }
+
+                let rect = word.bounding_rect();
+                result.push(RecognizedWord {
+                    text,
+                    x: region.x + (rect.left() as f64 * ratio_x).floor() as i32,
+                    y: region.y + (rect.top() as f64 * ratio_y).floor() as i32,
+                    w: (rect.width() as f64 * ratio_x).ceil() as u32,
+                    h: (rect.height() as f64 * ratio_y).ceil() as u32,
+                    confidence: confidence(&probs, rect.left(), rect.top(), rect.width(), rect.height()),
+                    line: idx as u32,
+                });
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+/// 识别 `region` 内的文字
+#[cfg(feature = "ocr")]
+pub fn recognize(region: &Rect, options: &OcrOptions) -> CaptureResult<Vec<RecognizedWord>> {
+    check_lang(options)?;
+    engine::recognize(region, options)
+}
+
+/// 识别 `region` 内的文字 (未启用 `ocr` feature)
+#[cfg(not(feature = "ocr"))]
+pub fn recognize(_region: &Rect, options: &OcrOptions) -> CaptureResult<Vec<RecognizedWord>> {
+    check_lang(options)?;
+    Err(Error::new(CaptureErrorCode::Unsupported, String::from("Text recognition is not enabled, rebuild with `--features ocr`!")))
+}
+
+/// 文字识别 (见 [crate::capture::Captor::recognize_text])
+pub struct RecognizeText {
+    pub region: Rect,
+    pub options: OcrOptions,
+}
+
+impl CaptureTask for RecognizeText {
+    type Output = Vec<RecognizedWord>;
+
+    fn run(&mut self) -> CaptureResult<Self::Output> {
+        recognize(&self.region, &self.options)
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    #[test]
+    fn lang() {
+        let mut options = OcrOptions { lang: None, detection_model: None, recognition_model: None };
+        assert!(check_lang(&options).is_ok());
+
+        options.lang = Some(String::from("chi_sim"));
+        assert_eq!(check_lang(&options).unwrap_err().status, CaptureErrorCode::InvalidArg);
+    }
+}
@@ -0,0 +1,529 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Seek, SeekFrom, Write},
+    path::Path,
+    sync::Mutex,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+use image::{Delay, RgbaImage};
+use image::codecs::gif::{GifEncoder, Repeat};
+use napi::Error;
+use napi::bindgen_prelude::AsyncTask;
+use crate::capture::{CaptureErrorCode, CaptureResult, CaptureTask, CodedTask};
+use crate::frame::Frame;
+use crate::stream::grab;
+use crate::utils::{EncodeOptions, RecordOptions, Recording, StreamOptions};
+
+/// 默认帧率
+const DEFAULT_FPS: u32 = 15;
+/// 最大帧率
+const MAX_FPS: u32 = 60;
+/// gif 的最大帧率 (帧间隔的精度为 10ms)
+const MAX_GIF_FPS: u32 = 50;
+/// mjpeg 默认质量
+const DEFAULT_QUALITY: u32 = 80;
+/// gif 调色板量化速度 (1 ~ 30, 越大越快, 画质越差)
+const GIF_SPEED: i32 = 10;
+
+/// AVI 文件中 `movi` 列表类型标识所在的位置 (索引中的偏移量以此为基准)
+const MOVI_OFFSET: u64 = 220;
+/// AVI 1.0 文件的大小上限 (超出后多数播放器无法读取, 文件头中的 32 位长度也会溢出)
+const MAX_AVI_SIZE: u64 = 1 << 30;
+
+fn write_failed(path: &str, err: impl std::fmt::Display) -> Error<CaptureErrorCode> {
+    Error::new(CaptureErrorCode::EncodeFailed, format!("Failed to write {}: {}!", path, err))
+}
+
+/// Motion-JPEG 编码的 AVI 写入器 (逐帧写入, 结束时回填文件头中的长度与帧数)
+pub struct AviWriter<W: Write + Seek> {
+    out: W,
+    /// 当前写入位置
+    pos: u64,
+    /// 各帧的 (偏移量, 长度)
+    index: Vec<(u32, u32)>,
+}
+
+impl<W: Write + Seek> AviWriter<W> {
+    pub fn new(mut out: W, w: u32, h: u32, fps: u32) -> io::Result<Self> {
+        let mut header: Vec<u8> = Vec::with_capacity(MOVI_OFFSET as usize + 4);
+        let fourcc = |header: &mut Vec<u8>, v: &[u8; 4]| header.extend_from_slice(v);
+        let dword = |header: &mut Vec<u8>, v: u32| header.extend_from_slice(&v.to_le_bytes());
+        let word = |header: &mut Vec<u8>, v: u16| header.extend_from_slice(&v.to_le_bytes());
+
+        fourcc(&mut header, b"RIFF");
+        dword(&mut header, 0);
+        fourcc(&mut header, b"AVI ");
+
+        fourcc(&mut header, b"LIST");
+        dword(&mut header, 192);
+        fourcc(&mut header, b"hdrl");
+
+        // 主文件头 (AVIMAINHEADER)
+        fourcc(&mut header, b"avih");
+        dword(&mut header, 56);
+        for v in [1_000_000 / fps, 0, 0, 0x10, 0, 0, 1, 0, w, h, 0, 0, 0, 0] {
+            dword(&mut header, v);
+        }
+
+        fourcc(&mut header, b"LIST");
+        dword(&mut header, 116);
+        fourcc(&mut header, b"strl");
+
+        // 视频流头 (AVISTREAMHEADER)
+        fourcc(&mut header, b"strh");
+        dword(&mut header, 56);
+        fourcc(&mut header, b"vids");
+        fourcc(&mut header, b"MJPG");
+        dword(&mut header, 0);
+        word(&mut header, 0);
+        word(&mut header, 0);
+        for v in [0, 1, fps, 0, 0, 0, 0, 0] {
+            dword(&mut header, v);
+        }
+        for v in [0, 0, w as u16, h as u16] {
+            word(&mut header, v);
+        }
+
+        // 视频格式 (BITMAPINFOHEADER)
+        fourcc(&mut header, b"strf");
+        dword(&mut header, 40);
+        dword(&mut header, 40);
+        dword(&mut header, w);
+        dword(&mut header, h);
+        word(&mut header, 1);
+        word(&mut header, 24);
+        fourcc(&mut header, b"MJPG");
+        for v in [w * h * 3, 0, 0, 0, 0] {
+            dword(&mut header, v);
+        }
+
+        fourcc(&mut header, b"LIST");
+        dword(&mut header, 0);
+        fourcc(&mut header, b"movi");
+
+        out.write_all(&header)?;
+        Ok(AviWriter { out, pos: header.len() as u64, index: vec![] })
+    }
+
+    /// 再写入长度为 `len` 的一帧后, 文件 (含结束时写入的索引) 是否仍不超过 [MAX_AVI_SIZE]
+    pub fn fits(&self, len: usize) -> bool {
+        let padded = len as u64 + len as u64 % 2;
+        self.pos + 8 + padded + 8 + (self.index.len() as u64 + 1) * 16 <= MAX_AVI_SIZE
+    }
+
+    /// 写入一帧 (jpeg 数据; 写入后文件将超过 [MAX_AVI_SIZE] 时报错, 已写入的部分仍可 `finish`)
+    pub fn write_frame(&mut self, jpeg: &[u8]) -> io::Result<()> {
+        if !self.fits(jpeg.len()) {
+            return Err(io::Error::new(io::ErrorKind::Other, "the AVI file would exceed 1 GB"));
+        }
+
+        let len = jpeg.len() as u32;
+        self.index.push(((self.pos - MOVI_OFFSET) as u32, len));
+
+        self.out.write_all(b"00dc")?;
+        self.out.write_all(&len.to_le_bytes())?;
+        self.out.write_all(jpeg)?;
+        // 数据块按 2 字节对齐
+        if len % 2 == 1 {
+            self.out.write_all(&[0])?;
+        }
+
+        self.pos += 8 + len as u64 + (len % 2) as u64;
+        Ok(())
+    }
+
+    /// 已写入的帧数
+    pub fn frames(&self) -> u32 {
+        self.index.len() as u32
+    }
+
+    /// 写入索引并回填文件头
+    pub fn finish(mut self) -> io::Result<W> {
+        let movi_size = (self.pos - MOVI_OFFSET) as u32;
+        let frames = self.frames();
+
+        self.out.write_all(b"idx1")?;
+        self.out.write_all(&(frames * 16).to_le_bytes())?;
+        for (offset, len) in &self.index {
+            self.out.write_all(b"00dc")?;
+            // AVIIF_KEYFRAME
+            self.out.write_all(&0x10u32.to_le_bytes())?;
+            self.out.write_all(&offset.to_le_bytes())?;
+            self.out.write_all(&len.to_le_bytes())?;
+        }
+        self.pos += 8 + frames as u64 * 16;
+
+        for (at, v) in [(4, (self.pos - 8) as u32), (48, frames), (140, frames), (MOVI_OFFSET - 4, movi_size)] {
+            self.out.seek(SeekFrom::Start(at))?;
+            self.out.write_all(&v.to_le_bytes())?;
+        }
+        self.out.seek(SeekFrom::End(0))?;
+        self.out.flush()?;
+
+        Ok(self.out)
+    }
+}
+
+/// 编码
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Codec {
+    Mjpeg,
+    Gif,
+}
+
+impl Codec {
+    /// 由选项或文件扩展名确定编码
+    pub fn of(codec: &Option<String>, path: &str) -> CaptureResult<Self> {
+        match codec.as_deref() {
+            Some("mjpeg") => Ok(Codec::Mjpeg),
+            Some("gif") => Ok(Codec::Gif),
+            Some(other) => Err(Error::new(CaptureErrorCode::InvalidArg, format!("Unsupported codec: {} (supported: mjpeg, gif)!", other))),
+            None => match Path::new(path).extension().and_then(|v| v.to_str()) {
+                Some(ext) if ext.eq_ignore_ascii_case("gif") => Ok(Codec::Gif),
+                _ => Ok(Codec::Mjpeg)
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Codec::Mjpeg => "mjpeg",
+            Codec::Gif => "gif",
+        }
+    }
+}
+
+/// 视频文件写入器
+enum Writer {
+    Avi(AviWriter<BufWriter<File>>, u32),
+    Gif(GifEncoder<BufWriter<File>>),
+}
+
+impl Writer {
+    fn create(path: &str, codec: Codec, w: u32, h: u32, fps: u32, quality: u32) -> CaptureResult<Self> {
+        let file = match File::create(path) {
+            Ok(v) => BufWriter::new(v),
+            Err(err) => return Err(write_failed(path, err))
+        };
+
+        match codec {
+            Codec::Mjpeg => match AviWriter::new(file, w, h, fps) {
+                Ok(v) => Ok(Writer::Avi(v, quality)),
+                Err(err) => Err(write_failed(path, err))
+            },
+            Codec::Gif => {
+                let mut encoder = GifEncoder::new_with_speed(file, GIF_SPEED);
+                match encoder.set_repeat(Repeat::Infinite) {
+                    Ok(_) => Ok(Writer::Gif(encoder)),
+                    Err(err) => Err(write_failed(path, err))
+                }
+            }
+        }
+    }
+
+    /// 写入一帧, 持续第 `from` 至第 `to` 个帧间隔
+    ///
+    /// 返回实际写入到的帧间隔 -- 小于 `to` 表示文件已达到大小上限, 无法继续写入
+    fn push(&mut self, path: &str, frame: &Frame, from: u64, to: u64, interval: Duration) -> CaptureResult<u64> {
+        match self {
+            Writer::Avi(writer, quality) => {
+                let jpeg = frame.encode(&EncodeOptions { format: String::from("jpeg"), compression: None, quality: Some(*quality) })?;
+                // 帧率固定, 截图不及时时重复写入上一帧以保持时长
+                for tick in from..to {
+                    if !writer.fits(jpeg.bytes.len()) {
+                        return Ok(tick);
+                    }
+                    writer.write_frame(&jpeg.bytes).map_err(|err| write_failed(path, err))?;
+                }
+                Ok(to)
+            }
+            Writer::Gif(encoder) => {
+                let image = match RgbaImage::from_raw(frame.w, frame.h, frame.rgba.clone()) {
+                    Some(v) => v,
+                    None => return Err(Error::new(CaptureErrorCode::EncodeFailed, String::from("Invalid frame size!")))
+                };
+                // 由起止时间计算, 避免逐帧舍入的误差累积
+                let delay = Delay::from_numer_denom_ms(((centis(to, interval) - centis(from, interval)) * 10) as u32, 1);
+                match encoder.encode_frame(image::Frame::from_parts(image, 0, 0, delay)) {
+                    Ok(_) => Ok(to),
+                    Err(err) => Err(write_failed(path, err))
+                }
+            }
+        }
+    }
+
+    fn finish(self, path: &str) -> CaptureResult<()> {
+        match self {
+            Writer::Avi(writer, _) => match writer.finish().and_then(|mut out| out.flush()) {
+                Ok(_) => Ok(()),
+                Err(err) => Err(write_failed(path, err))
+            },
+            // 释放编码器时写入文件尾
+            Writer::Gif(encoder) => {
+                drop(encoder);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 第 `tick` 个帧间隔开始的时间 (单位为 10ms, 即 gif 帧间隔的精度)
+fn centis(tick: u64, interval: Duration) -> u64 {
+    (tick as f64 * interval.as_secs_f64() * 100.0).round() as u64
+}
+
+/// 录制中的状态
+struct Session {
+    options: StreamOptions,
+    path: String,
+    codec: Codec,
+    writer: Writer,
+    interval: Duration,
+    w: u32,
+    h: u32,
+}
+
+impl Session {
+    /// 画面尺寸变化 (如屏幕分辨率改变) 时缩放到初始尺寸
+    fn fit(&self, frame: Frame) -> Frame {
+        if frame.w == self.w && frame.h == self.h {
+            return frame;
+        }
+        let mut fitted = Frame::new(self.w, self.h);
+        fitted.blit_scaled(&frame, 0, 0, self.w, self.h);
+        fitted
+    }
+
+    /// 按帧率截图直至收到终止信号或文件达到大小上限, 每帧的持续时间在下一帧截取后确定
+    fn run(mut self, first: Frame, signal: Receiver<()>) -> CaptureResult<Recording> {
+        let started = Instant::now();
+        let mut pending = (first, 0u64);
+        let mut frames = 1u32;
+        let mut next = started + self.interval;
+        let mut failure = None;
+        // 文件达到大小上限时实际写入到的帧间隔
+        let mut full = None;
+
+        loop {
+            // 收到终止信号或发送端被释放时结束
+            match signal.recv_timeout(next.saturating_duration_since(Instant::now())) {
+                Err(RecvTimeoutError::Timeout) => {}
+                _ => break
+            }
+            next = (next + self.interval).max(Instant::now());
+
+            let frame = match grab(&self.options) {
+                Ok(v) => self.fit(v),
+                Err(err) => {
+                    failure = Some(err);
+                    break;
+                }
+            };
+
+            let tick = ((started.elapsed().as_secs_f64() / self.interval.as_secs_f64()).round() as u64).max(pending.1 + 1);
+            match self.writer.push(&self.path, &pending.0, pending.1, tick, self.interval) {
+                Ok(written) if written < tick => {
+                    full = Some(written);
+                    break;
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    failure = Some(err);
+                    break;
+                }
+            }
+            pending = (frame, tick);
+            frames += 1;
+        }
+
+        // 出错时也写完已录制的部分, 保证文件可播放 (此时最后一帧可能无法写入)
+        let mut last = Ok(());
+        if full.is_none() {
+            match self.writer.push(&self.path, &pending.0, pending.1, pending.1 + 1, self.interval) {
+                Ok(written) if written == pending.1 => full = Some(written),
+                Ok(_) => {}
+                Err(err) => last = Err(err),
+            }
+        }
+        self.writer.finish(&self.path)?;
+        if let Some(err) = failure {
+            return Err(err);
+        }
+        last?;
+
+        let end = match full {
+            // 待写入的帧一次也未能写入
+            Some(written) if written == pending.1 => {
+                frames -= 1;
+                written
+            }
+            Some(written) => written,
+            None => pending.1 + 1
+        };
+
+        Ok(Recording {
+            path: self.path,
+            codec: self.codec.name().to_string(),
+            w: self.w,
+            h: self.h,
+            frames,
+            duration: (end as f64 * self.interval.as_secs_f64() * 1000.0).round() as u32,
+            truncated: full.is_some(),
+        })
+    }
+}
+
+/// 录屏 (由 [crate::capture::Captor::record] 创建)
+#[napi]
+pub struct ScreenRecorder {
+    /// 终止信号的发送端 -- 为 `None` 表示已结束 (释放发送端即通知子线程结束)
+    stopper: Mutex<Option<Sender<()>>>,
+    /// 录制线程 -- 结束后得到录制完成的文件
+    worker: Mutex<Option<JoinHandle<CaptureResult<Recording>>>>,
+}
+
+impl ScreenRecorder {
+    /// 截取第一帧并创建文件后开始录制 (参数或路径有误时立即报错)
+    pub fn start(options: RecordOptions) -> CaptureResult<Self> {
+        let codec = Codec::of(&options.codec, &options.path)?;
+        let max_fps = if codec == Codec::Gif { MAX_GIF_FPS } else { MAX_FPS };
+        let fps = match options.fps {
+            Some(v) => v.clamp(1, max_fps),
+            None => DEFAULT_FPS
+        };
+        let quality = match options.quality {
+            Some(v) => v.clamp(1, 100),
+            None => DEFAULT_QUALITY
+        };
+        let stream_options = StreamOptions {
+            screen_id: options.screen_id,
+            fps: Some(fps),
+            region: options.region,
+            format: None,
+            cursor: options.cursor,
+        };
+
+        let first = grab(&stream_options)?;
+        let writer = Writer::create(&options.path, codec, first.w, first.h, fps, quality)?;
+        let session = Session {
+            options: stream_options,
+            path: options.path,
+            codec,
+            writer,
+            interval: Duration::from_secs_f64(1.0 / fps as f64),
+            w: first.w,
+            h: first.h,
+        };
+
+        let (stopper, signal) = mpsc::channel::<()>();
+        let worker = thread::spawn(move || session.run(first, signal));
+
+        Ok(ScreenRecorder {
+            stopper: Mutex::new(Some(stopper)),
+            worker: Mutex::new(Some(worker)),
+        })
+    }
+}
+
+#[napi]
+impl ScreenRecorder {
+    /// 是否正在录制 (录制过程中出错或文件达到大小上限时自动结束)
+    #[napi(getter)]
+    pub fn running(&self) -> napi::Result<bool> {
+        let finished = match self.worker.lock().unwrap().as_ref() {
+            Some(worker) => worker.is_finished(),
+            None => true
+        };
+        Ok(self.stopper.lock().unwrap().is_some() && !finished)
+    }
+
+    /// 结束录制, 写完文件后 resolve (必须调用! 否则会一直录制; 已因文件达到大小上限而结束时, 同样 resolve 录制完成的文件)
+    #[napi(ts_return_type = "Promise<Recording>")]
+    pub fn stop(&self) -> AsyncTask<CodedTask<FinishRecording>> {
+        // 释放发送端即通知子线程结束
+        self.stopper.lock().unwrap().take();
+        CodedTask::new(FinishRecording { worker: self.worker.lock().unwrap().take() })
+    }
+}
+
+/// 等待录制线程写完文件 (见 [ScreenRecorder::stop])
+pub struct FinishRecording {
+    worker: Option<JoinHandle<CaptureResult<Recording>>>,
+}
+
+impl CaptureTask for FinishRecording {
+    type Output = Recording;
+
+    fn run(&mut self) -> CaptureResult<Self::Output> {
+        match self.worker.take() {
+            Some(worker) => match worker.join() {
+                Ok(result) => result,
+                Err(_) => Err(Error::new(CaptureErrorCode::CaptureFailed, String::from("The recording thread panicked!")))
+            },
+            None => Err(Error::new(CaptureErrorCode::InvalidArg, String::from("The recording has already been stopped!")))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use std::io::Cursor;
+    use super::*;
+
+    fn dword(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn avi() {
+        let mut writer = AviWriter::new(Cursor::new(vec![]), 4, 2, 10).unwrap();
+        writer.write_frame(&[0xFF, 0xD8, 0xFF]).unwrap();
+        writer.write_frame(&[0xFF, 0xD8, 0xFF, 0xD9]).unwrap();
+        let bytes = writer.finish().unwrap().into_inner();
+
+        assert_eq!(&bytes[0..4], b"RIFF");
+        assert_eq!(dword(&bytes, 4) as usize, bytes.len() - 8);
+        assert_eq!(&bytes[MOVI_OFFSET as usize..MOVI_OFFSET as usize + 4], b"movi");
+        // 帧数
+        assert_eq!(dword(&bytes, 48), 2);
+        assert_eq!(dword(&bytes, 140), 2);
+        // movi 列表: 类型标识 + 两帧 (第一帧补齐 1 字节)
+        assert_eq!(dword(&bytes, MOVI_OFFSET as usize - 4), 4 + (8 + 4) + (8 + 4));
+
+        let idx1 = MOVI_OFFSET as usize + 4 + 24;
+        assert_eq!(&bytes[idx1..idx1 + 4], b"idx1");
+        assert_eq!(dword(&bytes, idx1 + 4), 32);
+        // 第二帧的偏移量
+        assert_eq!(dword(&bytes, idx1 + 8 + 16 + 8), 4 + 12);
+    }
+
+    #[test]
+    fn avi_limit() {
+        let mut writer = AviWriter::new(Cursor::new(vec![]), 4, 2, 10).unwrap();
+        writer.pos = MAX_AVI_SIZE - 100;
+        assert!(writer.fits(60));
+        assert!(writer.write_frame(&[0; 60]).is_ok());
+        assert!(!writer.fits(60));
+        assert!(writer.write_frame(&[0; 60]).is_err());
+        assert_eq!(writer.frames(), 1);
+    }
+
+    #[test]
+    fn gif_delay() {
+        // 15 fps 的帧间隔为 66.7ms, 逐帧舍入会使每秒少 10ms
+        let interval = Duration::from_secs_f64(1.0 / 15.0);
+        let total: u64 = (0..15).map(|tick| centis(tick + 1, interval) - centis(tick, interval)).sum();
+        assert_eq!(total, 100);
+    }
+
+    #[test]
+    fn codec() {
+        assert_eq!(Codec::of(&None, "a.GIF").unwrap(), Codec::Gif);
+        assert_eq!(Codec::of(&None, "a.avi").unwrap(), Codec::Mjpeg);
+        assert_eq!(Codec::of(&Some(String::from("gif")), "a.avi").unwrap(), Codec::Gif);
+        assert!(Codec::of(&Some(String::from("h264")), "a.mp4").is_err());
+    }
+}
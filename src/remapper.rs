@@ -0,0 +1,762 @@
+//! 按键重映射
+//!
+//! 拦截规则中的源按键 (见 [crate::grab]), 经与 `Controller` 相同的后端 (enigo) 输出其他按键:
+//! - 普通映射: 源按键按下 / 重复 / 释放时, 输出的按键随之按下 / 重复 / 释放
+//! - 双重功能键: 点击与按住执行不同的动作; 判定前 (及判定后拦截结束前) 按下的其他按键被暂时拦截, 判定后重放
+//! - 层: 激活的层中的规则优先于基础层 (仅拦截基础层及已激活的层中的源按键)
+//!
+//! 指定了辅助键的源按键, 其辅助键在输出时仍处于按下状态; 被拦截的锁定键 (如 CapsLock) 不切换锁定状态
+
+use std::{
+    sync::{Arc, Mutex},
+    sync::mpsc::{Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+use enigo::{Enigo, KeyboardControllable};
+use napi::{Error, Status};
+use crate::gesture::matches_key;
+use crate::grab::{self, GrabTarget};
+use crate::hook::{Modifiers, Signal, Subscription};
+use crate::mapper::{EnigoMapper, RdevMapper};
+use crate::utils::{KeyCombination, RemapAction, RemapRule, RemapperOptions};
+
+/// 默认的点击 / 按住判定时长 (毫秒)
+const DEFAULT_TAPPING_TERM: u32 = 200;
+
+/// 已解析的动作
+#[derive(Debug, Clone, PartialEq)]
+enum Action {
+    Keys(KeyCombination),
+    Layer(String),
+    ToggleLayer(String),
+}
+
+impl Action {
+    fn parse(action: &RemapAction) -> Result<Self, String> {
+        match (&action.keys, &action.layer, &action.toggle_layer) {
+            (Some(keys), None, None) => match EnigoMapper::decode_key(keys.key.clone()) {
+                Some(_) => Ok(Action::Keys(keys.clone())),
+                None => Err(format!("invalid output key {}", keys.key))
+            },
+            (None, Some(layer), None) => Ok(Action::Layer(layer.clone())),
+            (None, None, Some(layer)) => Ok(Action::ToggleLayer(layer.clone())),
+            _ => Err(String::from("exactly one of keys / layer / toggleLayer is required"))
+        }
+    }
+
+    /// 输出的按键
+    fn output(&self) -> Option<&str> {
+        match self {
+            Action::Keys(keys) => Some(&keys.key),
+            _ => None
+        }
+    }
+}
+
+/// 需要执行的输出
+#[derive(Debug, Clone, PartialEq)]
+enum Effect {
+    /// 结束拦截引起的键盘独占 (见 [grab::release])
+    Release,
+    Press(String),
+    Lift(String),
+    /// 放行接下来模拟的源按键本身 (双重功能键点击时输出自身, 见 [grab::pass])
+    Pass(String),
+}
+
+/// 已注册的规则
+struct Entry {
+    rule: RemapRule,
+    action: Action,
+    hold: Option<Action>,
+    target: GrabTarget,
+}
+
+impl Entry {
+    fn new(rule: RemapRule) -> Result<Self, String> {
+        let action = Action::parse(&rule.action)?;
+        let hold = match &rule.hold {
+            Some(v) => Some(Action::parse(v)?),
+            None => None
+        };
+        let target = match rule.from.extra {
+            Some(_) => GrabTarget::of(&rule.from),
+            None => GrabTarget::any(rule.from.key.clone())
+        };
+        let target = if hold.is_some() { target.holding() } else { target };
+
+        Ok(Entry { rule, action, hold, target })
+    }
+
+    fn outputs(&self) -> impl Iterator<Item = &str> {
+        self.action.output().into_iter().chain(self.hold.iter().filter_map(|v| v.output()))
+    }
+}
+
+/// 按下中的源按键的动作 (释放时撤销)
+#[derive(Debug)]
+enum Held {
+    /// 输出的按键, 及为其按下的辅助键
+    Keys(String, Vec<&'static str>),
+    Layer(String),
+    Nothing,
+}
+
+/// 尚未判定点击 / 按住的双重功能键
+struct Pending {
+    key: String,
+    action: Action,
+    hold: Action,
+    deadline: Instant,
+}
+
+/// 重映射状态机 (不直接输出按键, 便于测试)
+struct Engine {
+    entries: Vec<Entry>,
+    tapping_term: Duration,
+    /// 已激活的层 (后激活的在后)
+    layers: Vec<String>,
+    pending: Option<Pending>,
+    /// 按下中的源按键
+    held: Vec<(String, Held)>,
+    /// 是否重放被暂时拦截的按键 (按下其他键判定为按住后, 直至拦截结束)
+    replaying: bool,
+    /// 已模拟的源按键本身 (其按下及释放经过钩子时忽略)
+    echoes: Vec<String>,
+}
+
+impl Engine {
+    fn new(tapping_term: Duration) -> Self {
+        Engine { entries: vec![], tapping_term, layers: vec![], pending: None, held: vec![], replaying: false, echoes: vec![] }
+    }
+
+    /// 添加规则 (替换同一层中相同的源按键)
+    fn add(&mut self, entry: Entry) -> Result<(), String> {
+        let overlaps = |a: &str, b: &str| matches_key(a, Some(b)) || matches_key(b, Some(a));
+        let rest: Vec<&Entry> = self.entries.iter().filter(|v| !(v.rule.from == entry.rule.from && v.rule.layer == entry.rule.layer)).collect();
+
+        // 输出的按键若同样被拦截, 将被再次重映射 (双重功能键点击时输出自身除外, 模拟时放行)
+        let tap_itself = entry.hold.is_some() && entry.action.output() == Some(entry.rule.from.key.as_str());
+        let mut own = entry.action.output().filter(|_| !tap_itself).into_iter().chain(entry.hold.iter().filter_map(|v| v.output()));
+        if let Some(output) = own.find(|output| overlaps(output, &entry.rule.from.key)) {
+            return Err(format!("{} is itself remapped", output));
+        }
+        for output in entry.outputs() {
+            if rest.iter().any(|v| overlaps(output, &v.rule.from.key)) {
+                return Err(format!("{} is itself remapped", output));
+            }
+        }
+        if let Some(output) = rest.iter().flat_map(|v| v.outputs()).find(|output| overlaps(output, &entry.rule.from.key)) {
+            return Err(format!("{} is the output of another rule", output));
+        }
+
+        self.entries.retain(|v| !(v.rule.from == entry.rule.from && v.rule.layer == entry.rule.layer));
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    fn remove(&mut self, from: &KeyCombination, layer: &Option<String>) -> bool {
+        let count = self.entries.len();
+        self.entries.retain(|v| !(v.rule.from == *from && v.rule.layer == *layer));
+        self.entries.len() != count
+    }
+
+    /// 已激活的层 (不重复, 先激活的在前)
+    fn active_layers(&self) -> Vec<String> {
+        let mut layers: Vec<String> = vec![];
+        for layer in self.layers.iter() {
+            if !layers.contains(layer) {
+                layers.push(layer.clone());
+            }
+        }
+        layers
+    }
+
+    fn is_active(&self, layer: &Option<String>) -> bool {
+        match layer {
+            Some(v) => self.layers.contains(v),
+            None => true
+        }
+    }
+
+    /// 需要拦截的目标 (基础层及已激活的层中的源按键)
+    fn targets(&self) -> Vec<GrabTarget> {
+        let mut targets: Vec<GrabTarget> = vec![];
+        for entry in self.entries.iter().filter(|v| self.is_active(&v.rule.layer)) {
+            if !targets.contains(&entry.target) {
+                targets.push(entry.target.clone());
+            }
+        }
+        targets
+    }
+
+    /// 匹配的规则: 后激活的层优先, 同一层中指定了辅助键的规则优先
+    fn lookup(&self, key: &str, modifiers: &Modifiers) -> Option<&Entry> {
+        let mut layers: Vec<Option<String>> = self.layers.iter().rev().map(|v| Some(v.clone())).collect();
+        layers.push(None);
+
+        for layer in layers.iter() {
+            let mut matched: Vec<&Entry> = self.entries.iter().filter(|v| v.rule.layer == *layer && v.target.matches(key, modifiers)).collect();
+            matched.sort_by_key(|v| v.target.modifiers.is_none());
+            if let Some(entry) = matched.first() {
+                return Some(entry);
+            }
+        }
+        None
+    }
+
+    /// 判定时间
+    fn deadline(&self) -> Option<Instant> {
+        self.pending.as_ref().map(|v| v.deadline)
+    }
+
+    /// 输入一次按键事件 (`key` 为 `None` 表示无法识别的按键; `held_back` 见 [crate::hook::KeyInput::held_back])
+    fn feed(&mut self, key: Option<&str>, down: bool, repeat: bool, held_back: bool, modifiers: &Modifiers, time: Instant) -> Vec<Effect> {
+        let mut effects = vec![];
+
+        // 模拟的源按键本身
+        if let Some(idx) = key.and_then(|key| self.echoes.iter().position(|v| v == key)) {
+            if !down {
+                self.echoes.remove(idx);
+            }
+            return effects;
+        }
+
+        if let Some(pending) = self.pending.take() {
+            if key == Some(pending.key.as_str()) {
+                if !down {
+                    // 点击
+                    effects.push(Effect::Release);
+                    if pending.action.output() == Some(pending.key.as_str()) {
+                        effects.push(Effect::Pass(pending.key.clone()));
+                        self.echoes.push(pending.key.clone());
+                    }
+                    let held = self.start(&pending.action, modifiers, &mut effects);
+                    self.stop(held, &mut effects);
+                } else {
+                    self.pending = Some(pending);
+                }
+                return effects;
+            }
+
+            if down {
+                // 按住期间按下了其他键: 判定为按住, 此后 (直至拦截结束) 被暂时拦截的按键均需重放
+                effects.push(Effect::Release);
+                let held = self.start(&pending.hold, modifiers, &mut effects);
+                self.held.push((pending.key, held));
+                self.replaying = true;
+            } else {
+                self.pending = Some(pending);
+            }
+        }
+
+        let key = match key {
+            Some(v) => v,
+            None => return effects
+        };
+
+        // 未被拦截的按下说明拦截已结束
+        if down && !repeat && !held_back {
+            self.replaying = false;
+        }
+        if !down {
+            if let Some(idx) = self.held.iter().position(|(v, _)| v == key) {
+                let (_, held) = self.held.remove(idx);
+                self.stop(held, &mut effects);
+            }
+            return effects;
+        }
+        if repeat {
+            if let Some((_, Held::Keys(output, _))) = self.held.iter().find(|(v, _)| v == key) {
+                effects.push(Effect::Press(output.clone()));
+            }
+            return effects;
+        }
+
+        let (action, hold) = match self.lookup(key, modifiers) {
+            Some(entry) => (entry.action.clone(), entry.hold.clone()),
+            None => {
+                // 被暂时拦截的按键: 重放 (匹配规则的按键已按规则处理)
+                if held_back && self.replaying {
+                    effects.push(Effect::Press(key.to_string()));
+                }
+                return effects;
+            }
+        };
+        match hold {
+            Some(hold) => {
+                self.pending = Some(Pending { key: key.to_string(), action, hold, deadline: time + self.tapping_term });
+            }
+            None => {
+                effects.push(Effect::Release);
+                let held = self.start(&action, modifiers, &mut effects);
+                self.held.push((key.to_string(), held));
+            }
+        }
+        effects
+    }
+
+    /// 按住超过判定时长时判定为按住
+    fn poll(&mut self, now: Instant) -> Vec<Effect> {
+        let mut effects = vec![];
+        match self.pending.take() {
+            Some(pending) if now >= pending.deadline => {
+                effects.push(Effect::Release);
+                let held = self.start(&pending.hold, &Modifiers::default(), &mut effects);
+                self.held.push((pending.key, held));
+            }
+            pending => self.pending = pending
+        }
+        effects
+    }
+
+    /// 撤销所有按下中的动作
+    fn reset(&mut self) -> Vec<Effect> {
+        let mut effects = vec![Effect::Release];
+        self.pending = None;
+        self.replaying = false;
+        for (_, held) in std::mem::take(&mut self.held) {
+            self.stop(held, &mut effects);
+        }
+        self.layers.clear();
+        effects
+    }
+
+    /// `modifiers`: 当前实际按下的辅助键 (不重复按下)
+    fn start(&mut self, action: &Action, modifiers: &Modifiers, effects: &mut Vec<Effect>) -> Held {
+        match action {
+            Action::Keys(keys) => {
+                let flag = |v: Option<bool>| match v {
+                    Some(v) => v,
+                    None => false
+                };
+                let extra = match &keys.extra {
+                    Some(v) => [
+                        ("Control", flag(v.ctrl) && !modifiers.ctrl()),
+                        ("Alt", flag(v.alt) && !modifiers.alt()),
+                        ("Shift", flag(v.shift) && !modifiers.shift()),
+                        ("Meta", flag(v.meta) && !modifiers.meta()),
+                    ],
+                    None => [("Control", false), ("Alt", false), ("Shift", false), ("Meta", false)]
+                };
+                let pressed: Vec<&'static str> = extra.iter().filter(|(_, v)| *v).map(|(name, _)| *name).collect();
+
+                for name in pressed.iter() {
+                    effects.push(Effect::Press(name.to_string()));
+                }
+                effects.push(Effect::Press(keys.key.clone()));
+                Held::Keys(keys.key.clone(), pressed)
+            }
+            Action::Layer(layer) => {
+                self.layers.push(layer.clone());
+                Held::Layer(layer.clone())
+            }
+            Action::ToggleLayer(layer) => {
+                if self.layers.contains(layer) {
+                    self.layers.retain(|v| v != layer);
+                } else {
+                    self.layers.push(layer.clone());
+                }
+                Held::Nothing
+            }
+        }
+    }
+
+    fn stop(&mut self, held: Held, effects: &mut Vec<Effect>) {
+        match held {
+            Held::Keys(key, pressed) => {
+                effects.push(Effect::Lift(key));
+                for name in pressed.iter().rev() {
+                    effects.push(Effect::Lift(name.to_string()));
+                }
+            }
+            Held::Layer(layer) => {
+                if let Some(idx) = self.layers.iter().rposition(|v| *v == layer) {
+                    self.layers.remove(idx);
+                }
+            }
+            Held::Nothing => {}
+        }
+    }
+}
+
+/// 执行输出
+fn emit(effects: Vec<Effect>) {
+    let mut player = Enigo::new();
+    for effect in effects {
+        match effect {
+            Effect::Release => grab::release(),
+            Effect::Pass(name) => grab::pass(&name),
+            Effect::Press(name) => {
+                if let Some(key) = EnigoMapper::decode_key(name) {
+                    player.key_down(key);
+                }
+            }
+            Effect::Lift(name) => {
+                if let Some(key) = EnigoMapper::decode_key(name) {
+                    player.key_up(key);
+                }
+            }
+        }
+    }
+}
+
+struct State {
+    engine: Engine,
+    enabled: bool,
+    /// 已拦截的目标
+    grabbed: Vec<GrabTarget>,
+}
+
+impl State {
+    /// 使拦截的目标与规则及已激活的层一致
+    fn sync(&mut self) -> napi::Result<()> {
+        let targets = if self.enabled { self.engine.targets() } else { vec![] };
+
+        let (keep, stale): (Vec<GrabTarget>, Vec<GrabTarget>) = self.grabbed.drain(..).partition(|v| targets.contains(v));
+        for target in stale.iter() {
+            grab::ungrab(target);
+        }
+        self.grabbed = keep;
+
+        for target in targets {
+            if !self.grabbed.contains(&target) {
+                grab::grab(target.clone())?;
+                self.grabbed.push(target);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[napi]
+pub struct Remapper {
+    /// 全局键盘钩子的订阅 -- 为 `None` 表示已结束
+    subscription: Mutex<Option<Subscription>>,
+
+    state: Arc<Mutex<State>>,
+}
+
+#[napi]
+impl Remapper {
+    /// 子线程中处理按键事件 (有待判定的双重功能键时等待至其判定时间)
+    fn setup(&self, signal: Receiver<Signal>) {
+        let state = self.state.clone();
+
+        thread::spawn(move || {
+            loop {
+                let deadline = state.lock().unwrap().engine.deadline();
+                let msg = match deadline {
+                    Some(deadline) => match signal.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                        Ok(v) => v,
+                        Err(RecvTimeoutError::Timeout) => {
+                            let mut state = state.lock().unwrap();
+                            let effects = state.engine.poll(Instant::now());
+                            emit(effects);
+                            let _ = state.sync();
+                            continue;
+                        }
+                        Err(RecvTimeoutError::Disconnected) => break
+                    },
+                    None => match signal.recv() {
+                        Ok(v) => v,
+                        Err(_) => break
+                    }
+                };
+
+                let input = match msg {
+                    Signal::Key(v) => v,
+                    Signal::Failed(_) => continue,
+                    Signal::Stop => break
+                };
+
+                let mut state = state.lock().unwrap();
+                if !state.enabled {
+                    continue;
+                }
+                let name = RdevMapper::encode_key(&input.key);
+                let effects = state.engine.feed(name.as_deref(), input.down, input.repeat, input.held_back, &input.modifiers, input.time);
+                if effects.is_empty() {
+                    continue;
+                }
+                emit(effects);
+                // 层的变化影响需要拦截的按键 (此时失败的拦截不影响已生效的规则)
+                let _ = state.sync();
+            }
+        });
+    }
+
+    #[napi(constructor)]
+    pub fn new(options: Option<RemapperOptions>) -> Self {
+        let tapping_term = match options.and_then(|v| v.tapping_term_ms) {
+            Some(v) => v,
+            None => DEFAULT_TAPPING_TERM
+        };
+
+        let (subscription, signal) = Subscription::new();
+        let instance = Remapper {
+            subscription: Mutex::new(Some(subscription)),
+            state: Arc::new(Mutex::new(State {
+                engine: Engine::new(Duration::from_millis(tapping_term as u64)),
+                enabled: true,
+                grabbed: vec![],
+            })),
+        };
+
+        instance.setup(signal);
+
+        instance
+    }
+
+    /// 添加规则 (替换同一层中 `from` 相同的规则)
+    /// 输出的按键本身被重映射 (会被再次重映射) 或无法拦截源按键时抛出异常
+    #[napi]
+    pub fn map(&self, rule: RemapRule) -> napi::Result<()> {
+        let entry = match Entry::new(rule.clone()) {
+            Ok(v) => v,
+            Err(reason) => return Err(Error::new(Status::InvalidArg, format!("Invalid rule for {}: {}!", rule.from.key, reason)))
+        };
+        grab::check_target(&entry.target)?;
+
+        let mut state = self.state.lock().unwrap();
+        let previous = state.engine.entries.iter().position(|v| v.rule.from == rule.from && v.rule.layer == rule.layer).map(|idx| state.engine.entries[idx].rule.clone());
+        if let Err(reason) = state.engine.add(entry) {
+            return Err(Error::new(Status::InvalidArg, format!("Invalid rule for {}: {}!", rule.from.key, reason)));
+        }
+
+        if let Err(err) = state.sync() {
+            // 恢复原有的规则
+            state.engine.remove(&rule.from, &rule.layer);
+            if let Some(previous) = previous {
+                if let Ok(entry) = Entry::new(previous) {
+                    let _ = state.engine.add(entry);
+                }
+            }
+            let _ = state.sync();
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// 移除 `layer` (默认为基础层) 中 `from` 的规则 (返回值表示此前是否已添加)
+    #[napi]
+    pub fn unmap(&self, from: KeyCombination, layer: Option<String>) -> napi::Result<bool> {
+        let mut state = self.state.lock().unwrap();
+        let removed = state.engine.remove(&from, &layer);
+        state.sync()?;
+
+        Ok(removed)
+    }
+
+    /// 已添加的规则 (按添加顺序)
+    #[napi(getter)]
+    pub fn rules(&self) -> napi::Result<Vec<RemapRule>> {
+        Ok(self.state.lock().unwrap().engine.entries.iter().map(|v| v.rule.clone()).collect())
+    }
+
+    /// 已激活的层 (按激活顺序)
+    #[napi(getter)]
+    pub fn active_layers(&self) -> napi::Result<Vec<String>> {
+        Ok(self.state.lock().unwrap().engine.active_layers())
+    }
+
+    /// 切换层的激活状态 (与 `toggleLayer` 动作相同), 返回值表示切换后是否已激活
+    #[napi]
+    pub fn toggle_layer(&self, layer: String) -> napi::Result<bool> {
+        let mut state = self.state.lock().unwrap();
+        let mut effects = vec![];
+        state.engine.start(&Action::ToggleLayer(layer.clone()), &Modifiers::default(), &mut effects);
+        state.sync()?;
+
+        Ok(state.engine.layers.contains(&layer))
+    }
+
+    /// 是否启用重映射 (默认为 `true`; 禁用时解除全部拦截并关闭已激活的层)
+    #[napi(getter)]
+    pub fn enabled(&self) -> napi::Result<bool> {
+        Ok(self.state.lock().unwrap().enabled)
+    }
+
+    #[napi(setter)]
+    pub fn set_enabled(&self, enabled: bool) -> napi::Result<()> {
+        let mut state = self.state.lock().unwrap();
+        if state.enabled && !enabled {
+            let effects = state.engine.reset();
+            emit(effects);
+        }
+        state.enabled = enabled;
+        state.sync()
+    }
+
+    /// 结束重映射并解除全部拦截 (与 `Observer.dispose` 相同, 必须调用!)
+    #[napi]
+    pub fn dispose(&self) -> napi::Result<()> {
+        if let Some(subscription) = self.subscription.lock().unwrap().take() {
+            drop(subscription);
+            let mut state = self.state.lock().unwrap();
+            let effects = state.engine.reset();
+            emit(effects);
+            state.engine.entries.clear();
+            state.sync()?;
+        }
+
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use crate::utils::ExtraKey;
+    use super::*;
+
+    fn keys(key: &str) -> RemapAction {
+        RemapAction { keys: Some(KeyCombination { key: String::from(key), extra: None }), layer: None, toggle_layer: None }
+    }
+
+    fn rule(from: &str, layer: Option<&str>, action: RemapAction, hold: Option<RemapAction>) -> Entry {
+        Entry::new(RemapRule {
+            from: KeyCombination { key: String::from(from), extra: None },
+            layer: layer.map(String::from),
+            action,
+            hold,
+        }).unwrap()
+    }
+
+    fn press(name: &str) -> Effect {
+        Effect::Press(String::from(name))
+    }
+
+    fn lift(name: &str) -> Effect {
+        Effect::Lift(String::from(name))
+    }
+
+    #[test]
+    fn remap() {
+        let none = Modifiers::default();
+        let now = Instant::now();
+        let mut engine = Engine::new(Duration::from_millis(200));
+        engine.add(rule("CapsLock", None, keys("Escape"), None)).unwrap();
+
+        assert_eq!(engine.feed(Some("CapsLock"), true, false, false, &none, now), vec![Effect::Release, press("Escape")]);
+        assert_eq!(engine.feed(Some("CapsLock"), true, true, false, &none, now), vec![press("Escape")]);
+        assert_eq!(engine.feed(Some("CapsLock"), false, false, false, &none, now), vec![lift("Escape")]);
+        // 输出的按键本身不被重映射
+        assert!(engine.feed(Some("Escape"), true, false, false, &none, now).is_empty());
+
+        // 输出组合键: 只按下尚未按下的辅助键
+        let combination = RemapAction {
+            keys: Some(KeyCombination { key: String::from("KeyZ"), extra: Some(ExtraKey { ctrl: Some(true), alt: None, shift: Some(true), meta: None }) }),
+            layer: None,
+            toggle_layer: None,
+        };
+        engine.add(rule("F1", None, combination, None)).unwrap();
+        let shift = Modifiers { shift_left: true, ..Default::default() };
+        assert_eq!(engine.feed(Some("F1"), true, false, false, &shift, now), vec![Effect::Release, press("Control"), press("KeyZ")]);
+        assert_eq!(engine.feed(Some("F1"), false, false, false, &shift, now), vec![lift("KeyZ"), lift("Control")]);
+
+        // 会被再次重映射的输出
+        assert!(engine.add(rule("Escape", None, keys("KeyA"), None)).is_err());
+        assert!(engine.add(rule("KeyQ", None, keys("CapsLock"), None)).is_err());
+        assert!(Entry::new(RemapRule { from: KeyCombination { key: String::from("KeyQ"), extra: None }, layer: None, action: RemapAction { keys: None, layer: None, toggle_layer: None }, hold: None }).is_err());
+    }
+
+    #[test]
+    fn dual_role() {
+        let none = Modifiers::default();
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut engine = Engine::new(Duration::from_millis(200));
+        engine.add(rule("CapsLock", None, keys("Escape"), Some(keys("Control")))).unwrap();
+        assert!(engine.targets()[0].hold);
+
+        // 点击
+        assert!(engine.feed(Some("CapsLock"), true, false, false, &none, at(0)).is_empty());
+        assert_eq!(engine.deadline(), Some(at(200)));
+        assert_eq!(engine.feed(Some("CapsLock"), false, false, false, &none, at(100)), vec![Effect::Release, press("Escape"), lift("Escape")]);
+
+        // 按住期间按下其他键: 按下 ctrl 后重放
+        engine.feed(Some("CapsLock"), true, false, false, &none, at(1000));
+        assert!(engine.feed(Some("CapsLock"), true, true, false, &none, at(1050)).is_empty());
+        assert_eq!(engine.feed(Some("KeyC"), true, false, true, &none, at(1100)), vec![Effect::Release, press("Control"), press("KeyC")]);
+        // 判定后、拦截结束前按下的按键同样重放
+        assert_eq!(engine.feed(Some("KeyV"), true, false, true, &none, at(1110)), vec![press("KeyV")]);
+        // 重放的按键经过钩子时视为重复按下
+        assert!(engine.feed(Some("KeyC"), true, true, false, &none, at(1120)).is_empty());
+        assert!(engine.feed(Some("KeyC"), false, false, false, &none, at(1150)).is_empty());
+        assert!(engine.feed(Some("KeyV"), false, false, false, &none, at(1160)).is_empty());
+        // 拦截结束后不再重放 (如其他实例的双重功能键引起的拦截)
+        assert!(engine.feed(Some("KeyX"), true, false, false, &none, at(1170)).is_empty());
+        assert!(engine.feed(Some("KeyY"), true, false, true, &none, at(1180)).is_empty());
+        assert_eq!(engine.feed(Some("CapsLock"), false, false, false, &none, at(1200)), vec![lift("Control")]);
+
+        // 按住超时
+        engine.feed(Some("CapsLock"), true, false, false, &none, at(2000));
+        assert!(engine.poll(at(2100)).is_empty());
+        assert_eq!(engine.poll(at(2200)), vec![Effect::Release, press("Control")]);
+        assert_eq!(engine.deadline(), None);
+        assert_eq!(engine.feed(Some("CapsLock"), false, false, false, &none, at(2500)), vec![lift("Control")]);
+
+        // 点击时输出自身: 放行模拟的按键, 其经过钩子时不再判定
+        let layer = RemapAction { keys: None, layer: Some(String::from("nav")), toggle_layer: None };
+        engine.add(rule("Space", None, keys("Space"), Some(layer))).unwrap();
+        engine.feed(Some("Space"), true, false, false, &none, at(3000));
+        assert_eq!(engine.feed(Some("Space"), false, false, false, &none, at(3050)), vec![Effect::Release, Effect::Pass(String::from("Space")), press("Space"), lift("Space")]);
+        assert!(engine.feed(Some("Space"), true, false, false, &none, at(3060)).is_empty());
+        assert!(engine.feed(Some("Space"), false, false, false, &none, at(3070)).is_empty());
+        assert_eq!(engine.deadline(), None);
+        engine.feed(Some("Space"), true, false, false, &none, at(3100));
+        assert_eq!(engine.deadline(), Some(at(3300)));
+    }
+
+    #[test]
+    fn layers() {
+        let none = Modifiers::default();
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let layer = |name: &str| RemapAction { keys: None, layer: Some(String::from(name)), toggle_layer: None };
+        let toggle = |name: &str| RemapAction { keys: None, layer: None, toggle_layer: Some(String::from(name)) };
+
+        let mut engine = Engine::new(Duration::from_millis(200));
+        // 输出自身同样会被再次重映射
+        assert!(engine.add(rule("Space", None, layer("nav"), Some(keys("Space")))).is_err());
+        assert!(engine.add(rule("Space", None, keys("Space"), None)).is_err());
+        engine.add(rule("Tab", None, layer("nav"), None)).unwrap();
+        engine.add(rule("KeyH", Some("nav"), keys("ArrowLeft"), None)).unwrap();
+        engine.add(rule("F12", None, toggle("num"), None)).unwrap();
+        engine.add(rule("KeyJ", Some("num"), keys("Digit4"), None)).unwrap();
+        // 未激活的层中的源按键不拦截
+        assert_eq!(engine.targets().len(), 2);
+        assert!(engine.feed(Some("KeyH"), true, false, false, &none, at(0)).is_empty());
+
+        // 按住层键
+        engine.feed(Some("Tab"), true, false, false, &none, at(100));
+        assert_eq!(engine.active_layers(), vec![String::from("nav")]);
+        assert_eq!(engine.targets().len(), 3);
+        assert_eq!(engine.feed(Some("KeyH"), true, false, false, &none, at(150)), vec![Effect::Release, press("ArrowLeft")]);
+        // 先释放层键: 按下中的按键仍按原动作释放
+        engine.feed(Some("Tab"), false, false, false, &none, at(200));
+        assert!(engine.active_layers().is_empty());
+        assert_eq!(engine.feed(Some("KeyH"), false, false, false, &none, at(250)), vec![lift("ArrowLeft")]);
+
+        // 切换层
+        engine.feed(Some("F12"), true, false, false, &none, at(300));
+        engine.feed(Some("F12"), false, false, false, &none, at(350));
+        assert_eq!(engine.feed(Some("KeyJ"), true, false, false, &none, at(360)), vec![Effect::Release, press("Digit4")]);
+        engine.feed(Some("KeyJ"), false, false, false, &none, at(370));
+        engine.feed(Some("F12"), true, false, false, &none, at(400));
+        assert!(engine.lookup("KeyJ", &none).is_none());
+
+        // 按住双重功能的层键期间被暂时拦截的按键: 按层中的规则处理
+        engine.add(rule("Backquote", None, keys("Escape"), Some(layer("nav")))).unwrap();
+        engine.feed(Some("Backquote"), true, false, false, &none, at(500));
+        assert_eq!(engine.feed(Some("KeyH"), true, false, true, &none, at(550)), vec![Effect::Release, Effect::Release, press("ArrowLeft")]);
+        assert_eq!(engine.feed(Some("KeyH"), false, false, false, &none, at(600)), vec![lift("ArrowLeft")]);
+        engine.feed(Some("Backquote"), false, false, false, &none, at(650));
+        assert!(engine.lookup("KeyH", &none).is_none());
+
+        assert_eq!(engine.reset(), vec![Effect::Release]);
+    }
+}
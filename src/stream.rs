@@ -0,0 +1,374 @@
+use std::{
+    sync::{Arc, Mutex},
+    sync::atomic::{AtomicBool, Ordering},
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use napi::{JsFunction, JsUnknown, NapiValue};
+use napi::bindgen_prelude::ToNapiValue;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use crate::capture::{capture_area, capture_screen_region, to_js_error, CaptureErrorCode, CaptureResult};
+use crate::cursor;
+use crate::frame::Frame;
+use crate::utils::{DiffFrame, DirtyRect, EncodeOptions, StreamFrame, StreamOptions};
+
+/// 默认帧率
+const DEFAULT_FPS: u32 = 30;
+/// 最大帧率
+const MAX_FPS: u32 = 120;
+/// 差异比较的默认图块边长
+const DEFAULT_TILE_SIZE: u32 = 64;
+/// 连续截图失败时的最大重试间隔
+const MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// 当前 unix 时间戳 (ms)
+pub fn now() -> i64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(v) => v.as_millis() as i64,
+        Err(_) => 0
+    }
+}
+
+/// 按选项截取一帧 (指定区域优先, 否则为指定屏幕或主屏幕)
+pub fn grab(options: &StreamOptions) -> CaptureResult<Frame> {
+    let (mut frame, region) = match &options.region {
+        Some(region) => (capture_area(region)?, region.clone()),
+        None => capture_screen_region(options.screen_id)?
+    };
+
+    if options.cursor == Some(true) {
+        cursor::composite(&mut frame, &region);
+    }
+
+    Ok(frame)
+}
+
+/// 帧间隔
+pub fn frame_interval(fps: Option<u32>) -> Duration {
+    let fps = match fps {
+        Some(v) => v.clamp(1, MAX_FPS),
+        None => DEFAULT_FPS
+    };
+    Duration::from_secs_f64(1.0 / fps as f64)
+}
+
+/// 重试不会成功的错误 (如屏幕不存在 / 没有权限), 遇到时结束截图
+fn is_fatal(code: CaptureErrorCode) -> bool {
+    !matches!(code, CaptureErrorCode::CaptureFailed | CaptureErrorCode::Timeout)
+}
+
+/// 连续失败 `failures` 次后的重试间隔 (逐次加倍, 至多 [MAX_BACKOFF])
+fn backoff(interval: Duration, failures: u32) -> Duration {
+    interval.saturating_mul(1 << failures.min(16)).min(MAX_BACKOFF.max(interval))
+}
+
+/// 截图的调度状态 (由调用方提供当前时间, 便于测试)
+struct Schedule {
+    interval: Duration,
+    seq: u32,
+    /// 自上一帧交付以来丢弃的帧数
+    dropped: u32,
+    /// 连续失败的次数
+    failures: u32,
+    /// 下一帧的时间
+    next: Instant,
+}
+
+impl Schedule {
+    fn new(interval: Duration, now: Instant) -> Self {
+        Schedule { interval, seq: 0, dropped: 0, failures: 0, next: now }
+    }
+
+    /// 在 `now` 执行一帧 (参数为帧序号与丢弃的帧数), 返回需交付的结果
+    ///
+    /// `pending` 为真 (上一帧尚未被处理) 时丢弃当前帧; 截图失败时按连续失败的次数推迟下一帧
+    fn tick<T>(&mut self, now: Instant, pending: bool, step: impl FnOnce(u32, u32) -> CaptureResult<T>) -> Option<CaptureResult<T>> {
+        // 截图耗时超过帧间隔时不追帧
+        self.next = (self.next + self.interval).max(now);
+
+        let result = if pending {
+            self.dropped += 1;
+            None
+        } else {
+            let result = step(self.seq, self.dropped);
+            match &result {
+                Ok(_) => {
+                    self.dropped = 0;
+                    self.failures = 0;
+                }
+                Err(_) => {
+                    self.failures += 1;
+                    self.next = now + backoff(self.interval, self.failures);
+                }
+            }
+            Some(result)
+        };
+
+        self.seq = self.seq.wrapping_add(1);
+        result
+    }
+}
+
+/// 连续截图 (由 [crate::capture::Captor::start_stream] 创建)
+#[napi]
+pub struct CaptureStream {
+    /// 终止信号的发送端 -- 为 `None` 表示已结束 (释放发送端即通知子线程结束)
+    stopper: Mutex<Option<Sender<()>>>,
+    /// 子线程是否已自行结束 (遇到无法恢复的错误时)
+    finished: Arc<AtomicBool>,
+}
+
+impl CaptureStream {
+    /// 在子线程中按 `interval` 执行 `step` (参数为帧序号与丢弃的帧数), 结果交由 `deliver`
+    ///
+    /// `pending` 为真 (上一帧尚未被处理) 时丢弃当前帧; 截图失败时退避重试, 遇到无法恢复的错误时结束
+    fn run<T, S, D>(interval: Duration, pending: Arc<AtomicBool>, mut step: S, mut deliver: D) -> Self
+    where
+        T: 'static + Send,
+        S: 'static + Send + FnMut(u32, u32) -> CaptureResult<T>,
+        D: 'static + Send + FnMut(CaptureResult<T>),
+    {
+        let (stopper, signal) = mpsc::channel::<()>();
+        let finished = Arc::new(AtomicBool::new(false));
+        let done = finished.clone();
+
+        thread::spawn(move || {
+            let mut schedule = Schedule::new(interval, Instant::now());
+
+            loop {
+                // 等待下一帧 -- 收到终止信号或发送端被释放时结束
+                match signal.recv_timeout(schedule.next.saturating_duration_since(Instant::now())) {
+                    Err(RecvTimeoutError::Timeout) => {}
+                    _ => break
+                }
+
+                let result = schedule.tick(Instant::now(), pending.load(Ordering::SeqCst), |seq, dropped| {
+                    pending.store(true, Ordering::SeqCst);
+                    step(seq, dropped)
+                });
+                if let Some(result) = result {
+                    let fatal = matches!(&result, Err(err) if is_fatal(err.status));
+                    deliver(result);
+                    if fatal {
+                        break;
+                    }
+                }
+            }
+            done.store(true, Ordering::SeqCst);
+        });
+
+        CaptureStream { stopper: Mutex::new(Some(stopper)), finished }
+    }
+
+    /// 在子线程中按帧率截图, 经 `produce` 处理后通过 `callback` 交付给 js
+    ///
+    /// 上一帧尚未被 js 处理时丢弃当前帧, 避免回调在事件循环中堆积
+    fn spawn<T, F>(options: StreamOptions, callback: JsFunction, mut produce: F) -> napi::Result<Self>
+    where
+        T: 'static + Send + ToNapiValue,
+        F: 'static + Send + FnMut(Frame, u32, u32) -> CaptureResult<T>,
+    {
+        let interval = frame_interval(options.fps);
+
+        // 是否有尚未被 js 处理的帧 (js 侧开始处理时复位)
+        let pending = Arc::new(AtomicBool::new(false));
+        let delivered = pending.clone();
+        // 回调参数为 `(err, frame)`, 异常的 `code` 为 [CaptureErrorCode]
+        let tsfn: ThreadsafeFunction<CaptureResult<T>, ErrorStrategy::Fatal> = callback.create_threadsafe_function(0, move |ctx| {
+            delivered.store(false, Ordering::SeqCst);
+            let args: Vec<JsUnknown> = match ctx.value {
+                Ok(value) => {
+                    let value = unsafe { JsUnknown::from_raw_unchecked(ctx.env.raw(), T::to_napi_value(ctx.env.raw(), value)?) };
+                    vec![ctx.env.get_null()?.into_unknown(), value]
+                },
+                Err(err) => vec![to_js_error(ctx.env, err), ctx.env.get_undefined()?.into_unknown()]
+            };
+            Ok(args)
+        })?;
+
+        Ok(Self::run(
+            interval,
+            pending,
+            move |seq, dropped| grab(&options).and_then(|frame| produce(frame, seq, dropped)),
+            move |result| {
+                tsfn.call(result, ThreadsafeFunctionCallMode::NonBlocking);
+            },
+        ))
+    }
+
+    /// 输出格式 (默认为 `rgba`)
+    fn format_of(options: &StreamOptions) -> EncodeOptions {
+        match &options.format {
+            Some(v) => v.clone(),
+            None => EncodeOptions { format: String::from("rgba"), compression: None, quality: None }
+        }
+    }
+
+    /// 连续截图, 每次交付整帧
+    pub fn start(options: StreamOptions, callback: JsFunction) -> napi::Result<Self> {
+        let format = Self::format_of(&options);
+
+        Self::spawn(options, callback, move |frame, seq, dropped| {
+            Ok(StreamFrame { seq, timestamp: now(), dropped, image: frame.encode(&format)? })
+        })
+    }
+
+    /// 连续截图, 与上一帧比较后仅交付发生变化的区域 (无变化时 `changed` 为 `false`, `rects` 为空)
+    pub fn start_diff(options: StreamOptions, tile_size: Option<u32>, callback: JsFunction) -> napi::Result<Self> {
+        let format = Self::format_of(&options);
+        let tile = match tile_size {
+            Some(v) => v.max(8),
+            None => DEFAULT_TILE_SIZE
+        };
+        let mut prev: Option<Frame> = None;
+
+        Self::spawn(options, callback, move |frame, seq, dropped| {
+            let rects = match &prev {
+                Some(p) => frame.diff(p, tile),
+                None => vec![(0, 0, frame.w, frame.h)],
+            };
+
+            let mut dirty = vec![];
+            for (x, y, w, h) in rects {
+                dirty.push(DirtyRect { x, y, image: frame.crop(x, y, w, h).encode(&format)? });
+            }
+
+            let payload = DiffFrame {
+                seq,
+                timestamp: now(),
+                dropped,
+                w: frame.w,
+                h: frame.h,
+                changed: !dirty.is_empty(),
+                rects: dirty,
+            };
+            prev = Some(frame);
+
+            Ok(payload)
+        })
+    }
+}
+
+#[napi]
+impl CaptureStream {
+    /// 是否正在截图 (遇到无法恢复的错误 -- 如屏幕不存在 / 没有权限 -- 时, 交付该错误后自行结束)
+    #[napi(getter)]
+    pub fn running(&self) -> napi::Result<bool> {
+        Ok(self.stopper.lock().unwrap().is_some() && !self.finished.load(Ordering::SeqCst))
+    }
+
+    /// 结束截图 (必须调用! 否则子线程会一直持有回调函数)
+    #[napi]
+    pub fn stop(&self) -> napi::Result<()> {
+        // 释放发送端即通知子线程结束
+        self.stopper.lock().unwrap().take();
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    #[test]
+    fn interval() {
+        assert_eq!(frame_interval(Some(10)), Duration::from_millis(100));
+        assert_eq!(frame_interval(Some(0)), Duration::from_secs(1));
+        assert_eq!(frame_interval(None), Duration::from_secs_f64(1.0 / 30.0));
+    }
+
+    /// 帧序号与丢弃的帧数 (截图失败时为 `None`)
+    fn frame(result: Option<CaptureResult<(u32, u32)>>) -> Option<Option<(u32, u32)>> {
+        result.map(|v| v.ok())
+    }
+
+    #[test]
+    fn schedule() {
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let mut schedule = Schedule::new(Duration::from_millis(10), at(0));
+
+        assert_eq!(frame(schedule.tick(at(0), false, |seq, dropped| Ok((seq, dropped)))), Some(Some((0, 0))));
+        assert_eq!(schedule.next, at(10));
+        // 上一帧尚未被处理: 丢弃
+        assert_eq!(frame(schedule.tick(at(10), true, |seq, dropped| Ok((seq, dropped)))), None);
+        assert_eq!(frame(schedule.tick(at(20), false, |seq, dropped| Ok((seq, dropped)))), Some(Some((2, 1))));
+        // 截图耗时超过帧间隔时不追帧
+        assert_eq!(frame(schedule.tick(at(55), false, |seq, dropped| Ok((seq, dropped)))), Some(Some((3, 0))));
+        assert_eq!(schedule.next, at(55));
+    }
+
+    #[test]
+    fn transient_error() {
+        assert_eq!(backoff(Duration::from_millis(10), 1), Duration::from_millis(20));
+        assert_eq!(backoff(Duration::from_millis(10), 3), Duration::from_millis(80));
+        assert_eq!(backoff(Duration::from_millis(10), 40), MAX_BACKOFF);
+        assert_eq!(backoff(Duration::from_secs(10), 2), Duration::from_secs(10));
+        assert!(!is_fatal(CaptureErrorCode::CaptureFailed));
+        assert!(is_fatal(CaptureErrorCode::InvalidArg));
+
+        // 失败后退避, 恢复后按原帧率截图
+        let start = Instant::now();
+        let at = |ms: u64| start + Duration::from_millis(ms);
+        let busy = |_: u32, _: u32| -> CaptureResult<(u32, u32)> { Err(napi::Error::new(CaptureErrorCode::CaptureFailed, String::from("busy"))) };
+        let mut schedule = Schedule::new(Duration::from_millis(10), at(0));
+
+        assert_eq!(frame(schedule.tick(at(0), false, busy)), Some(None));
+        assert_eq!(schedule.next, at(20));
+        assert_eq!(frame(schedule.tick(at(20), false, busy)), Some(None));
+        assert_eq!(schedule.next, at(60));
+        assert_eq!(frame(schedule.tick(at(60), false, |seq, dropped| Ok((seq, dropped)))), Some(Some((2, 0))));
+        assert_eq!(schedule.next, at(70));
+        assert_eq!(frame(schedule.tick(at(70), false, busy)), Some(None));
+        assert_eq!(schedule.next, at(90));
+    }
+
+    /// 交付时立即视为已处理, 返回 (截图流, 交付的结果)
+    fn collect(mut results: impl 'static + Send + FnMut(u32) -> CaptureResult<u32>) -> (CaptureStream, Arc<Mutex<Vec<CaptureResult<u32>>>>) {
+        let delivered = Arc::new(Mutex::new(vec![]));
+        let sink = delivered.clone();
+        let pending = Arc::new(AtomicBool::new(false));
+        let handled = pending.clone();
+
+        let stream = CaptureStream::run(Duration::from_millis(10), pending, move |seq, _| results(seq), move |result| {
+            sink.lock().unwrap().push(result);
+            handled.store(false, Ordering::SeqCst);
+        });
+        (stream, delivered)
+    }
+
+    /// 等待子线程结束
+    fn join(stream: &CaptureStream) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !stream.finished.load(Ordering::SeqCst) {
+            assert!(Instant::now() < deadline, "the stream thread did not finish");
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn stop() {
+        let (stream, delivered) = collect(|seq| Ok(seq));
+        assert!(stream.running().unwrap());
+
+        stream.stop().unwrap();
+        assert!(!stream.running().unwrap());
+        join(&stream);
+        // 子线程结束后不再交付
+        let count = delivered.lock().unwrap().len();
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(delivered.lock().unwrap().len(), count);
+    }
+
+    #[test]
+    fn fatal_error() {
+        let (stream, delivered) = collect(|_| Err(napi::Error::new(CaptureErrorCode::InvalidArg, String::from("Screen with id 9 does not exist!"))));
+        join(&stream);
+
+        // 仅交付一次, 随后结束
+        assert_eq!(delivered.lock().unwrap().len(), 1);
+        assert!(!stream.running().unwrap());
+    }
+}
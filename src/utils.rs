@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
+
 /// 辅助键 (ctrl / shift / alt 中的 0/1/2/3 个)
 #[napi(object)]
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize)]
 pub struct ExtraKey {
     pub ctrl: Option<bool>,
     /// windows/linux -- `alt`; macos -- `option`
@@ -12,26 +14,127 @@ pub struct ExtraKey {
 
 /// 组合键情况 (目标键 + 辅助键)
 #[napi(object)]
-#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize)]
 pub struct KeyCombination {
     /// 目标键
-    #[napi(ts_type = "'F1' | 'F2' | 'F3' | 'F4' | 'F5' | 'F6' | 'F7' | 'F8' | 'F9' | 'F10' | 'F11' | 'F12' | 'Digit0' | 'Digit1' | 'Digit2' | 'Digit3' | 'Digit4' | 'Digit5' | 'Digit6' | 'Digit7' | 'Digit8' | 'Digit9' | 'KeyA' | 'KeyB' | 'KeyC' | 'KeyD' | 'KeyE' | 'KeyF' | 'KeyG' | 'KeyH' | 'KeyI' | 'KeyJ' | 'KeyK' | 'KeyL' | 'KeyM' | 'KeyN' | 'KeyO' | 'KeyP' | 'KeyQ' | 'KeyR' | 'KeyS' | 'KeyT' | 'KeyU' | 'KeyV' | 'KeyW' | 'KeyX' | 'KeyY' | 'KeyZ' | 'Meta' | 'Escape' | 'Tab' | 'CapsLock' | 'Shift' | 'Control' | 'Alt' | 'Space' | 'ArrowUp' | 'ArrowRight' | 'ArrowDown' | 'ArrowLeft' | 'Enter' | 'Backspace' | 'Delete' | 'Home' | 'PageUp' | 'PageDown' | 'End' | 'Backquote' | 'Minus' | 'Equal' | 'BracketLeft' | 'BracketRight' | 'Comma' | 'Period' | 'Semicolon' | 'Quote' | 'Slash' | 'BackSlash' | 'ShiftLeft' | 'ShiftRight' | 'ControlLeft' | 'ControlRight' | 'AltLeft' | 'AltRight' | 'Numpad0' | 'Numpad1' | 'Numpad2' | 'Numpad3' | 'Numpad4' | 'Numpad5' | 'Numpad6' | 'Numpad7' | 'Numpad8' | 'Numpad9' | 'NumpadAdd' | 'NumpadSubtract' | 'NumpadMultiply' | 'NumpadDivide'")]
+    #[napi(ts_type = "'F1' | 'F2' | 'F3' | 'F4' | 'F5' | 'F6' | 'F7' | 'F8' | 'F9' | 'F10' | 'F11' | 'F12' | 'Digit0' | 'Digit1' | 'Digit2' | 'Digit3' | 'Digit4' | 'Digit5' | 'Digit6' | 'Digit7' | 'Digit8' | 'Digit9' | 'KeyA' | 'KeyB' | 'KeyC' | 'KeyD' | 'KeyE' | 'KeyF' | 'KeyG' | 'KeyH' | 'KeyI' | 'KeyJ' | 'KeyK' | 'KeyL' | 'KeyM' | 'KeyN' | 'KeyO' | 'KeyP' | 'KeyQ' | 'KeyR' | 'KeyS' | 'KeyT' | 'KeyU' | 'KeyV' | 'KeyW' | 'KeyX' | 'KeyY' | 'KeyZ' | 'Meta' | 'Escape' | 'Tab' | 'CapsLock' | 'Shift' | 'Control' | 'Alt' | 'Space' | 'ArrowUp' | 'ArrowRight' | 'ArrowDown' | 'ArrowLeft' | 'Enter' | 'Backspace' | 'Delete' | 'Home' | 'PageUp' | 'PageDown' | 'End' | 'Backquote' | 'Minus' | 'Equal' | 'BracketLeft' | 'BracketRight' | 'Comma' | 'Period' | 'Semicolon' | 'Quote' | 'Slash' | 'BackSlash' | 'IntlBackslash' | 'Insert' | 'PrintScreen' | 'Pause' | 'NumLock' | 'ScrollLock' | 'Fn' | 'ShiftLeft' | 'ShiftRight' | 'ControlLeft' | 'ControlRight' | 'AltLeft' | 'AltRight' | 'Numpad0' | 'Numpad1' | 'Numpad2' | 'Numpad3' | 'Numpad4' | 'Numpad5' | 'Numpad6' | 'Numpad7' | 'Numpad8' | 'Numpad9' | 'NumpadAdd' | 'NumpadSubtract' | 'NumpadMultiply' | 'NumpadDivide' | 'NumpadEnter' | 'NumpadDecimal'")]
     pub key: String,
     /// 辅助键 见[ExtraKey]
     pub extra: Option<ExtraKey>,
 }
 
+/// 具名快捷键绑定 (见 `HotkeyManager`)
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize)]
+pub struct HotkeyBinding {
+    /// 快捷键名称 (唯一)
+    pub name: String,
+    /// 注册方 (如插件名)
+    pub owner: String,
+    /// 组合键 (在目标键按下时触发)
+    pub combination: KeyCombination,
+}
+
+/// 快捷键冲突
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct HotkeyConflict {
+    /// 冲突类型: 与已注册的快捷键相同 / 与已注册的快捷键互相包含 (如 `ctrl + K` 与 `ctrl + shift + K`) / 与系统快捷键相同
+    #[napi(ts_type = "'duplicate' | 'subsumed' | 'system'")]
+    pub kind: String,
+    /// 冲突的快捷键名称 (`kind` 为 `'system'` 时为 null)
+    pub name: Option<String>,
+    /// 冲突的快捷键注册方 (`kind` 为 `'system'` 时为 null)
+    pub owner: Option<String>,
+    /// 冲突的组合键
+    pub combination: KeyCombination,
+    /// 说明
+    pub description: String,
+}
+
+/// 按键序列选项
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct SequenceOptions {
+    /// 相邻两步之间的最大间隔 (毫秒), 默认 `1000`
+    pub timeout_ms: Option<u32>,
+}
+
+/// 进行中的按键序列
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct PendingSequence {
+    /// `onSequence` 返回的句柄
+    pub id: u32,
+    /// 已按下的步骤
+    pub matched: Vec<KeyCombination>,
+    /// 等待按下的下一步
+    pub next: KeyCombination,
+    /// 剩余等待时间 (毫秒)
+    pub remaining_ms: u32,
+}
+
+/// 缩写展开规则 (见 `TextExpander`)
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct Expansion {
+    /// 缩写 (如 `;sig`), 输入完成时立即展开
+    pub abbreviation: String,
+    /// 展开后的文本, 支持占位符: `{date}` / `{time}` / `{date:%Y/%m/%d}` (strftime 格式) / `{clipboard}` / `{cursor}` (展开后光标位置); `{{` / `}}` 为花括号本身
+    pub expansion: String,
+    /// 仅在这些应用中生效 (进程名 / 应用名, 不区分大小写, 忽略 `.exe`), 默认在所有应用中生效
+    pub apps: Option<Vec<String>>,
+}
+
+/// 缩写展开选项
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct ExpanderOptions {
+    /// 输出方式: 模拟键盘输入 / 经剪贴板粘贴 (粘贴后恢复剪贴板中原有的文本), 默认 `'type'`
+    #[napi(ts_type = "'type' | 'paste'")]
+    pub method: Option<String>,
+    /// 是否按缩写的大小写调整展开结果 (`;Sig` -- 首字母大写, `;SIG` -- 全部大写), 默认 `true`
+    pub propagate_case: Option<bool>,
+    /// 不生效的应用 (如密码管理器 / 终端)
+    pub excluded_apps: Option<Vec<String>>,
+}
+
+/// 事件发生后的修饰键 (区分左右) 与锁定键状态
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct KeyState {
+    pub ctrl_left: bool,
+    pub ctrl_right: bool,
+    pub alt_left: bool,
+    pub alt_right: bool,
+    pub shift_left: bool,
+    pub shift_right: bool,
+    pub meta_left: bool,
+    pub meta_right: bool,
+    pub caps_lock: bool,
+    /// macos 下始终为 `false`
+    pub num_lock: bool,
+    /// macos 下始终为 `false`
+    pub scroll_lock: bool,
+}
+
 /// 按键事件 (目标键 + 辅助键 + 按键状态)
 #[napi(object)]
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
 pub struct KeyEv {
     /// 目标键
-    #[napi(ts_type = "'F1' | 'F2' | 'F3' | 'F4' | 'F5' | 'F6' | 'F7' | 'F8' | 'F9' | 'F10' | 'F11' | 'F12' | 'Digit0' | 'Digit1' | 'Digit2' | 'Digit3' | 'Digit4' | 'Digit5' | 'Digit6' | 'Digit7' | 'Digit8' | 'Digit9' | 'KeyA' | 'KeyB' | 'KeyC' | 'KeyD' | 'KeyE' | 'KeyF' | 'KeyG' | 'KeyH' | 'KeyI' | 'KeyJ' | 'KeyK' | 'KeyL' | 'KeyM' | 'KeyN' | 'KeyO' | 'KeyP' | 'KeyQ' | 'KeyR' | 'KeyS' | 'KeyT' | 'KeyU' | 'KeyV' | 'KeyW' | 'KeyX' | 'KeyY' | 'KeyZ' | 'Meta' | 'Escape' | 'Tab' | 'CapsLock' | 'Shift' | 'Control' | 'Alt' | 'Space' | 'ArrowUp' | 'ArrowRight' | 'ArrowDown' | 'ArrowLeft' | 'Enter' | 'Backspace' | 'Delete' | 'Home' | 'PageUp' | 'PageDown' | 'End' | 'Backquote' | 'Minus' | 'Equal' | 'BracketLeft' | 'BracketRight' | 'Comma' | 'Period' | 'Semicolon' | 'Quote' | 'Slash' | 'BackSlash' | 'ShiftLeft' | 'ShiftRight' | 'ControlLeft' | 'ControlRight' | 'AltLeft' | 'AltRight' | 'Numpad0' | 'Numpad1' | 'Numpad2' | 'Numpad3' | 'Numpad4' | 'Numpad5' | 'Numpad6' | 'Numpad7' | 'Numpad8' | 'Numpad9' | 'NumpadAdd' | 'NumpadSubtract' | 'NumpadMultiply' | 'NumpadDivide'")]
+    #[napi(ts_type = "'F1' | 'F2' | 'F3' | 'F4' | 'F5' | 'F6' | 'F7' | 'F8' | 'F9' | 'F10' | 'F11' | 'F12' | 'Digit0' | 'Digit1' | 'Digit2' | 'Digit3' | 'Digit4' | 'Digit5' | 'Digit6' | 'Digit7' | 'Digit8' | 'Digit9' | 'KeyA' | 'KeyB' | 'KeyC' | 'KeyD' | 'KeyE' | 'KeyF' | 'KeyG' | 'KeyH' | 'KeyI' | 'KeyJ' | 'KeyK' | 'KeyL' | 'KeyM' | 'KeyN' | 'KeyO' | 'KeyP' | 'KeyQ' | 'KeyR' | 'KeyS' | 'KeyT' | 'KeyU' | 'KeyV' | 'KeyW' | 'KeyX' | 'KeyY' | 'KeyZ' | 'Meta' | 'Escape' | 'Tab' | 'CapsLock' | 'Shift' | 'Control' | 'Alt' | 'Space' | 'ArrowUp' | 'ArrowRight' | 'ArrowDown' | 'ArrowLeft' | 'Enter' | 'Backspace' | 'Delete' | 'Home' | 'PageUp' | 'PageDown' | 'End' | 'Backquote' | 'Minus' | 'Equal' | 'BracketLeft' | 'BracketRight' | 'Comma' | 'Period' | 'Semicolon' | 'Quote' | 'Slash' | 'BackSlash' | 'IntlBackslash' | 'Insert' | 'PrintScreen' | 'Pause' | 'NumLock' | 'ScrollLock' | 'Fn' | 'ShiftLeft' | 'ShiftRight' | 'ControlLeft' | 'ControlRight' | 'AltLeft' | 'AltRight' | 'Numpad0' | 'Numpad1' | 'Numpad2' | 'Numpad3' | 'Numpad4' | 'Numpad5' | 'Numpad6' | 'Numpad7' | 'Numpad8' | 'Numpad9' | 'NumpadAdd' | 'NumpadSubtract' | 'NumpadMultiply' | 'NumpadDivide' | 'NumpadEnter' | 'NumpadDecimal'")]
     pub key: String,
     /// 辅助键 见[ExtraKey]
     pub extra: Option<ExtraKey>,
     /// 是否是按下状态 (默认为 `false`)
     pub down: Option<bool>,
+    /// 修饰键与锁定键的详细状态 (仅由 `onKeyAll` 回调提供, 注册时忽略)
+    pub state: Option<KeyState>,
+    /// 未知按键 (`key` 为 `'Unknown'`) 的原始键码 (仅由 `onKeyAll` 回调提供, 注册时忽略)
+    pub code: Option<u32>,
+    /// 是否是长按产生的自动重复按下 (仅由 `onKeyAll` 回调提供, 注册时忽略)
+    pub is_repeat: Option<bool>,
 }
 
 /// KeyEvRegister(keycode, ctrl, alt, shift, meta, down)
@@ -73,6 +176,18 @@ impl KeyEvRegister {
         })
     }
 
+    /// 组合键的按下事件
+    pub fn from_combination(combination: KeyCombination) -> Self {
+        KeyEvRegister::from_key_ev(KeyEv {
+            key: combination.key,
+            extra: combination.extra,
+            down: Some(true),
+            state: None,
+            code: None,
+            is_repeat: None,
+        })
+    }
+
     pub fn to_key_ev(&self) -> KeyEv {
         KeyEv {
             key: self.0.clone(),
@@ -83,6 +198,9 @@ impl KeyEvRegister {
                 meta: Some(self.4),
             }),
             down: Some(self.5),
+            state: None,
+            code: None,
+            is_repeat: None,
         }
     }
 }
@@ -107,6 +225,64 @@ pub struct MouseLocation {
     pub y: i32,
 }
 
+/// 鼠标移动选项
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct MouseMoveOptions {
+    /// 是否使用相对定位 (相对当前鼠标位置), 默认 `false`
+    pub relative: Option<bool>,
+    /// 坐标相对于指定 id 的屏幕左上角 (仅绝对定位时有效, 默认相对于主屏幕左上角)
+    pub screen: Option<u32>,
+    /// 是否将目标位置限制在可见桌面内, 绝对定位默认 `true`, 相对定位默认 `false`
+    pub clamp: Option<bool>,
+}
+
+/// 鼠标位置选项
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct MouseLocationOptions {
+    /// 返回相对于指定 id 的屏幕左上角的坐标 (默认相对于主屏幕左上角)
+    pub screen: Option<u32>,
+}
+
+/// 单个屏幕的截图结果 (`data` 与 `code` 有且仅有一个不为 null)
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct ScreenCapture {
+    /// 屏幕唯一标识
+    pub id: u32,
+    /// 截图数据 (png)
+    pub data: Option<Vec<u8>>,
+    /// 失败原因 (`'no-display' | 'permission-denied' | 'capture-failed' | 'encode-failed' | 'invalid-arg' | 'timeout'`)
+    pub code: Option<String>,
+    /// 失败详情
+    pub message: Option<String>,
+}
+
+/// 颜色 (RGBA, 各分量 0 ~ 255)
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+/// 矩形区域 (虚拟桌面坐标, 以主屏幕左上角为原点)
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct Rect {
+    /// 左上角 x 坐标
+    pub x: i32,
+    /// 左上角 y 坐标
+    pub y: i32,
+    /// 宽度
+    pub w: u32,
+    /// 高度
+    pub h: u32,
+}
+
 /// 图像数据
 #[napi(object)]
 #[derive(Debug, Eq, PartialEq, Hash, Clone)]
@@ -117,6 +293,270 @@ pub struct RawImage {
     pub h: u32,
     /// 图像字节
     pub bytes: Vec<u8>,
+    /// 每行字节数 (仅原始像素格式 `rgba` / `bgra` 有值)
+    pub stride: Option<u32>,
+    /// 图像格式 (默认为 `rgba`)
+    #[napi(ts_type = "'rgba' | 'bgra' | 'png' | 'jpeg' | 'webp' | 'qoi'")]
+    pub format: Option<String>,
+}
+
+/// 图像编码选项
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct EncodeOptions {
+    /// 图像格式 (`rgba` / `bgra` 为原始像素)
+    #[napi(ts_type = "'rgba' | 'bgra' | 'png' | 'jpeg' | 'webp' | 'qoi'")]
+    pub format: String,
+    /// png 压缩等级 (0 ~ 9, 默认为 `6`)
+    pub compression: Option<u32>,
+    /// jpeg / webp 质量 (1 ~ 100, 默认为 `80`; webp 为 `100` 时使用无损编码)
+    pub quality: Option<u32>,
+}
+
+/// 屏幕信息
+#[napi(object)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct ScreenInfo {
+    /// 屏幕唯一标识
+    pub id: u32,
+    /// 屏幕左上角在虚拟桌面中的 x 坐标
+    pub x: i32,
+    /// 屏幕左上角在虚拟桌面中的 y 坐标
+    pub y: i32,
+    /// 屏幕宽度
+    pub width: u32,
+    /// 屏幕高度
+    pub height: u32,
+    /// 缩放比例 (如 `1.5` 表示 150%)
+    pub scale_factor: f64,
+    /// 顺时针旋转角度 (0 / 90 / 180 / 270)
+    pub rotation: f64,
+    /// 刷新率 (Hz)
+    pub refresh_rate: f64,
+    /// 是否是主屏幕
+    pub is_primary: bool,
+}
+
+/// 物理坐标 (截图中的像素坐标, 相对于所在屏幕的左上角)
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct PhysicalPoint {
+    /// 所在屏幕的 id
+    pub screen_id: u32,
+    /// x 方向 (像素)
+    pub x: i32,
+    /// y 方向 (像素)
+    pub y: i32,
+}
+
+/// 连续截图选项
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct StreamOptions {
+    /// 屏幕 id (默认为主屏幕, 指定 `region` 时忽略)
+    pub screen_id: Option<u32>,
+    /// 帧率 (1 ~ 120, 默认为 `30`)
+    pub fps: Option<u32>,
+    /// 截取区域 (虚拟桌面坐标)
+    pub region: Option<Rect>,
+    /// 输出格式 (默认为 `rgba`)
+    pub format: Option<EncodeOptions>,
+    /// 是否绘制光标 (默认为 `false`; 无法读取光标时不绘制)
+    pub cursor: Option<bool>,
+}
+
+/// 连续截图的单帧
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct StreamFrame {
+    /// 帧序号 (从 `0` 开始, 含被丢弃的帧)
+    pub seq: u32,
+    /// 截取时间 (unix 时间戳, ms)
+    pub timestamp: i64,
+    /// 自上一帧以来因 js 处理不及时而丢弃的帧数
+    pub dropped: u32,
+    /// 图像数据
+    pub image: RawImage,
+}
+
+/// 录屏选项
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct RecordOptions {
+    /// 输出文件路径
+    pub path: String,
+    /// 屏幕 id (默认为主屏幕, 指定 `region` 时忽略)
+    pub screen_id: Option<u32>,
+    /// 录制区域 (虚拟桌面坐标)
+    pub region: Option<Rect>,
+    /// 帧率 (默认为 `15`; `gif` 最高为 `50`)
+    pub fps: Option<u32>,
+    /// 编码 (`mjpeg` 输出 AVI 文件; 默认按文件扩展名推断, `.gif` 为 `gif`, 其余为 `mjpeg`)
+    #[napi(ts_type = "'mjpeg' | 'gif'")]
+    pub codec: Option<String>,
+    /// `mjpeg` 的压缩质量 (1 ~ 100, 默认为 `80`)
+    pub quality: Option<u32>,
+    /// 是否绘制光标 (默认为 `false`; 无法读取光标时不绘制)
+    pub cursor: Option<bool>,
+}
+
+/// 录制完成的文件
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct Recording {
+    /// 文件路径
+    pub path: String,
+    /// 编码
+    pub codec: String,
+    /// 画面宽度 (像素)
+    pub w: u32,
+    /// 画面高度 (像素)
+    pub h: u32,
+    /// 实际截取的帧数
+    pub frames: u32,
+    /// 时长 (ms)
+    pub duration: u32,
+    /// 是否因文件达到大小上限 (AVI 为 1 GB) 而提前结束
+    pub truncated: bool,
+}
+
+/// 发生变化的区域 (相对于整帧左上角)
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct DirtyRect {
+    /// 左上角 x 坐标
+    pub x: u32,
+    /// 左上角 y 坐标
+    pub y: u32,
+    /// 区域内的图像数据
+    pub image: RawImage,
+}
+
+/// 差异比较模式下的单帧
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct DiffFrame {
+    /// 帧序号 (从 `0` 开始, 含被丢弃的帧)
+    pub seq: u32,
+    /// 截取时间 (unix 时间戳, ms)
+    pub timestamp: i64,
+    /// 自上一帧以来因 js 处理不及时而丢弃的帧数
+    pub dropped: u32,
+    /// 整帧宽度
+    pub w: u32,
+    /// 整帧高度
+    pub h: u32,
+    /// 与上一帧相比是否有变化 (首帧总为 `true`, 且 `rects` 为整帧)
+    pub changed: bool,
+    /// 发生变化的区域
+    pub rects: Vec<DirtyRect>,
+}
+
+/// 以图找图选项
+#[napi(object)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct FindImageOptions {
+    /// 查找区域 (虚拟桌面坐标, 默认为整个虚拟桌面)
+    pub region: Option<Rect>,
+    /// 最低匹配度 (0 ~ 1, 默认为 `0.9`; 纯色模板按颜色的接近程度计算)
+    pub threshold: Option<f64>,
+    /// 是否按灰度匹配 (更快, 但忽略颜色差异; 默认为 `false`)
+    pub grayscale: Option<bool>,
+    /// 模板的缩放比例 (默认为 `[1]`)
+    pub scales: Option<Vec<f64>>,
+}
+
+/// 以图找图的匹配结果 (虚拟桌面坐标)
+#[napi(object)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct ImageMatch {
+    /// 左上角 x 坐标
+    pub x: i32,
+    /// 左上角 y 坐标
+    pub y: i32,
+    /// 宽度
+    pub w: u32,
+    /// 高度
+    pub h: u32,
+    /// 匹配度 (归一化互相关系数, 越接近 `1` 越相似)
+    pub confidence: f64,
+    /// 匹配时模板的缩放比例
+    pub scale: f64,
+}
+
+/// 光标信息
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct CursorInfo {
+    /// 光标位置 x (虚拟桌面坐标, 即热点所在位置)
+    pub x: i32,
+    /// 光标位置 y (虚拟桌面坐标, 即热点所在位置)
+    pub y: i32,
+    /// 热点相对于光标图像左上角的 x 偏移
+    pub hot_x: u32,
+    /// 热点相对于光标图像左上角的 y 偏移
+    pub hot_y: u32,
+    /// 光标图像 (rgba)
+    pub image: RawImage,
+}
+
+/// 标注 (坐标均为图像像素坐标, 以图像左上角为原点)
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct Annotation {
+    /// 标注类型: 矩形框 / 箭头 / 文字 / 模糊 / 马赛克 / 裁剪 (裁剪后, 其后的标注以裁剪后的图像为准)
+    #[napi(ts_type = "'rect' | 'arrow' | 'text' | 'blur' | 'pixelate' | 'crop'")]
+    pub kind: String,
+    /// 作用区域 (`rect` / `blur` / `pixelate` / `crop` 必填)
+    pub rect: Option<Rect>,
+    /// 箭头起点 (`arrow` 必填)
+    pub from: Option<MouseLocation>,
+    /// 箭头终点, 即箭头所指处 (`arrow` 必填)
+    pub to: Option<MouseLocation>,
+    /// 文字左上角位置 (`text` 必填)
+    pub at: Option<MouseLocation>,
+    /// 文字内容 (`text` 必填, 仅支持 ASCII 字符, 其余字符显示为方框; `\n` 换行)
+    pub text: Option<String>,
+    /// 线条 / 文字颜色 (默认为红色)
+    pub color: Option<Color>,
+    /// 填充色 (`rect` 的内部 / `text` 的背景, 默认不填充)
+    pub fill: Option<Color>,
+    /// 线宽 (默认为 `2`)
+    pub thickness: Option<u32>,
+    /// `text`: 字号倍数 (默认为 `2`, 即每个字 10x14 像素); `blur`: 模糊半径 (默认为 `8`); `pixelate`: 色块边长 (默认为 `10`)
+    pub size: Option<u32>,
+}
+
+/// 文字识别选项
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct OcrOptions {
+    /// 语言 (默认为 `eng`, 目前仅支持拉丁字母的英文)
+    pub lang: Option<String>,
+    /// 文字检测模型 (`text-detection.rten`) 的本地路径
+    pub detection_model: Option<String>,
+    /// 文字识别模型 (`text-recognition.rten`) 的本地路径
+    pub recognition_model: Option<String>,
+}
+
+/// 识别出的单词 (位置为虚拟桌面坐标)
+#[napi(object)]
+#[derive(Debug, PartialEq, Clone)]
+pub struct RecognizedWord {
+    /// 文字内容
+    pub text: String,
+    /// 左上角 x 坐标
+    pub x: i32,
+    /// 左上角 y 坐标
+    pub y: i32,
+    /// 宽度
+    pub w: u32,
+    /// 高度
+    pub h: u32,
+    /// 置信度 (0 ~ 1, 单词区域内各像素被检测为文字的平均概率)
+    pub confidence: f64,
+    /// 所在行的序号 (从 0 开始, 同一行的单词序号相同)
+    pub line: u32,
 }
 
 /// 剪切板单项的存储结构
@@ -147,9 +587,46 @@ mod test {
                 meta: Some(false),
             }),
             down: Some(true),
+            state: None,
+            code: None,
+            is_repeat: None,
         };
 
         let p = KeyEvRegister::from_key_ev(ev);
         println!("p: {:#?}", p);
     }
 }
+
+/// 重映射的动作 (`keys` / `layer` / `toggleLayer` 三者取其一)
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct RemapAction {
+    /// 输出的组合键 (源按键按下期间保持按下)
+    pub keys: Option<KeyCombination>,
+    /// 源按键按下期间激活的层
+    pub layer: Option<String>,
+    /// 按下时开启 / 关闭的层
+    pub toggle_layer: Option<String>,
+}
+
+/// 重映射规则 (见 `Remapper`)
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct RemapRule {
+    /// 源按键 (未指定辅助键时不论辅助键状态)
+    pub from: KeyCombination,
+    /// 规则所在的层 (默认为基础层, 始终生效)
+    pub layer: Option<String>,
+    /// 按下时的动作 (指定了 `hold` 时为点击时的动作)
+    pub action: RemapAction,
+    /// 按住时的动作 (按住超过 `tappingTermMs`, 或按住期间按下了其他键)
+    pub hold: Option<RemapAction>,
+}
+
+/// `Remapper` 选项
+#[napi(object)]
+#[derive(Debug, Eq, PartialEq, Hash, Clone)]
+pub struct RemapperOptions {
+    /// 区分点击与按住的时长 (毫秒), 默认 `200`
+    pub tapping_term_ms: Option<u32>,
+}
@@ -0,0 +1,150 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+use napi::Error;
+use crate::capture::{capture_area, find_image, pixels_at, CaptureErrorCode, CaptureResult, CaptureTask};
+use crate::frame::Frame;
+use crate::utils::{Color, FindImageOptions, ImageMatch, MouseLocation, Rect};
+
+/// 默认轮询间隔 -- ms
+pub const DEFAULT_INTERVAL: u32 = 100;
+/// 区域变化检测的图块边长
+const CHANGE_TILE_SIZE: u32 = 16;
+
+/// 每隔 `interval` 调用一次 `probe`, 直到其返回结果或超时
+///
+/// 在 libuv 的工作线程中执行, 不阻塞 js 主线程
+pub fn poll<T>(timeout: Duration, interval: Duration, mut probe: impl FnMut() -> CaptureResult<Option<T>>) -> CaptureResult<T> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        if let Some(v) = probe()? {
+            return Ok(v);
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(Error::new(CaptureErrorCode::Timeout, format!("The condition was not met within {}ms!", timeout.as_millis())));
+        }
+        thread::sleep(interval.min(deadline - now));
+    }
+}
+
+/// 两个颜色的 R / G / B 分量之差是否均不超过 `tolerance` (忽略透明通道)
+pub fn color_matches(a: &Color, b: &Color, tolerance: u8) -> bool {
+    a.r.abs_diff(b.r) <= tolerance && a.g.abs_diff(b.g) <= tolerance && a.b.abs_diff(b.b) <= tolerance
+}
+
+/// 等待图像出现 (见 [crate::capture::Captor::wait_for_image])
+pub struct WaitForImage {
+    pub template: Frame,
+    pub options: FindImageOptions,
+    pub timeout: Duration,
+    pub interval: Duration,
+}
+
+impl CaptureTask for WaitForImage {
+    type Output = ImageMatch;
+
+    fn run(&mut self) -> CaptureResult<Self::Output> {
+        poll(self.timeout, self.interval, || {
+            Ok(find_image(&self.template, &self.options)?.into_iter().next())
+        })
+    }
+}
+
+/// 等待指定点变为指定颜色 (见 [crate::capture::Captor::wait_for_pixel])
+pub struct WaitForPixel {
+    pub point: MouseLocation,
+    pub color: Color,
+    pub tolerance: u8,
+    pub timeout: Duration,
+    pub interval: Duration,
+}
+
+impl CaptureTask for WaitForPixel {
+    type Output = Color;
+
+    fn run(&mut self) -> CaptureResult<Self::Output> {
+        poll(self.timeout, self.interval, || {
+            let current = pixels_at(&[self.point.clone()])?.remove(0);
+            Ok(if color_matches(&current, &self.color, self.tolerance) { Some(current) } else { None })
+        })
+    }
+}
+
+/// 等待区域内发生变化 (见 [crate::capture::Captor::wait_for_region_change])
+pub struct WaitForRegionChange {
+    pub region: Rect,
+    pub timeout: Duration,
+    pub interval: Duration,
+}
+
+impl CaptureTask for WaitForRegionChange {
+    type Output = Vec<Rect>;
+
+    fn run(&mut self) -> CaptureResult<Self::Output> {
+        let region = &self.region;
+        let mut baseline = capture_area(region)?;
+
+        poll(self.timeout, self.interval, || {
+            let current = capture_area(region)?;
+            let changed = current.diff(&baseline, CHANGE_TILE_SIZE);
+            if changed.is_empty() {
+                baseline = current;
+                return Ok(None);
+            }
+
+            // 截图为物理像素, 需换算回虚拟桌面坐标
+            let ratio_x = region.w as f64 / current.w.max(1) as f64;
+            let ratio_y = region.h as f64 / current.h.max(1) as f64;
+            Ok(Some(changed.into_iter().map(|(x, y, w, h)| Rect {
+                x: region.x + (x as f64 * ratio_x).floor() as i32,
+                y: region.y + (y as f64 * ratio_y).floor() as i32,
+                w: (w as f64 * ratio_x).ceil() as u32,
+                h: (h as f64 * ratio_y).ceil() as u32,
+            }).collect()))
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod unit_test {
+    use super::*;
+
+    #[test]
+    fn poll_timeout() {
+        let started = Instant::now();
+        let mut calls = 0;
+        let result: CaptureResult<()> = poll(Duration::from_millis(50), Duration::from_millis(10), || {
+            calls += 1;
+            Ok(None)
+        });
+
+        assert_eq!(result.unwrap_err().status, CaptureErrorCode::Timeout);
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert!(calls >= 2);
+    }
+
+    #[test]
+    fn poll_resolve() {
+        let mut calls = 0;
+        let result = poll(Duration::from_secs(1), Duration::from_millis(1), || {
+            calls += 1;
+            Ok(if calls == 3 { Some(calls) } else { None })
+        });
+
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn tolerance() {
+        let a = Color { r: 10, g: 200, b: 30, a: 255 };
+        let b = Color { r: 14, g: 196, b: 30, a: 0 };
+
+        assert!(color_matches(&a, &b, 4));
+        assert!(!color_matches(&a, &b, 3));
+    }
+}